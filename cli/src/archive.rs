@@ -0,0 +1,229 @@
+use chrono::Datelike;
+use clap::ArgMatches;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use mos::export::parquet::ParquetArchive;
+
+use crate::Error;
+
+/// Files kept open at once. Past that the oldest is finished and its partition goes on
+/// in a new part file
+const MAX_OPEN_FILES: usize = 256;
+
+/// Failures listed for each kind in the summary
+const EXAMPLES_PER_KIND: usize = 3;
+
+/// Where the bulletins come from
+enum Inputs {
+    Dir(PathBuf),
+    #[cfg(feature = "s3")]
+    S3 {
+        bulletins: mos::storage::s3::Bulletins,
+        prefix: String,
+    },
+}
+
+impl Inputs {
+    /// A directory, or "s3://bucket/prefix"
+    fn open(source: &str) -> Result<Inputs, Error> {
+        if source.starts_with("s3://") {
+            #[cfg(feature = "s3")]
+            {
+                let (bucket, prefix) = mos::storage::s3::parse_url(source)
+                    .ok_or_else(|| format!("not an s3://bucket/prefix url: {}", source))?;
+                return Ok(Inputs::S3 {
+                    bulletins: mos::storage::s3::Bulletins::from_env(bucket)?,
+                    prefix: prefix.to_string(),
+                });
+            }
+            #[cfg(not(feature = "s3"))]
+            return Err("reading from S3 needs a build with the s3 feature".into());
+        }
+        Ok(Inputs::Dir(PathBuf::from(source)))
+    }
+
+    /// Every file under the directory, or every key under the prefix, sorted
+    fn names(&self) -> Result<Vec<String>, Error> {
+        let mut names = match self {
+            Inputs::Dir(dir) => {
+                let mut files = Vec::new();
+                walk(dir, &mut files)?;
+                files
+                    .into_iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect()
+            }
+            #[cfg(feature = "s3")]
+            Inputs::S3 { bulletins, prefix } => bulletins.keys(prefix)?,
+        };
+        names.sort();
+        Ok(names)
+    }
+
+    fn reader(&self, name: &str) -> Result<Box<dyn BufRead>, mos::error::Error> {
+        match self {
+            Inputs::Dir(_) => match File::open(name) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(source) => Err(mos::error::Error::Io {
+                    path: Some(PathBuf::from(name)),
+                    source,
+                }),
+            },
+            #[cfg(feature = "s3")]
+            Inputs::S3 { bulletins, .. } => Ok(Box::new(io::Cursor::new(bulletins.get(name)?))),
+        }
+    }
+}
+
+/// A station's year of cycles, written to part files one after another
+#[derive(Default)]
+struct Partition {
+    file: Option<ParquetArchive<File>>,
+    parts: usize,
+}
+
+/// Converts a directory or S3 prefix of archived bulletins to Parquet partitioned by
+/// station and year, as `station=KFIT/year=2020/part-0.parquet`, reporting progress
+/// and then what failed to parse on stderr
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let out = Path::new(matches.value_of("out").unwrap_or("."));
+    let inputs = Inputs::open(matches.value_of("source").unwrap_or("."))?;
+    let names = inputs.names()?;
+    let progress_line = atty::is(atty::Stream::Stderr);
+
+    let mut partitions: HashMap<(String, i32), Partition> = HashMap::new();
+    // Partitions with an open file, oldest first
+    let mut open: Vec<(String, i32)> = Vec::new();
+    let mut files_written = 0;
+    let mut bulletins = 0;
+    let mut rows = 0;
+    let mut failures: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+
+    for (i, name) in names.iter().enumerate() {
+        if progress_line {
+            eprint!("\r\x1b[K[{}/{}] {}", i + 1, names.len(), name);
+        } else {
+            eprintln!("[{}/{}] {}", i + 1, names.len(), name);
+        }
+
+        let reader = match inputs.reader(name) {
+            Ok(reader) => reader,
+            Err(err) => {
+                failures
+                    .entry(err.code())
+                    .or_default()
+                    .push(format!("{}: {}", name, err));
+                continue;
+            }
+        };
+        for (j, parsed) in mos::parse_stream(reader).enumerate() {
+            let mos = match parsed {
+                Ok(mos) => mos,
+                Err(err) => {
+                    failures.entry(err.code()).or_default().push(format!(
+                        "{} (bulletin {}): {}",
+                        name,
+                        j + 1,
+                        err
+                    ));
+                    continue;
+                }
+            };
+
+            let key = (mos.meta.icao.clone(), mos.meta.timestamp.year());
+            let partition = partitions.entry(key.clone()).or_default();
+            if partition.file.is_none() {
+                let dir = out
+                    .join(format!("station={}", key.0))
+                    .join(format!("year={}", key.1));
+                fs::create_dir_all(&dir)?;
+                let path = dir.join(format!("part-{}.parquet", partition.parts));
+                partition.file = Some(ParquetArchive::new(File::create(path)?)?);
+                partition.parts += 1;
+                files_written += 1;
+                open.push(key);
+            }
+            if let Some(file) = partition.file.as_mut() {
+                file.append(&mos)?;
+            }
+            bulletins += 1;
+            rows += mos.entries.len();
+
+            if open.len() > MAX_OPEN_FILES {
+                let oldest = open.remove(0);
+                close(&mut partitions, &oldest)?;
+            }
+        }
+    }
+    for key in &open {
+        close(&mut partitions, key)?;
+    }
+    if progress_line {
+        eprintln!();
+    }
+
+    let stderr = io::stderr();
+    let mut summary = stderr.lock();
+    writeln!(
+        summary,
+        "{} bulletins ({} rows) from {} inputs, in {} files over {} partitions under {}",
+        bulletins,
+        rows,
+        names.len(),
+        files_written,
+        partitions.len(),
+        out.display()
+    )?;
+    let failed: usize = failures.values().map(Vec::len).sum();
+    if failed > 0 {
+        writeln!(summary, "{} failed:", failed)?;
+        for (code, examples) in &failures {
+            writeln!(summary, "  {} ({})", code, examples.len())?;
+            for example in examples.iter().take(EXAMPLES_PER_KIND) {
+                writeln!(summary, "    {}", example)?;
+            }
+            if examples.len() > EXAMPLES_PER_KIND {
+                writeln!(
+                    summary,
+                    "    and {} more",
+                    examples.len() - EXAMPLES_PER_KIND
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn close(
+    partitions: &mut HashMap<(String, i32), Partition>,
+    key: &(String, i32),
+) -> Result<(), Error> {
+    if let Some(file) = partitions
+        .get_mut(key)
+        .and_then(|partition| partition.file.take())
+    {
+        file.close()?;
+    }
+    Ok(())
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk(&path, files)?;
+        } else if !is_hidden(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map_or(false, |name| name.starts_with('.'))
+}
@@ -0,0 +1,115 @@
+use clap::ArgMatches;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use mos::client::Endpoint;
+use mos::stations::Aliases;
+
+use crate::Error;
+
+/// Defaults from `~/.config/cia-backed/config.toml`. A flag beats the matching
+/// `CIA_BACKED_*` environment variable, which beats the file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Station used when a command is given no ICAO
+    pub station: Option<String>,
+    pub units: Option<String>,
+    pub format: Option<String>,
+    pub product: Option<String>,
+    /// Named lists of stations, e.g. `route = ["KFIT", "KBED"]`, accepted by `fetch` in
+    /// place of an ICAO
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Other names for stations, e.g. `home = "KFIT"`, accepted anywhere an ICAO is
+    pub aliases: Aliases,
+    /// Where bulletins are fetched from, preferred first, by the names in
+    /// `mos::client::SOURCES`. Just the NWS when empty
+    pub sources: Vec<String>,
+}
+
+impl Config {
+    /// Reads the file given by `--config`, `CIA_BACKED_CONFIG` or the default path, then
+    /// applies the environment. A missing file at the default path is not an error
+    pub fn load(matches: &ArgMatches) -> Result<Config, Error> {
+        let path = match matches
+            .value_of("config")
+            .map(String::from)
+            .or_else(|| env::var("CIA_BACKED_CONFIG").ok())
+        {
+            Some(path) => Some(PathBuf::from(path)),
+            None => default_path().filter(|path| path.is_file()),
+        };
+        let mut config = match path {
+            Some(path) => {
+                let text = fs::read_to_string(&path)
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+                toml::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))?
+            }
+            None => Config::default(),
+        };
+
+        for (var, field) in &mut [
+            ("CIA_BACKED_STATION", &mut config.station),
+            ("CIA_BACKED_UNITS", &mut config.units),
+            ("CIA_BACKED_FORMAT", &mut config.format),
+            ("CIA_BACKED_PRODUCT", &mut config.product),
+        ] {
+            if let Ok(val) = env::var(*var) {
+                **field = Some(val);
+            }
+        }
+        if let Ok(sources) = env::var("CIA_BACKED_SOURCES") {
+            config.sources = sources.split(',').map(String::from).collect();
+        }
+
+        Ok(config)
+    }
+
+    /// The flag's value, else the configured one, else `default`
+    pub fn value<'a>(&'a self, matches: &'a ArgMatches, name: &str, default: &'a str) -> &'a str {
+        let configured = match name {
+            "units" => self.units.as_deref(),
+            "format" => self.format.as_deref(),
+            "product" => self.product.as_deref(),
+            _ => None,
+        };
+        matches.value_of(name).or(configured).unwrap_or(default)
+    }
+
+    /// The stations named by the `icao` argument, or the configured station without one.
+    /// A group or alias expands to its stations
+    pub fn stations(&self, matches: &ArgMatches) -> Result<Vec<String>, Error> {
+        let name = match matches.value_of("icao").or_else(|| self.station.as_deref()) {
+            Some(name) => name,
+            None => return Err("no station given and no default station configured".into()),
+        };
+        match self.groups.get(name) {
+            Some(group) => Ok(group.clone()),
+            None => Ok(self.aliases.resolve(name)),
+        }
+    }
+
+    /// The configured sources, for `Failover`
+    pub fn endpoints(&self) -> Result<Vec<Endpoint>, Error> {
+        if self.sources.is_empty() {
+            return Ok(vec![Endpoint::nws()]);
+        }
+        let mut endpoints = Vec::with_capacity(self.sources.len());
+        for name in &self.sources {
+            endpoints.push(Endpoint::named(name)?);
+        }
+        Ok(endpoints)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/cia-backed/config.toml`, falling back to `~/.config`
+fn default_path() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("cia-backed").join("config.toml"))
+}
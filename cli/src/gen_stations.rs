@@ -0,0 +1,149 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use mos::batch::{Limits, Throttle};
+use mos::client::{ReqwestTransport, Transport};
+use mos::Product;
+
+use crate::Error;
+
+const API: &str = "https://api.weather.gov";
+
+/// Wait between requests, to stay well under the API's rate limit
+const REQUEST_DELAY_MS: u64 = 250;
+
+const FEET_PER_METER: f64 = 3.280_84;
+
+const SUPPORTED_HEADER: &str = "\
+# Stations with MOS guidance, one per line as \"ICAO PRODUCT=LOCATION...\" with the
+# api.weather.gov location of the collective carrying each product. Written by
+# `cia-backed gen-stations` from the MAV and MEX collectives of the api.weather.gov text
+# products; rerun it rather than editing by hand. It's read by `stations::supports()`
+# and `products::fetch()`; the client fetches guidance for stations missing from it too
+";
+
+const STATIONS_HEADER: &str = "\
+# MOS station metadata, tab-separated as ICAO, latitude, longitude, elevation (ft MSL),
+# IANA time zone, forecast office and name. Written by `cia-backed gen-stations` from
+# the api.weather.gov station and point metadata of the stations in the MAV and MEX
+# collectives; rerun it rather than editing by hand
+";
+
+/// Rewrites mos/data/mos_stations.txt and mos/data/stations.txt from api.weather.gov:
+/// the stations come from the latest MAV and MEX collectives of every location, their
+/// position, elevation, name and time zone from `/stations`, and their forecast office
+/// from `/points`. Stations the metadata endpoints don't know are listed as supported
+/// but left out of the station table
+pub fn run() -> Result<(), Error> {
+    let transport = Throttle::new(
+        ReqwestTransport::default(),
+        Limits {
+            max_in_flight: 1,
+            per_host: 1,
+            delay: Duration::from_millis(REQUEST_DELAY_MS),
+        },
+    );
+
+    // Each station's products with the location of the collective carrying them
+    let mut products: BTreeMap<String, Vec<(Product, String)>> = BTreeMap::new();
+    for &product in &[Product::Mav, Product::Mex] {
+        for location in mos::products::locations(&transport, product)? {
+            let latest = match get_json(
+                &transport,
+                &format!(
+                    "{}/products/types/{}/locations/{}/latest",
+                    API,
+                    product.code(),
+                    location
+                ),
+            ) {
+                Ok(latest) => latest,
+                Err(err) => {
+                    eprintln!("skipping {} {}: {}", product.code(), location, err);
+                    continue;
+                }
+            };
+            let text = latest["productText"].as_str().unwrap_or_default();
+            for mos in mos::parse_stream(text.as_bytes()).filter_map(Result::ok) {
+                let listed = products.entry(mos.meta.icao).or_default();
+                if listed.iter().all(|(seen, _)| *seen != product) {
+                    listed.push((product, location.clone()));
+                }
+            }
+            eprintln!(
+                "{} {}: {} stations so far",
+                product.code(),
+                location,
+                products.len()
+            );
+        }
+    }
+
+    let mut supported = String::from(SUPPORTED_HEADER);
+    let mut stations = String::from(STATIONS_HEADER);
+    for (icao, listed) in &products {
+        let names: Vec<String> = listed
+            .iter()
+            .map(|(product, location)| format!("{}={}", product.code().to_lowercase(), location))
+            .collect();
+        writeln!(supported, "{} {}", icao, names.join(" "))?;
+
+        match station_line(&transport, icao) {
+            Ok(line) => writeln!(stations, "{}", line)?,
+            Err(err) => eprintln!("no metadata for {}: {}", icao, err),
+        }
+    }
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../mos/data");
+    for (name, contents) in &[("mos_stations.txt", supported), ("stations.txt", stations)] {
+        let path = dir.join(name);
+        fs::write(&path, contents)?;
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+/// The station's line of stations.txt
+fn station_line<T: Transport>(transport: &T, icao: &str) -> Result<String, Error> {
+    let station = get_json(transport, &format!("{}/stations/{}", API, icao))?;
+    let coordinates = &station["geometry"]["coordinates"];
+    let (lon, lat) = match (coordinates[0].as_f64(), coordinates[1].as_f64()) {
+        (Some(lon), Some(lat)) => (lon, lat),
+        _ => return Err("no coordinates".into()),
+    };
+    let properties = &station["properties"];
+    let elevation = properties["elevation"]["value"]
+        .as_f64()
+        .ok_or("no elevation")?;
+    let tz = properties["timeZone"].as_str().ok_or("no time zone")?;
+    let name = properties["name"].as_str().unwrap_or(icao);
+
+    let point = get_json(transport, &format!("{}/points/{:.4},{:.4}", API, lat, lon))?;
+    let wfo = point["properties"]["cwa"]
+        .as_str()
+        .ok_or("no forecast office")?;
+
+    Ok(format!(
+        "{}\t{:.4}\t{:.4}\t{}\t{}\t{}\t{}",
+        icao,
+        lat,
+        lon,
+        (elevation * FEET_PER_METER).round(),
+        tz,
+        wfo,
+        name.replace('\t', " ")
+    ))
+}
+
+fn get_json<T: Transport>(transport: &T, url: &str) -> Result<Value, Error> {
+    let res = transport.get(url)?;
+    if !(200..300).contains(&res.status) {
+        return Err(format!("{} from {}", res.status, url).into());
+    }
+    Ok(serde_json::from_str(&res.body)?)
+}
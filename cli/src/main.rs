@@ -0,0 +1,354 @@
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+use std::time::Duration;
+
+use mos::batch::{Batch, Limits};
+use mos::client::ReqwestTransport;
+use mos::units::Units;
+use mos::{ParseOptions, Product, MOS};
+
+#[cfg(feature = "parquet")]
+mod archive;
+mod config;
+mod gen_stations;
+mod output;
+mod table;
+mod watch;
+
+use config::Config;
+use output::Format;
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+fn main() {
+    let matches = App::new("cia-backed")
+        .about("Fetch and parse NWS MOS guidance")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .global(true)
+                .takes_value(true)
+                .possible_values(output::FORMATS)
+                .help("Output format [default: json]"),
+        )
+        .arg(
+            Arg::with_name("units")
+                .long("units")
+                .short("u")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["imperial", "metric"])
+                .help("Temperature and wind speed units [default: imperial]"),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .global(true)
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Config file [default: ~/.config/cia-backed/config.toml]"),
+        )
+        .subcommand(
+            SubCommand::with_name("fetch")
+                .about("Fetch and parse the latest bulletin for a station")
+                .arg(
+                    Arg::with_name("icao")
+                        .help("Station or group, e.g. KFIT [default: configured station]"),
+                )
+                .arg(
+                    Arg::with_name("product")
+                        .long("product")
+                        .short("p")
+                        .takes_value(true)
+                        .possible_values(&["mav", "mex"])
+                        .help("MOS product to fetch [default: mav]"),
+                )
+                .arg(
+                    Arg::with_name("slack")
+                        .long("slack")
+                        .takes_value(true)
+                        .value_name("WEBHOOK_URL")
+                        .help("Also post a summary to a Slack incoming webhook"),
+                )
+                .arg(
+                    Arg::with_name("discord")
+                        .long("discord")
+                        .takes_value(true)
+                        .value_name("WEBHOOK_URL")
+                        .help("Also post a summary embed to a Discord webhook"),
+                )
+                .arg(
+                    Arg::with_name("max-in-flight")
+                        .long("max-in-flight")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("8")
+                        .help("Stations of a group fetched at once"),
+                )
+                .arg(
+                    Arg::with_name("per-host")
+                        .long("per-host")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("4")
+                        .help("Requests in flight to any one host"),
+                )
+                .arg(
+                    Arg::with_name("delay")
+                        .long("delay")
+                        .takes_value(true)
+                        .value_name("MILLISECONDS")
+                        .default_value("0")
+                        .help("Least time between the starts of two requests to a host"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("watch")
+                .about("Poll for new model cycles and print each one as it appears")
+                .arg(
+                    Arg::with_name("icao")
+                        .help("Station identifier, e.g. KFIT [default: configured station]"),
+                )
+                .arg(
+                    Arg::with_name("product")
+                        .long("product")
+                        .short("p")
+                        .takes_value(true)
+                        .possible_values(&["mav", "mex"])
+                        .help("MOS product to fetch [default: mav]"),
+                )
+                .arg(
+                    Arg::with_name("interval")
+                        .long("interval")
+                        .short("i")
+                        .takes_value(true)
+                        .value_name("SECONDS")
+                        .default_value("300")
+                        .help("Polling interval"),
+                )
+                .arg(
+                    Arg::with_name("exec")
+                        .long("exec")
+                        .takes_value(true)
+                        .value_name("COMMAND")
+                        .help(
+                            "Run a shell command for each new cycle instead of printing, with \
+                             the forecast on stdin and MOS_ICAO/MOS_CYCLE set",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("webhooks")
+                        .long("webhooks")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("JSON list of webhooks and the triggers that post to them"),
+                )
+                .arg(
+                    Arg::with_name("mqtt")
+                        .long("mqtt")
+                        .takes_value(true)
+                        .value_name("HOST[:PORT]")
+                        .help("Publish entries and next-hour values to an MQTT broker"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Show how the forecast changed between two bulletins")
+                .arg(
+                    Arg::with_name("old")
+                        .required(true)
+                        .help("Bulletin file of the older cycle"),
+                )
+                .arg(
+                    Arg::with_name("new")
+                        .required(true)
+                        .help("Bulletin file of the newer cycle"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("stations")
+                .about("List the stations with embedded location metadata"),
+        )
+        .subcommand(
+            SubCommand::with_name("parse")
+                .about("Parse a bulletin from a file, or stdin if the file is -")
+                .arg(Arg::with_name("file").required(true))
+                .arg(
+                    Arg::with_name("strict")
+                        .long("strict")
+                        .help("Fail on cells that can't be parsed instead of skipping them"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("archive")
+                .about("Convert a directory or S3 prefix of archived bulletins to Parquet")
+                .arg(
+                    Arg::with_name("source")
+                        .required(true)
+                        .help("Directory of bulletin files, or s3://bucket/prefix"),
+                )
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .short("o")
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .required(true)
+                        .help("Directory the station=/year= partitions are written under"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("gen-snapshots")
+                .about("Rewrite the JSON snapshots of the parser test fixtures")
+                .setting(AppSettings::Hidden),
+        )
+        .subcommand(
+            SubCommand::with_name("gen-stations")
+                .about("Rewrite the embedded station tables from api.weather.gov")
+                .setting(AppSettings::Hidden),
+        )
+        .get_matches();
+
+    if let Err(err) = run(&matches) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let (name, sub) = matches.subcommand();
+    let sub = match sub {
+        Some(sub) => sub,
+        None => return Err("no subcommand given".into()),
+    };
+    let config = Config::load(sub)?;
+    let format = config.value(sub, "format", "json").parse::<Format>()?;
+    let units = config.value(sub, "units", "imperial").parse::<Units>()?;
+
+    let forecasts = match name {
+        "fetch" => fetch(sub, &config)?,
+        "parse" => vec![parse(sub)?],
+        "watch" => return watch::run(sub, format, units, &config),
+        "stations" => return stations(format),
+        "diff" => return diff(sub, format),
+        "gen-snapshots" => return gen_snapshots(),
+        "gen-stations" => return gen_stations::run(),
+        #[cfg(feature = "parquet")]
+        "archive" => return archive::run(sub),
+        #[cfg(not(feature = "parquet"))]
+        "archive" => return Err("archive needs a build with the parquet feature".into()),
+        _ => return Err(format!("unknown subcommand: {}", name).into()),
+    };
+
+    for mos in forecasts {
+        println!("{}", output::render(&mos.in_units(units), format)?);
+    }
+    Ok(())
+}
+
+fn fetch(matches: &ArgMatches, config: &Config) -> Result<Vec<MOS>, Error> {
+    let product = config.value(matches, "product", "mav").parse::<Product>()?;
+    let limits = Limits {
+        max_in_flight: matches.value_of("max-in-flight").unwrap_or("8").parse()?,
+        per_host: matches.value_of("per-host").unwrap_or("4").parse()?,
+        delay: Duration::from_millis(matches.value_of("delay").unwrap_or("0").parse()?),
+    };
+    let batch = Batch::with_endpoints(ReqwestTransport::default(), limits, config.endpoints()?);
+
+    let mut forecasts = Vec::new();
+    for fetched in batch.fetch_all(&config.stations(matches)?, product) {
+        let mos = fetched?;
+        if let Some(webhook_url) = matches.value_of("slack") {
+            mos::notify::slack::post(webhook_url, &mos)?;
+        }
+        if let Some(webhook_url) = matches.value_of("discord") {
+            mos::notify::discord::post(webhook_url, &mos)?;
+        }
+        forecasts.push(mos);
+    }
+
+    Ok(forecasts)
+}
+
+fn parse(matches: &ArgMatches) -> Result<MOS, Error> {
+    let opts = ParseOptions {
+        strict: matches.is_present("strict"),
+        ..ParseOptions::default()
+    };
+    parse_file(matches.value_of("file").unwrap_or("-"), &opts)
+}
+
+fn parse_file(path: &str, opts: &ParseOptions) -> Result<MOS, Error> {
+    let text = read_input(path)?;
+    Ok(MOS::new_with(
+        text.trim_start_matches(|c| c == '\n' || c == '\r'),
+        opts,
+    )?)
+}
+
+fn diff(matches: &ArgMatches, format: Format) -> Result<(), Error> {
+    let opts = ParseOptions::default();
+    let old = parse_file(matches.value_of("old").unwrap_or_default(), &opts)?;
+    let new = parse_file(matches.value_of("new").unwrap_or_default(), &opts)?;
+    let diffs = old.diff(&new);
+
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&diffs)?),
+        _ => {
+            println!(
+                "{} {} → {}",
+                new.meta.icao,
+                old.meta.timestamp.format("%d %HZ"),
+                new.meta.timestamp.format("%d %HZ")
+            );
+            for diff in diffs {
+                println!("{}", diff);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn stations(format: Format) -> Result<(), Error> {
+    let stations = mos::stations::all();
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(stations)?),
+        _ => {
+            for station in stations {
+                println!(
+                    "{}  {:>8.4} {:>9.4} {:>6}ft  {}",
+                    station.icao, station.lat, station.lon, station.elevation, station.name
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn gen_snapshots() -> Result<(), Error> {
+    let dir = mos::testing::snapshots_dir();
+    fs::create_dir_all(&dir)?;
+    for fixture in mos::testing::snapshot_fixtures() {
+        let path = dir.join(mos::testing::snapshot_name(&fixture));
+        fs::write(&path, mos::testing::snapshot(&fixture))?;
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+fn read_input(path: &str) -> Result<String, Error> {
+    if path == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
+}
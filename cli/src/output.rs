@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use mos::export::json::SerializeOptions;
+use mos::MOS;
+
+use crate::table;
+use crate::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Json,
+    Raw,
+    Table,
+    Csv,
+    Ics,
+    GeoJson,
+    Summary,
+    Html,
+    Markdown,
+}
+
+pub const FORMATS: &[&str] = &[
+    "json", "raw", "table", "csv", "ics", "geojson", "summary", "html", "markdown",
+];
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "raw" => Ok(Format::Raw),
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "ics" => Ok(Format::Ics),
+            "geojson" => Ok(Format::GeoJson),
+            "summary" => Ok(Format::Summary),
+            "html" => Ok(Format::Html),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!("unknown output format: {}", s).into()),
+        }
+    }
+}
+
+pub fn render(mos: &MOS, format: Format) -> Result<String, Error> {
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(
+            &mos.to_json_with(&SerializeOptions::default())?,
+        )?),
+        Format::Raw => Ok(mos.raw.clone()),
+        Format::Table => Ok(table::render(mos)),
+        Format::Csv => Ok(mos.to_csv()),
+        Format::Ics => Ok(mos.to_ical()),
+        Format::GeoJson => Ok(serde_json::to_string_pretty(&mos.to_geojson()?)?),
+        Format::Summary => Ok(mos.summarize()),
+        Format::Html => Ok(mos.to_html()),
+        Format::Markdown => Ok(mos.to_markdown()),
+    }
+}
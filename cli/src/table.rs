@@ -0,0 +1,126 @@
+use colored::{ColoredString, Colorize};
+
+use mos::units::Units;
+use mos::{MOSEntry, MOS};
+
+/// PoP at or above which precipitation columns are highlighted
+const WET_POP: isize = 30;
+/// Sustained wind at or above which the wind column is bolded. MOS carries no gust
+/// forecast, so this stands in for gusty conditions
+const GUSTY_WSP: isize = 20;
+/// `GUSTY_WSP` in km/h, for metric copies
+const GUSTY_WSP_KMH: isize = 37;
+
+const HEADER: &[(&str, usize)] = &[
+    ("TIME", 7),
+    ("TMP", 4),
+    ("DPT", 4),
+    ("CLD", 4),
+    ("WIND", 9),
+    ("P06", 4),
+    ("P12", 4),
+    ("TYP", 4),
+    ("CIG", 4),
+    ("VIS", 4),
+    ("OBV", 4),
+];
+
+/// Renders one aligned row per valid time, colored when writing to a terminal
+pub fn render(mos: &MOS) -> String {
+    if !atty::is(atty::Stream::Stdout) {
+        colored::control::set_override(false);
+    }
+
+    let mut lines = vec![
+        format!(
+            "{} {}",
+            mos.meta.icao.bold(),
+            mos.meta.timestamp.format("%Y-%m-%d %HZ")
+        ),
+        HEADER
+            .iter()
+            .map(|(name, width)| format!("{:>width$}", name, width = width))
+            .collect::<Vec<String>>()
+            .join(" "),
+    ];
+    lines.extend(mos.entries.iter().map(row));
+
+    lines.join("\n")
+}
+
+fn row(entry: &MOSEntry) -> String {
+    let cells: Vec<ColoredString> = vec![
+        cell(&entry.timestamp.format("%a %HZ").to_string(), 7).normal(),
+        cell(&num(entry.tmp), 4).normal(),
+        cell(&num(entry.dpt), 4).normal(),
+        cell(&text(&entry.cld), 4).normal(),
+        wind(entry),
+        pop(entry.p06),
+        pop(entry.p12),
+        typ(entry),
+        cig(entry.cig),
+        cell(&num(entry.vis), 4).normal(),
+        cell(&text(&entry.obv), 4).normal(),
+    ];
+
+    cells
+        .iter()
+        .map(|cell| cell.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Pads before coloring so escape codes don't throw off the alignment
+fn cell(val: &str, width: usize) -> String {
+    format!("{:>width$}", val, width = width)
+}
+
+fn num(val: Option<isize>) -> String {
+    val.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn text(val: &Option<String>) -> String {
+    val.clone().unwrap_or_default()
+}
+
+fn wind(entry: &MOSEntry) -> ColoredString {
+    let val = match (entry.wdr, entry.wsp) {
+        (Some(wdr), Some(wsp)) => format!("{:03}/{:02}", wdr * 10, wsp),
+        _ => String::new(),
+    };
+    let padded = cell(&val, 9);
+    let gusty = match entry.units {
+        Units::Imperial => GUSTY_WSP,
+        Units::Metric => GUSTY_WSP_KMH,
+    };
+    match entry.wsp {
+        Some(wsp) if wsp >= gusty => padded.bold(),
+        _ => padded.normal(),
+    }
+}
+
+fn pop(val: Option<isize>) -> ColoredString {
+    let padded = cell(&num(val), 4);
+    match val {
+        Some(pop) if pop >= WET_POP => padded.blue(),
+        _ => padded.normal(),
+    }
+}
+
+fn typ(entry: &MOSEntry) -> ColoredString {
+    let padded = cell(&text(&entry.typ), 4);
+    match entry.p06.or(entry.p12) {
+        Some(pop) if pop >= WET_POP => padded.blue(),
+        _ => padded.normal(),
+    }
+}
+
+/// Red below 500 ft (LIFR), amber from 500 to 900 ft (IFR)
+fn cig(val: Option<isize>) -> ColoredString {
+    let padded = cell(&num(val), 4);
+    match val {
+        Some(cig) if cig <= 2 => padded.red().bold(),
+        Some(3) => padded.yellow(),
+        _ => padded.normal(),
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use clap::ArgMatches;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use mos::client::{ReqwestTransport, Transport};
+use mos::failover::Failover;
+use mos::notify::webhook::Dispatcher;
+use mos::units::Units;
+use mos::{error, Product, MOS};
+
+use crate::config::Config;
+use crate::output::{self, Format};
+use crate::Error;
+
+/// Polls for the station's bulletin and reports every new model cycle, forever. Webhooks,
+/// MQTT and the hook get it in the same units as the output
+pub fn run(
+    matches: &ArgMatches,
+    format: Format,
+    units: Units,
+    config: &Config,
+) -> Result<(), Error> {
+    let stations = config.stations(matches)?;
+    let icao = match stations.as_slice() {
+        [icao] => icao.as_str(),
+        _ => return Err("watch takes a single station, not a group".into()),
+    };
+    let product = config.value(matches, "product", "mav").parse::<Product>()?;
+    let interval = matches
+        .value_of("interval")
+        .unwrap_or("300")
+        .parse::<u64>()?;
+    let hook = matches.value_of("exec");
+    let mut webhooks = match matches.value_of("webhooks") {
+        Some(path) => Some(Dispatcher::from_json(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+    #[cfg(feature = "mqtt")]
+    let mut mqtt = match matches.value_of("mqtt") {
+        Some(broker) => Some(connect_mqtt(broker, icao)?),
+        None => None,
+    };
+    #[cfg(not(feature = "mqtt"))]
+    {
+        if matches.is_present("mqtt") {
+            return Err("--mqtt needs a build with the mqtt feature".into());
+        }
+    }
+
+    let failover = Failover::new(ReqwestTransport::default(), config.endpoints()?);
+    let mut last_cycle: Option<DateTime<Utc>> = None;
+    loop {
+        let fetched = poll(&failover, icao, product, units);
+        if let (Ok(mos), Some(webhooks)) = (&fetched, webhooks.as_mut()) {
+            if let Err(err) = webhooks.dispatch(mos) {
+                eprintln!("error: webhook failed: {}", err);
+            }
+        }
+        // Every poll rather than every cycle, so the next-hour values stay current
+        #[cfg(feature = "mqtt")]
+        {
+            if let (Ok(mos), Some(mqtt)) = (&fetched, mqtt.as_mut()) {
+                if let Err(err) = mqtt.publish(mos) {
+                    eprintln!("error: mqtt publish failed: {}", err);
+                }
+            }
+        }
+
+        match fetched {
+            Ok(mos) if Some(mos.meta.timestamp) != last_cycle => {
+                last_cycle = Some(mos.meta.timestamp);
+                let rendered = output::render(&mos, format)?;
+                match hook {
+                    Some(hook) => {
+                        if let Err(err) = exec(hook, &mos, &rendered) {
+                            eprintln!("error: hook failed: {}", err);
+                        }
+                    }
+                    None => println!("{}", rendered),
+                }
+            }
+            Ok(_) => (),
+            Err(err) => eprintln!("error: {}", err),
+        }
+
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// The station's latest bulletin in the units asked for
+fn poll<T: Transport>(
+    failover: &Failover<T>,
+    icao: &str,
+    product: Product,
+    units: Units,
+) -> Result<MOS, error::Error> {
+    failover.fetch(icao, product).map(|mos| mos.in_units(units))
+}
+
+/// Connects to a broker given as HOST or HOST:PORT, publishing to the default topics
+#[cfg(feature = "mqtt")]
+fn connect_mqtt(broker: &str, icao: &str) -> Result<mos::notify::mqtt::Publisher, Error> {
+    let (host, port) = match broker.rfind(':') {
+        Some(colon) => (&broker[..colon], broker[colon + 1..].parse::<u16>()?),
+        None => (broker, 1883),
+    };
+    let client_id = format!("cia-backed-{}", icao.to_lowercase());
+    Ok(mos::notify::mqtt::Publisher::connect(
+        &client_id,
+        host,
+        port,
+        Default::default(),
+    )?)
+}
+
+/// Runs the hook through the shell with the rendered forecast on stdin
+fn exec(hook: &str, mos: &MOS, rendered: &str) -> Result<(), Error> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(hook)
+        .env("MOS_ICAO", &mos.meta.icao)
+        .env("MOS_CYCLE", mos.meta.timestamp.to_rfc3339())
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(rendered.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(format!("hook exited with {}", status).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mos::client::Endpoint;
+    use mos::testing::{self, FnTransport};
+
+    use super::*;
+
+    #[test]
+    fn polls_in_the_units_asked_for() {
+        let transport = FnTransport(|_: &str| Ok(testing::page(&testing::load("kfit_mav.txt"))));
+        let failover = Failover::new(transport, vec![Endpoint::nws()]);
+
+        let imperial = poll(&failover, "KFIT", Product::Mav, Units::Imperial).unwrap();
+        let metric = poll(&failover, "KFIT", Product::Mav, Units::Metric).unwrap();
+        assert_eq!(metric.units, Units::Metric);
+        assert_eq!(metric, imperial.to_metric());
+        assert_eq!(imperial.entries[0].tmp, Some(35));
+        assert_eq!(metric.entries[0].tmp, Some(2));
+    }
+}
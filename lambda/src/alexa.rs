@@ -0,0 +1,248 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use mos::{MOSEntry, MOS};
+
+const STATION_SLOT: &str = "Station";
+
+#[derive(Deserialize)]
+pub struct SkillRequestEvent {
+    pub request: SkillRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum SkillRequest {
+    LaunchRequest,
+    IntentRequest {
+        intent: Intent,
+    },
+    SessionEndedRequest,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+pub struct Intent {
+    pub name: String,
+    #[serde(default)]
+    pub slots: HashMap<String, Slot>,
+}
+
+#[derive(Deserialize)]
+pub struct Slot {
+    pub value: Option<String>,
+    pub resolutions: Option<Resolutions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resolutions {
+    pub resolutions_per_authority: Vec<Authority>,
+}
+
+#[derive(Deserialize)]
+pub struct Authority {
+    #[serde(default)]
+    pub values: Vec<ResolvedValue>,
+}
+
+#[derive(Deserialize)]
+pub struct ResolvedValue {
+    pub value: ResolvedValueInner,
+}
+
+#[derive(Deserialize)]
+pub struct ResolvedValueInner {
+    pub id: Option<String>,
+}
+
+impl Slot {
+    /// Prefers the entity resolution ID (e.g. "Fitchburg" resolving to KFIT) over the raw
+    /// spoken value
+    pub fn icao(&self) -> Option<String> {
+        let resolved = self.resolutions.as_ref().and_then(|res| {
+            res.resolutions_per_authority
+                .iter()
+                .flat_map(|auth| auth.values.iter())
+                .filter_map(|val| val.value.id.clone())
+                .next()
+        });
+
+        resolved
+            .or_else(|| self.value.clone())
+            .map(|icao| icao.replace(' ', "").to_uppercase())
+    }
+}
+
+impl SkillRequestEvent {
+    /// Returns the requested station, if the event is a forecast intent that carries one
+    pub fn icao(&self) -> Option<String> {
+        match &self.request {
+            SkillRequest::IntentRequest { intent } => {
+                intent.slots.get(STATION_SLOT).and_then(|slot| slot.icao())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SkillResponse {
+    version: &'static str,
+    response: ResponseBody,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseBody {
+    output_speech: OutputSpeech,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card: Option<Card>,
+    should_end_session: bool,
+}
+
+#[derive(Serialize)]
+struct OutputSpeech {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct Card {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: String,
+    content: String,
+}
+
+impl SkillResponse {
+    pub fn speech(text: &str) -> SkillResponse {
+        SkillResponse {
+            version: "1.0",
+            response: ResponseBody {
+                output_speech: OutputSpeech {
+                    kind: "PlainText",
+                    text: text.to_string(),
+                },
+                card: None,
+                should_end_session: true,
+            },
+        }
+    }
+
+    pub fn with_card(mut self, title: &str, content: &str) -> SkillResponse {
+        self.response.card = Some(Card {
+            kind: "Simple",
+            title: title.to_string(),
+            content: content.to_string(),
+        });
+        self
+    }
+
+    pub fn keep_session(mut self) -> SkillResponse {
+        self.response.should_end_session = false;
+        self
+    }
+}
+
+/// Builds the spoken summary and card text for the first 24 hours of the forecast, which
+/// starts 6 hours after the model cycle
+pub fn format(mos: &MOS) -> SkillResponse {
+    let end = mos.meta.timestamp + Duration::hours(30);
+    let day: Vec<&MOSEntry> = mos
+        .entries
+        .iter()
+        .filter(|entry| entry.timestamp <= end)
+        .collect();
+
+    SkillResponse::speech(&spoken_summary(&mos.meta.icao, &day))
+        .with_card(&format!("Forecast for {}", mos.meta.icao), &card_text(&day))
+}
+
+fn spoken_summary(icao: &str, day: &[&MOSEntry]) -> String {
+    let first = match day.first() {
+        Some(first) => first,
+        None => return format!("Sorry, there is no forecast data for {}.", icao),
+    };
+
+    let mut sentences = Vec::new();
+    if let Some(tmp) = first.tmp {
+        let mut sentence = format!("At {}, expect {} degrees", icao, tmp);
+        if let (Some(wdr), Some(wsp)) = (first.wdr, first.wsp) {
+            if wsp == 0 {
+                sentence.push_str(" with calm winds");
+            } else {
+                sentence.push_str(&format!(
+                    " with winds from the {} at {} knots",
+                    compass(wdr * 10),
+                    wsp
+                ));
+            }
+        }
+        sentences.push(sentence + ".");
+    }
+
+    let nx: Vec<isize> = day.iter().filter_map(|entry| entry.nx).collect();
+    match (nx.iter().max(), nx.iter().min()) {
+        (Some(high), Some(low)) if nx.len() > 1 => {
+            sentences.push(format!("Look for a high of {} and a low of {}.", high, low))
+        }
+        (Some(extreme), _) => {
+            sentences.push(format!("Look for a temperature extreme of {}.", extreme))
+        }
+        _ => (),
+    }
+
+    let pop = day
+        .iter()
+        .filter_map(|entry| entry.p12.or(entry.p06))
+        .max()
+        .unwrap_or(0);
+    sentences.push(format!(
+        "There is up to a {} percent chance of precipitation over the next day.",
+        pop
+    ));
+
+    sentences.join(" ")
+}
+
+fn card_text(day: &[&MOSEntry]) -> String {
+    day.iter()
+        .step_by(2)
+        .map(|entry| {
+            let mut line = entry.timestamp.format("%a %HZ:").to_string();
+            if let Some(tmp) = entry.tmp {
+                line.push_str(&format!(" {}°F", tmp));
+            }
+            if let Some(cld) = &entry.cld {
+                line.push_str(&format!(", {}", cld));
+            }
+            if let (Some(wdr), Some(wsp)) = (entry.wdr, entry.wsp) {
+                line.push_str(&format!(", wind {:03}° at {} kt", wdr * 10, wsp));
+            }
+            if let Some(pop) = entry.p06 {
+                line.push_str(&format!(", {}% precip", pop));
+            }
+            line
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn compass(degrees: isize) -> &'static str {
+    const POINTS: [&str; 8] = [
+        "north",
+        "northeast",
+        "east",
+        "southeast",
+        "south",
+        "southwest",
+        "west",
+        "northwest",
+    ];
+    let idx = ((degrees.rem_euclid(360) as f64 / 45.0).round() as usize) % POINTS.len();
+    POINTS[idx]
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use warp::http::{HeaderMap, Method, Response};
+use warp::path::FullPath;
+use warp::Filter;
+
+use cia_lambda::{http, metrics};
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Runs the API Gateway HTTP API as a plain server, by default on 127.0.0.1:8080, with
+/// Prometheus metrics on /metrics
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt::init();
+
+    let addr: SocketAddr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("127.0.0.1:8080"))
+        .parse()?;
+
+    let metrics = warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .map(|| {
+            warp::reply::with_header(
+                metrics::METRICS.render(),
+                "Content-Type",
+                "text/plain; version=0.0.4",
+            )
+        });
+    let api = warp::method()
+        .and(warp::path::full())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .and_then(proxy);
+
+    warp::serve(metrics.or(api)).run(addr).await;
+    Ok(())
+}
+
+/// Hands the request to the Lambda's HTTP handler as if API Gateway had sent it, and
+/// decodes binary bodies the way API Gateway would
+async fn proxy(
+    method: Method,
+    path: FullPath,
+    query: HashMap<String, String>,
+    headers: HeaderMap,
+    body: impl AsRef<[u8]>,
+) -> Result<Response<Vec<u8>>, Infallible> {
+    let body = String::from_utf8_lossy(body.as_ref()).into_owned();
+    let event = http::HttpRequestEvent {
+        http_method: method.to_string(),
+        path: path.as_str().to_string(),
+        headers: Some(
+            headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .to_str()
+                        .ok()
+                        .map(|value| (name.to_string(), value.to_string()))
+                })
+                .collect(),
+        ),
+        query_string_parameters: Some(query),
+        path_parameters: None,
+        body: if body.is_empty() { None } else { Some(body) },
+    };
+
+    let res = http::handle(event).await;
+    let mut builder = Response::builder().status(res.status_code);
+    for (name, value) in &res.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let body = if res.is_base64_encoded {
+        match base64::decode(&res.body) {
+            Ok(body) => body,
+            Err(_) => return Ok(invalid_response()),
+        }
+    } else {
+        res.body.into_bytes()
+    };
+    Ok(builder.body(body).unwrap_or_else(|_| invalid_response()))
+}
+
+fn invalid_response() -> Response<Vec<u8>> {
+    let mut res = Response::new(b"invalid response".to_vec());
+    *res.status_mut() = warp::http::StatusCode::INTERNAL_SERVER_ERROR;
+    res
+}
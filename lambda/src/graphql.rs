@@ -0,0 +1,143 @@
+use async_graphql::{
+    EmptyMutation, EmptySubscription, Object, Request, Schema, SimpleObject, Variables,
+};
+use chrono::Duration;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub type MOSSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+#[derive(Deserialize)]
+pub struct GraphQLRequestEvent {
+    pub query: String,
+    #[serde(default)]
+    pub variables: Option<Value>,
+    #[serde(default, rename = "operationName")]
+    pub operation_name: Option<String>,
+}
+
+pub fn schema() -> MOSSchema {
+    Schema::new(Query, EmptyMutation, EmptySubscription)
+}
+
+pub async fn execute(e: GraphQLRequestEvent) -> Value {
+    let mut request = Request::new(e.query);
+    if let Some(variables) = e.variables {
+        request = request.variables(Variables::from_json(variables));
+    }
+    if let Some(operation_name) = e.operation_name {
+        request = request.operation_name(operation_name);
+    }
+
+    let response = schema().execute(request).await;
+    serde_json::to_value(response).unwrap_or(Value::Null)
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Fetches and parses the latest MOS bulletin for a station
+    async fn station(&self, icao: String) -> async_graphql::Result<Station> {
+        match crate::get(&icao) {
+            Ok(mos) => Ok(Station { mos }),
+            Err(err) => Err(async_graphql::Error::new(err.to_string())),
+        }
+    }
+}
+
+pub struct Station {
+    mos: mos::MOS,
+}
+
+#[Object]
+impl Station {
+    async fn icao(&self) -> &str {
+        &self.mos.meta.icao
+    }
+
+    /// Model cycle time in RFC 3339
+    async fn cycle(&self) -> String {
+        self.mos.meta.timestamp.to_rfc3339()
+    }
+
+    async fn raw(&self) -> &str {
+        &self.mos.raw
+    }
+
+    /// Entries valid within `hours` of the model cycle, or all of them
+    async fn entries(&self, hours: Option<i32>) -> Vec<Entry> {
+        let cutoff = hours.map(|h| self.mos.meta.timestamp + Duration::hours(h as i64));
+        self.mos
+            .entries
+            .iter()
+            .filter(|entry| match cutoff {
+                Some(cutoff) => entry.timestamp <= cutoff,
+                None => true,
+            })
+            .map(Entry::from)
+            .collect()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Entry {
+    /// Valid time in RFC 3339
+    timestamp: String,
+    nx: Option<i64>,
+    tmp: Option<i64>,
+    dpt: Option<i64>,
+    cld: Option<String>,
+    wdr: Option<i64>,
+    wsp: Option<i64>,
+    p06: Option<i64>,
+    p12: Option<i64>,
+    q06: Option<i64>,
+    q12: Option<i64>,
+    /// Thunderstorm / severe thunderstorm probabilities, 6h
+    t06: Option<Vec<i64>>,
+    /// Thunderstorm / severe thunderstorm probabilities, 12h
+    t12: Option<Vec<i64>>,
+    poz: Option<i64>,
+    pos: Option<i64>,
+    typ: Option<String>,
+    snw: Option<i64>,
+    cig: Option<i64>,
+    vis: Option<i64>,
+    obv: Option<String>,
+}
+
+fn to_i64(val: Option<isize>) -> Option<i64> {
+    val.map(|v| v as i64)
+}
+
+fn pair_to_vec(val: Option<(isize, isize)>) -> Option<Vec<i64>> {
+    val.map(|(a, b)| vec![a as i64, b as i64])
+}
+
+impl From<&mos::MOSEntry> for Entry {
+    fn from(entry: &mos::MOSEntry) -> Self {
+        Entry {
+            timestamp: entry.timestamp.to_rfc3339(),
+            nx: to_i64(entry.nx),
+            tmp: to_i64(entry.tmp),
+            dpt: to_i64(entry.dpt),
+            cld: entry.cld.clone(),
+            wdr: to_i64(entry.wdr),
+            wsp: to_i64(entry.wsp),
+            p06: to_i64(entry.p06),
+            p12: to_i64(entry.p12),
+            q06: to_i64(entry.q06),
+            q12: to_i64(entry.q12),
+            t06: pair_to_vec(entry.t06),
+            t12: pair_to_vec(entry.t12),
+            poz: to_i64(entry.poz),
+            pos: to_i64(entry.pos),
+            typ: entry.typ.clone(),
+            snw: to_i64(entry.snw),
+            cig: to_i64(entry.cig),
+            vis: to_i64(entry.vis),
+            obv: entry.obv.clone(),
+        }
+    }
+}
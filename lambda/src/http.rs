@@ -0,0 +1,316 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::Instrument;
+
+#[cfg(feature = "graphql")]
+use crate::graphql;
+use mos::export::json::{SerializeOptions, TimestampFormat};
+use mos::units::Units;
+use mos::MOS;
+
+use crate::request_id;
+
+/// Body encoding picked with the `format` query parameter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    Json,
+    /// Base64-encoded MessagePack, which API Gateway decodes for binary media types
+    Msgpack,
+    /// The `MOS::to_html()` page, also served for paths ending in .html
+    Html,
+}
+
+/// API Gateway (REST, proxy integration) request event
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRequestEvent {
+    pub http_method: String,
+    pub path: String,
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub query_string_parameters: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub path_parameters: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub is_base64_encoded: bool,
+}
+
+impl HttpResponse {
+    fn new(status_code: u16, content_type: &str, body: String) -> HttpResponse {
+        let mut headers = HashMap::new();
+        headers.insert(String::from("Content-Type"), content_type.to_string());
+        HttpResponse {
+            status_code,
+            headers,
+            body,
+            is_base64_encoded: false,
+        }
+    }
+
+    fn error(status_code: u16, code: &str, msg: &str) -> HttpResponse {
+        HttpResponse::new(
+            status_code,
+            "application/json",
+            serde_json::json!({ "error": msg, "code": code }).to_string(),
+        )
+    }
+
+    fn failure(status_code: u16, err: &mos::error::Error) -> HttpResponse {
+        HttpResponse::error(status_code, err.code(), &err.to_string())
+    }
+
+    /// Sets the X-Request-Id header, and adds `requestId` to JSON error bodies
+    fn with_request_id(mut self, id: &str) -> HttpResponse {
+        if self.status_code >= 400 {
+            if let Ok(serde_json::Value::Object(mut body)) = serde_json::from_str(&self.body) {
+                body.insert(String::from("requestId"), id.into());
+                self.body = serde_json::Value::Object(body).to_string();
+            }
+        }
+        self.headers
+            .insert(String::from(request_id::HEADERS[0]), id.to_string());
+        self
+    }
+}
+
+impl HttpRequestEvent {
+    /// Case-insensitive header lookup
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.as_ref().and_then(|headers| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, val)| val.as_str())
+        })
+    }
+
+    /// The caller's X-Request-Id or X-Correlation-Id, or a generated one
+    pub fn request_id(&self) -> String {
+        request_id::or_generate(
+            request_id::HEADERS
+                .iter()
+                .find_map(|name| self.header(name)),
+        )
+    }
+
+    /// The station comes from the `{icao}` path parameter, the `icao` query parameter, or
+    /// the last path segment, in that order, without any .html extension
+    fn icao(&self) -> Option<String> {
+        self.path_parameters
+            .as_ref()
+            .and_then(|params| params.get("icao").cloned())
+            .or_else(|| {
+                self.query_string_parameters
+                    .as_ref()
+                    .and_then(|params| params.get("icao").cloned())
+            })
+            .or_else(|| {
+                self.path
+                    .rsplit('/')
+                    .find(|seg| !seg.is_empty())
+                    .map(|seg| seg.to_string())
+            })
+            .map(|icao| icao.trim_end_matches(".html").to_string())
+    }
+
+    /// `units=metric` converts the JSON body, anything else is rejected
+    fn units(&self) -> Result<Units, mos::error::Error> {
+        match self
+            .query_string_parameters
+            .as_ref()
+            .and_then(|params| params.get("units"))
+        {
+            Some(units) => units.parse::<Units>(),
+            None => Ok(Units::Imperial),
+        }
+    }
+
+    /// `format=json|msgpack|html`, JSON by default, or HTML for paths ending in .html
+    fn encoding(&self) -> Result<Encoding, mos::error::Error> {
+        if self.path.ends_with(".html") {
+            return Ok(Encoding::Html);
+        }
+        match self
+            .query_string_parameters
+            .as_ref()
+            .and_then(|params| params.get("format"))
+            .map(|format| format.to_lowercase())
+            .as_deref()
+        {
+            None | Some("json") => Ok(Encoding::Json),
+            Some("msgpack") => Ok(Encoding::Msgpack),
+            Some("html") => Ok(Encoding::Html),
+            Some(format) => Err(mos::error::Error::unknown("format", format)),
+        }
+    }
+
+    /// `timestamps=rfc3339|epoch|local` picks how times are written in the JSON body
+    fn options(&self) -> Result<SerializeOptions, mos::error::Error> {
+        let timestamps = match self
+            .query_string_parameters
+            .as_ref()
+            .and_then(|params| params.get("timestamps"))
+        {
+            Some(format) => format.parse::<TimestampFormat>()?,
+            None => TimestampFormat::default(),
+        };
+
+        Ok(SerializeOptions { timestamps })
+    }
+}
+
+/// Serves the request in a span carrying its request ID, which is echoed back in the
+/// response
+pub async fn handle(e: HttpRequestEvent) -> HttpResponse {
+    let id = e.request_id();
+    let span =
+        tracing::info_span!("request", request_id = %id, method = %e.http_method, path = %e.path);
+    let res = respond(e).instrument(span.clone()).await;
+    span.in_scope(|| tracing::info!(status = res.status_code, "responded"));
+    res.with_request_id(&id)
+}
+
+async fn respond(e: HttpRequestEvent) -> HttpResponse {
+    #[cfg(feature = "graphql")]
+    {
+        if e.http_method.eq_ignore_ascii_case("POST") && e.path.ends_with("/graphql") {
+            let body = e.body.as_deref().unwrap_or("");
+            return match serde_json::from_str::<graphql::GraphQLRequestEvent>(body) {
+                Ok(req) => HttpResponse::new(
+                    200,
+                    "application/json",
+                    graphql::execute(req).await.to_string(),
+                ),
+                Err(err) => HttpResponse::error(400, "E_BAD_REQUEST", &err.to_string()),
+            };
+        }
+    }
+
+    if !e.http_method.eq_ignore_ascii_case("GET") {
+        return HttpResponse::error(405, "E_METHOD_NOT_ALLOWED", "method not allowed");
+    }
+
+    let icao = match e.icao() {
+        Some(icao) => icao,
+        None => return HttpResponse::error(400, "E_BAD_REQUEST", "no station given"),
+    };
+    let units = match e.units() {
+        Ok(units) => units,
+        Err(err) => return HttpResponse::failure(400, &err),
+    };
+    let opts = match e.options() {
+        Ok(opts) => opts,
+        Err(err) => return HttpResponse::failure(400, &err),
+    };
+    let encoding = match e.encoding() {
+        Ok(encoding) => encoding,
+        Err(err) => return HttpResponse::failure(400, &err),
+    };
+    let mos = match crate::get(&icao) {
+        Ok(mos) => match units {
+            Units::Imperial => mos,
+            Units::Metric => mos.to_metric(),
+        },
+        Err(err @ mos::error::Error::StationNotFound(_)) => {
+            return HttpResponse::failure(404, &err)
+        }
+        Err(err @ mos::error::Error::UpstreamUnavailable(_)) => {
+            return HttpResponse::failure(503, &err)
+        }
+        Err(err) => return HttpResponse::failure(500, &err),
+    };
+
+    let etag = etag(&mos, &opts, encoding);
+    let last_modified = mos
+        .meta
+        .timestamp
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+
+    let mut res = match e.header("If-None-Match") {
+        Some(tags) if etag_matches(tags, &etag) => HttpResponse {
+            status_code: 304,
+            headers: HashMap::new(),
+            body: String::new(),
+            is_base64_encoded: false,
+        },
+        _ => match encoding {
+            Encoding::Json => match mos.to_json_with(&opts) {
+                Ok(body) => HttpResponse::new(200, "application/json", body.to_string()),
+                Err(err) => return HttpResponse::failure(500, &err),
+            },
+            Encoding::Msgpack => match mos.to_msgpack_with(&opts) {
+                Ok(body) => HttpResponse {
+                    is_base64_encoded: true,
+                    ..HttpResponse::new(200, "application/msgpack", base64::encode(body))
+                },
+                Err(err) => return HttpResponse::failure(500, &err),
+            },
+            Encoding::Html => HttpResponse::new(200, "text/html; charset=utf-8", mos.to_html()),
+        },
+    };
+    res.headers.insert(String::from("ETag"), etag);
+    res.headers
+        .insert(String::from("Last-Modified"), last_modified);
+
+    res
+}
+
+/// Strong ETag built from the station, model cycle and a hash of the bulletin text, so it
+/// changes exactly when a new cycle (or a correction to one) is published. Metric copies,
+/// other timestamp formats, MessagePack and HTML get their own tag since the body differs.
+/// `age_minutes` is left out of what's hashed so clients only refetch when the forecast
+/// itself changes
+pub fn etag(mos: &MOS, opts: &SerializeOptions, encoding: Encoding) -> String {
+    let mut suffix = String::new();
+    if mos.units == Units::Metric {
+        suffix.push_str("-metric");
+    }
+    match opts.timestamps {
+        TimestampFormat::Rfc3339 => (),
+        TimestampFormat::Epoch => suffix.push_str("-epoch"),
+        TimestampFormat::Local => suffix.push_str("-local"),
+    }
+    match encoding {
+        Encoding::Json => (),
+        Encoding::Msgpack => suffix.push_str("-msgpack"),
+        Encoding::Html => suffix.push_str("-html"),
+    }
+    format!(
+        "\"{}-{}-{:016x}{}\"",
+        mos.meta.icao,
+        mos.meta.timestamp.format("%Y%m%d%H"),
+        fnv1a(mos.raw.as_bytes()),
+        suffix
+    )
+}
+
+/// Weak comparison, as If-None-Match calls for: tags match whether or not the client
+/// marks them `W/`
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || opaque(tag) == opaque(etag))
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.trim_start_matches("W/")
+}
+
+/// 64-bit FNV-1a, stable across builds unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,118 @@
+use chrono::Utc;
+use mos::cache::{self, Cache, MemoryCache};
+use mos::client::{Endpoint, ReqwestTransport};
+use mos::failover::Failover;
+use mos::stations::Aliases;
+use mos::{Product, MOS};
+use once_cell::sync::Lazy;
+use std::time::Instant;
+
+pub mod alexa;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod http;
+pub mod metrics;
+pub mod request_id;
+
+/// Forecasts cached in the Redis or ElastiCache server at `REDIS_URL`, or else the
+/// DynamoDB table in `CACHE_TABLE`, with the features for them. Without either, in the
+/// container's memory
+static CACHE: Lazy<Box<dyn Cache + Send + Sync>> = Lazy::new(|| {
+    #[cfg(feature = "redis")]
+    {
+        if let Ok(url) = std::env::var("REDIS_URL") {
+            match mos::cache::redis::RedisCache::open(&url) {
+                Ok(cache) => return Box::new(cache),
+                Err(err) => eprintln!("not caching in REDIS_URL: {}", err),
+            }
+        }
+    }
+    #[cfg(feature = "dynamodb")]
+    {
+        if let Ok(table) = std::env::var("CACHE_TABLE") {
+            match mos::cache::dynamodb::DynamoDbCache::from_env(&table) {
+                Ok(cache) => return Box::new(cache),
+                Err(err) => eprintln!("not caching in CACHE_TABLE: {}", err),
+            }
+        }
+    }
+    Box::new(MemoryCache::new())
+});
+
+/// Station aliases from the JSON in `STATION_ALIASES`
+static ALIASES: Lazy<Aliases> = Lazy::new(|| match std::env::var("STATION_ALIASES") {
+    Ok(json) => Aliases::from_json(&json).unwrap_or_else(|err| {
+        eprintln!("ignoring STATION_ALIASES: {}", err);
+        Aliases::default()
+    }),
+    Err(_) => Aliases::default(),
+});
+
+/// Fetches from the comma-separated sources in `MOS_SOURCES`, named as in
+/// `mos::client::SOURCES` and preferred first, or from the NWS without it. Unknown names
+/// are skipped
+static FAILOVER: Lazy<Failover<ReqwestTransport>> = Lazy::new(|| {
+    let mut endpoints = Vec::new();
+    if let Ok(sources) = std::env::var("MOS_SOURCES") {
+        for name in sources.split(',') {
+            match Endpoint::named(name) {
+                Ok(endpoint) => endpoints.push(endpoint),
+                Err(err) => eprintln!("ignoring a source in MOS_SOURCES: {}", err),
+            }
+        }
+    }
+    if endpoints.is_empty() {
+        endpoints.push(Endpoint::nws());
+    }
+    Failover::new(ReqwestTransport::default(), endpoints)
+});
+
+/// The stations a name given in a request stands for
+pub fn resolve(name: &str) -> Vec<String> {
+    ALIASES.resolve(name)
+}
+
+/// Fetches the station's MAV bulletin from the sources in `MOS_SOURCES`, or takes it from
+/// the cache while it's the latest cycle. A name aliased to several stations gets the first
+pub fn get(name: &str) -> Result<MOS, mos::error::Error> {
+    let stations = resolve(name);
+    let icao = stations.first().map_or(name, String::as_str);
+    metrics::METRICS.request(icao);
+    let now = Utc::now();
+    let key = cache::key(icao, Product::Mav, cache::expected_cycle(Product::Mav, now));
+    if let Some(mos) = cached(icao, &key) {
+        metrics::METRICS.cache_hit();
+        return Ok(mos);
+    }
+
+    let start = Instant::now();
+    let res = FAILOVER.fetch(icao, Product::Mav);
+    metrics::METRICS.fetched(start.elapsed(), &res);
+    match &res {
+        Ok(mos) => tracing::info!(
+            icao,
+            cycle = %mos.meta.timestamp,
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "fetched"
+        ),
+        Err(err) => tracing::warn!(icao, code = err.code(), error = %err, "fetch failed"),
+    }
+    if let Ok(mos) = &res {
+        let ttl = cache::ttl(Product::Mav, mos.meta.timestamp, now);
+        if let Err(err) = CACHE.put(&key, mos, ttl) {
+            tracing::warn!(icao, code = err.code(), error = %err, "caching failed");
+        }
+    }
+    res
+}
+
+/// A failing cache only costs the fetch it would have saved
+fn cached(icao: &str, key: &str) -> Option<MOS> {
+    match CACHE.get(key) {
+        Ok(mos) => mos,
+        Err(err) => {
+            tracing::warn!(icao, code = err.code(), error = %err, "cache lookup failed");
+            None
+        }
+    }
+}
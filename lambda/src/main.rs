@@ -0,0 +1,136 @@
+use lambda::lambda;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::Instrument;
+
+use cia_lambda::alexa;
+#[cfg(feature = "graphql")]
+use cia_lambda::graphql;
+use cia_lambda::{get, http, request_id, resolve};
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Event {
+    Http(http::HttpRequestEvent),
+    #[cfg(feature = "graphql")]
+    GraphQL(graphql::GraphQLRequestEvent),
+    Alexa(alexa::SkillRequestEvent),
+    Weather(WeatherRequestEvent),
+}
+
+#[derive(Deserialize)]
+struct WeatherRequestEvent {
+    /// ICAO identifier or an alias from `STATION_ALIASES`
+    icao: String,
+    /// Correlation ID for the logs and errors, generated if not given
+    #[serde(default, alias = "requestId")]
+    request_id: Option<String>,
+    /// "msgpack" returns the forecasts as base64 MessagePack instead of the raw bulletins
+    #[serde(default)]
+    format: Option<String>,
+    /// Post a summary to the Slack webhook in `SLACK_WEBHOOK_URL`
+    #[serde(default)]
+    notify: bool,
+    /// Archive the cycle in the DynamoDB table in `DYNAMODB_TABLE`
+    #[cfg(feature = "dynamodb")]
+    #[serde(default)]
+    store: bool,
+}
+
+#[derive(Serialize)]
+struct WeatherRequestOutput {
+    message: String,
+}
+
+#[lambda]
+#[tokio::main]
+async fn main(e: Event) -> Result<Value, Error> {
+    // Only the first invocation in a container installs it
+    let _ = tracing_subscriber::fmt::try_init();
+
+    match e {
+        Event::Http(e) => Ok(serde_json::to_value(http::handle(e).await)?),
+        #[cfg(feature = "graphql")]
+        Event::GraphQL(e) => Ok(graphql::execute(e).await),
+        Event::Alexa(e) => {
+            let span = tracing::info_span!("skill", request_id = %request_id::or_generate(None));
+            span.in_scope(|| skill(e))
+        }
+        Event::Weather(e) => {
+            let id = request_id::or_generate(e.request_id.as_deref());
+            let span = tracing::info_span!("weather", request_id = %id, icao = %e.icao);
+            weather(e)
+                .instrument(span)
+                .await
+                // The runtime only reports the message, so the ID goes in it
+                .map_err(|err| format!("{} (request {})", err, id).into())
+        }
+    }
+}
+
+fn skill(e: alexa::SkillRequestEvent) -> Result<Value, Error> {
+    use alexa::{SkillRequest, SkillResponse};
+
+    let response = match &e.request {
+        SkillRequest::IntentRequest { intent } => match intent.name.as_str() {
+            "AMAZON.StopIntent" | "AMAZON.CancelIntent" => SkillResponse::speech("Goodbye."),
+            _ => match e.icao() {
+                Some(icao) => match get(&icao) {
+                    Ok(mos) => alexa::format(&mos),
+                    Err(_) => SkillResponse::speech(&format!(
+                        "Sorry, I couldn't get the forecast for {}.",
+                        icao
+                    )),
+                },
+                None => SkillResponse::speech("Which station would you like the forecast for?")
+                    .keep_session(),
+            },
+        },
+        SkillRequest::LaunchRequest => {
+            SkillResponse::speech("Which station would you like the forecast for?").keep_session()
+        }
+        _ => SkillResponse::speech("Goodbye."),
+    };
+
+    Ok(serde_json::to_value(response)?)
+}
+
+async fn weather(e: WeatherRequestEvent) -> Result<Value, Error> {
+    let mut forecasts = Vec::new();
+    for icao in resolve(&e.icao) {
+        let mos = match get(&icao) {
+            Ok(mos) => mos,
+            // The runtime only reports the message, so the code goes in front of it
+            Err(err) => return Err(format!("{}: {}", err.code(), err).into()),
+        };
+
+        if e.notify {
+            let webhook_url = std::env::var("SLACK_WEBHOOK_URL")?;
+            mos::notify::slack::post(&webhook_url, &mos)?;
+        }
+
+        #[cfg(feature = "dynamodb")]
+        {
+            if e.store {
+                let table = std::env::var("DYNAMODB_TABLE")?;
+                mos::storage::dynamodb::Store::from_env(&table)
+                    .put_cycle(&mos)
+                    .await?;
+            }
+        }
+
+        forecasts.push(mos);
+    }
+
+    match e.format.as_deref() {
+        Some("msgpack") => Ok(Value::String(base64::encode(
+            mos::export::msgpack::encode_all(&forecasts)?,
+        ))),
+        _ => {
+            let bulletins: Vec<&str> = forecasts.iter().map(|mos| mos.raw.as_str()).collect();
+            Ok(Value::String(bulletins.join("\n")))
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use mos::{stations, Product, MOS};
+
+/// Process-wide counters. Only worth reading from a long-lived server; in Lambda they
+/// last as long as the container
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+/// Upper bounds of the fetch latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    requests: BTreeMap<String, u64>,
+    fetch_buckets: [u64; LATENCY_BUCKETS.len()],
+    fetch_count: u64,
+    fetch_seconds: f64,
+    fetch_errors: BTreeMap<&'static str, u64>,
+    parse_failures: u64,
+    cache_hits: u64,
+}
+
+impl Metrics {
+    /// Counts a forecast request for a station. Stations data/mos_stations.txt doesn't
+    /// list are all counted as "other", so that made-up names can't grow the label set
+    pub fn request(&self, icao: &str) {
+        let station = if stations::supports(icao, Product::Mav) {
+            icao.to_uppercase()
+        } else {
+            String::from("other")
+        };
+        let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        *inner.requests.entry(station).or_insert(0) += 1;
+    }
+
+    /// Records an upstream fetch and how it went
+    pub fn fetched(&self, elapsed: Duration, res: &Result<MOS, mos::error::Error>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        let seconds = elapsed.as_secs_f64();
+        for (bucket, bound) in inner.fetch_buckets.iter_mut().zip(LATENCY_BUCKETS.iter()) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        inner.fetch_count += 1;
+        inner.fetch_seconds += seconds;
+
+        if let Err(err) = res {
+            let code = err.code();
+            *inner.fetch_errors.entry(code).or_insert(0) += 1;
+            if code.starts_with("E_PARSE") || code == "E_EMPTY_BULLETIN" {
+                inner.parse_failures += 1;
+            }
+        }
+    }
+
+    /// Counts a forecast served from the cache instead of fetched
+    pub fn cache_hit(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        inner.cache_hits += 1;
+    }
+
+    /// Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP cia_requests_total Forecast requests by station."
+        );
+        let _ = writeln!(out, "# TYPE cia_requests_total counter");
+        for (station, count) in &inner.requests {
+            let _ = writeln!(
+                out,
+                "cia_requests_total{{station=\"{}\"}} {}",
+                escape(station),
+                count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP cia_fetch_duration_seconds Time spent fetching bulletins from the NWS."
+        );
+        let _ = writeln!(out, "# TYPE cia_fetch_duration_seconds histogram");
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(inner.fetch_buckets.iter()) {
+            let _ = writeln!(
+                out,
+                "cia_fetch_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "cia_fetch_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            inner.fetch_count
+        );
+        let _ = writeln!(
+            out,
+            "cia_fetch_duration_seconds_sum {}",
+            inner.fetch_seconds
+        );
+        let _ = writeln!(
+            out,
+            "cia_fetch_duration_seconds_count {}",
+            inner.fetch_count
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP cia_fetch_errors_total Failed fetches by error code."
+        );
+        let _ = writeln!(out, "# TYPE cia_fetch_errors_total counter");
+        for (code, count) in &inner.fetch_errors {
+            let _ = writeln!(out, "cia_fetch_errors_total{{code=\"{}\"}} {}", code, count);
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP cia_parse_failures_total Bulletins that could not be parsed."
+        );
+        let _ = writeln!(out, "# TYPE cia_parse_failures_total counter");
+        let _ = writeln!(out, "cia_parse_failures_total {}", inner.parse_failures);
+
+        let _ = writeln!(
+            out,
+            "# HELP cia_cache_hits_total Forecasts served without fetching."
+        );
+        let _ = writeln!(out, "# TYPE cia_cache_hits_total counter");
+        let _ = writeln!(out, "cia_cache_hits_total {}", inner.cache_hits);
+
+        out
+    }
+}
+
+/// Label values escape backslashes, quotes and newlines
+fn escape(val: &str) -> String {
+    val.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,17 @@
+use uuid::Uuid;
+
+/// Headers a caller can set to tie its request to our logs, checked in order
+pub const HEADERS: [&str; 2] = ["X-Request-Id", "X-Correlation-Id"];
+
+/// The caller's ID if it gave a usable one, otherwise a fresh UUID
+pub fn or_generate(given: Option<&str>) -> String {
+    match given.map(str::trim) {
+        // Bounded and printable so it can't garble log lines or headers
+        Some(id)
+            if !id.is_empty() && id.len() <= 128 && id.chars().all(|c| c.is_ascii_graphic()) =>
+        {
+            id.to_string()
+        }
+        _ => Uuid::new_v4().to_string(),
+    }
+}
@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use mos::MOS;
+
+const SINGLE: &str = include_str!("../tests/fixtures/kfit_mav.txt");
+const TRUNCATED: &str = include_str!("../tests/fixtures/kfit_mav_truncated.txt");
+const COLLECTIVE: &str = include_str!("../tests/fixtures/mav_collective.txt");
+
+/// Collective files hold one bulletin per station separated by blank lines
+fn bulletins(collective: &str) -> Vec<&str> {
+    collective
+        .split("\n\n")
+        .filter(|bulletin| !bulletin.trim().is_empty())
+        .collect()
+}
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("parse single station", |b| {
+        b.iter(|| MOS::new(black_box(SINGLE)).unwrap())
+    });
+    c.bench_function("parse truncated", |b| {
+        b.iter(|| MOS::new(black_box(TRUNCATED)).unwrap())
+    });
+
+    let stations = bulletins(COLLECTIVE);
+    let mut group = c.benchmark_group("collective");
+    group.throughput(Throughput::Elements(stations.len() as u64));
+    group.bench_function("parse all stations", |b| {
+        b.iter(|| {
+            for bulletin in stations.iter() {
+                MOS::new(black_box(bulletin)).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
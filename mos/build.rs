@@ -0,0 +1,9 @@
+fn main() {
+    // Generates the prost types of proto/mos.proto for the proto feature
+    #[cfg(feature = "proto")]
+    {
+        println!("cargo:rerun-if-changed=proto/mos.proto");
+        prost_build::compile_protos(&["proto/mos.proto"], &["proto/"])
+            .unwrap_or_else(|err| panic!("could not compile proto/mos.proto: {}", err));
+    }
+}
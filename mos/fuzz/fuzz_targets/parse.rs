@@ -0,0 +1,15 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use mos::{ParseOptions, MOS};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = MOS::new(text);
+        let opts = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        let _ = MOS::new_with(text, &opts);
+    }
+});
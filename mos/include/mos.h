@@ -0,0 +1,20 @@
+#ifndef MOS_H
+#define MOS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Parses a NUL-terminated MOS bulletin. Returns its JSON, or
+ * {"error": CODE, "message": ...} if it doesn't parse, and NULL only if text
+ * is NULL. The result must be released with mos_free. */
+char *mos_parse(const char *text);
+
+/* Frees a string returned by mos_parse. NULL is ignored. */
+void mos_free(char *json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
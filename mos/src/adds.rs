@@ -0,0 +1,82 @@
+use super::client::{self, Transport};
+use super::error;
+
+/// Queries the aviationweather.gov ADDS text data server and returns the XML response
+pub(crate) fn query<T: Transport + ?Sized>(
+    transport: &T,
+    data_source: &str,
+    params: &str,
+) -> Result<String, error::Error> {
+    let res = transport.get(&format!(
+        "https://aviationweather.gov/adds/dataserver_current/httpparam?dataSource={}\
+         &requestType=retrieve&format=xml&{}",
+        data_source, params
+    ))?;
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "aviationweather.gov",
+            status: res.status,
+        });
+    }
+
+    Ok(res.body)
+}
+
+/// Every `<tag>` element in the document, from its opening tag through its closing
+/// one. The data server's XML has no nesting of like elements or CDATA, so a tag scan
+/// is enough
+pub(crate) fn elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let close = format!("</{}>", tag);
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(start) = open_tag(&xml[from..], tag).map(|at| from + at) {
+        let head_end = match xml[start..].find('>') {
+            Some(len) => start + len + 1,
+            None => break,
+        };
+        let end = if xml[..head_end].ends_with("/>") {
+            head_end
+        } else {
+            match xml[head_end..].find(&close) {
+                Some(len) => head_end + len + close.len(),
+                None => break,
+            }
+        };
+        out.push(&xml[start..end]);
+        from = end;
+    }
+    out
+}
+
+/// Text of the first `<tag>` element
+pub(crate) fn text(xml: &str, tag: &str) -> Option<String> {
+    let element = *elements(xml, tag).first()?;
+    let start = element.find('>')? + 1;
+    let end = element.rfind("</")?;
+    if end < start {
+        return None;
+    }
+    Some(client::unescape(element[start..end].trim()))
+}
+
+/// Value of an attribute on the first `<tag>` element
+pub(crate) fn attr(xml: &str, tag: &str, name: &str) -> Option<String> {
+    let element = *elements(xml, tag).first()?;
+    let head = &element[..element.find('>')?];
+    let key = format!(" {}=\"", name);
+    let start = head.find(&key)? + key.len();
+    let end = start + head[start..].find('"')?;
+    Some(client::unescape(&head[start..end]))
+}
+
+fn open_tag(xml: &str, tag: &str) -> Option<usize> {
+    let open = format!("<{}", tag);
+    let mut from = 0;
+    loop {
+        let at = from + xml[from..].find(&open)?;
+        match xml[at + open.len()..].chars().next() {
+            Some('>') | Some(' ') | Some('/') | Some('\n') | Some('\t') => return Some(at),
+            _ => from = at + open.len(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+#[cfg(feature = "client")]
+use super::error;
+#[cfg(feature = "client")]
+use super::stations;
+
+/// An Area Forecast Discussion
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Discussion {
+    /// Issuing forecast office, e.g. "BOX"
+    pub office: String,
+    pub sections: Vec<Section>,
+    pub raw: String,
+}
+
+/// One dot-headed section of the discussion
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Section {
+    /// Heading without the slashed period, e.g. "NEAR TERM"
+    pub title: String,
+    /// The slashed period after the heading, e.g. "THROUGH TONIGHT"
+    pub period: Option<String>,
+    pub body: String,
+}
+
+impl Discussion {
+    /// Splits the product text into its sections. Each one opens with a ".HEADING..."
+    /// line and runs to the "&&" after it, or the next heading
+    pub fn parse(office: &str, raw: &str) -> Discussion {
+        let mut sections: Vec<Section> = Vec::new();
+        let mut open = false;
+        for line in raw.lines() {
+            let trimmed = line.trim_end();
+            if let Some(section) = heading(trimmed) {
+                sections.push(section);
+                open = true;
+            } else if trimmed == "&&" || trimmed == "$$" {
+                open = false;
+            } else if open {
+                if let Some(section) = sections.last_mut() {
+                    if !section.body.is_empty() || !trimmed.is_empty() {
+                        section.body.push_str(trimmed);
+                        section.body.push('\n');
+                    }
+                }
+            }
+        }
+        for section in &mut sections {
+            section.body = section.body.trim_end().to_string();
+        }
+
+        Discussion {
+            office: office.to_uppercase(),
+            sections,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// The first section whose title starts with `name`, ignoring case, so "AVIATION"
+    /// also finds "AVIATION /12Z TAFS/"
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        let name = name.to_uppercase();
+        self.sections
+            .iter()
+            .find(|section| section.title.starts_with(&name))
+    }
+
+    pub fn synopsis(&self) -> Option<&Section> {
+        self.section("SYNOPSIS")
+    }
+
+    pub fn near_term(&self) -> Option<&Section> {
+        self.section("NEAR TERM")
+    }
+
+    pub fn aviation(&self) -> Option<&Section> {
+        self.section("AVIATION")
+    }
+}
+
+/// ".NEAR TERM /THROUGH TONIGHT/..." with any text after the dots starting the body
+fn heading(line: &str) -> Option<Section> {
+    let rest = line.strip_prefix('.')?;
+    if !rest.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let dots = rest.find("...")?;
+    let (head, body) = (&rest[..dots], rest[dots + 3..].trim());
+
+    let (title, period) = match head.find(" /") {
+        Some(slash) => (
+            &head[..slash],
+            Some(
+                head[slash..]
+                    .trim_matches(|c| c == '/' || c == ' ')
+                    .to_string(),
+            ),
+        ),
+        None => (head, None),
+    };
+    let mut body = body.to_string();
+    if !body.is_empty() {
+        body.push('\n');
+    }
+
+    Some(Section {
+        title: title.trim().to_string(),
+        period: period.filter(|period| !period.is_empty()),
+        body,
+    })
+}
+
+/// Latest discussion from the office covering a station in the embedded table
+#[cfg(feature = "client")]
+pub fn get(icao: &str) -> Result<Discussion, error::Error> {
+    fetch(client::default_transport(), icao)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(transport: &T, icao: &str) -> Result<Discussion, error::Error> {
+    let station =
+        stations::lookup(icao).ok_or_else(|| error::Error::UnknownStation(icao.to_uppercase()))?;
+    fetch_office(transport, station.wfo)
+}
+
+/// Latest discussion from a forecast office, by its three-letter identifier
+#[cfg(feature = "client")]
+pub fn fetch_office<T: Transport + ?Sized>(
+    transport: &T,
+    office: &str,
+) -> Result<Discussion, error::Error> {
+    let office = office.to_uppercase();
+    let res = transport.get(&format!(
+        "https://forecast.weather.gov/product.php?site=NWS&issuedby={}&product=AFD\
+         &format=txt&version=1&glossary=0",
+        office
+    ))?;
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "nws",
+            status: res.status,
+        });
+    }
+
+    let raw = client::extract_pre(&res.body)?;
+    Ok(Discussion::parse(&office, &raw))
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use super::adds;
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+#[cfg(feature = "client")]
+use super::error;
+use super::stations;
+
+/// An active AIRMET or SIGMET
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Advisory {
+    pub kind: AdvisoryKind,
+    pub hazard: Hazard,
+    /// As issued, e.g. "LT-MOD" or "MOD"
+    pub severity: Option<String>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: DateTime<Utc>,
+    /// Feet MSL
+    pub min_altitude: Option<u32>,
+    /// Feet MSL
+    pub max_altitude: Option<u32>,
+    /// Outline of the affected area as (lat, lon) vertices
+    pub area: Vec<(f64, f64)>,
+    pub raw: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisoryKind {
+    Airmet,
+    Sigmet,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hazard {
+    Icing,
+    Turbulence,
+    Ifr,
+    MountainObscuration,
+    Convective,
+    Ash,
+    Other(String),
+}
+
+/// A lat/lon box in degrees
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+impl Hazard {
+    fn from_code(code: &str) -> Hazard {
+        match code {
+            "ICE" => Hazard::Icing,
+            "TURB" => Hazard::Turbulence,
+            "IFR" => Hazard::Ifr,
+            "MTN OBSCN" => Hazard::MountainObscuration,
+            "CONVECTIVE" => Hazard::Convective,
+            "ASH" => Hazard::Ash,
+            other => Hazard::Other(other.to_string()),
+        }
+    }
+}
+
+impl BoundingBox {
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        lat >= self.min_lat && lat <= self.max_lat && lon >= self.min_lon && lon <= self.max_lon
+    }
+}
+
+impl Advisory {
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        self.valid_from <= at && at < self.valid_to
+    }
+
+    /// True when the point is inside the advisory area
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        // Ray casting along the latitude line through the point
+        let mut inside = false;
+        let mut j = match self.area.len() {
+            0 => return false,
+            len => len - 1,
+        };
+        for (i, &(lat_i, lon_i)) in self.area.iter().enumerate() {
+            let (lat_j, lon_j) = self.area[j];
+            if (lat_i > lat) != (lat_j > lat)
+                && lon < (lon_j - lon_i) * (lat - lat_i) / (lat_j - lat_i) + lon_i
+            {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+
+    /// True when the advisory area overlaps the box, going by the area's own bounds
+    pub fn intersects(&self, bbox: &BoundingBox) -> bool {
+        match self.bounds() {
+            Some(bounds) => {
+                bounds.min_lat <= bbox.max_lat
+                    && bounds.max_lat >= bbox.min_lat
+                    && bounds.min_lon <= bbox.max_lon
+                    && bounds.max_lon >= bbox.min_lon
+            }
+            None => false,
+        }
+    }
+
+    /// Smallest box around the advisory area
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        let (&(lat, lon), rest) = self.area.split_first()?;
+        Some(rest.iter().fold(
+            BoundingBox {
+                min_lat: lat,
+                min_lon: lon,
+                max_lat: lat,
+                max_lon: lon,
+            },
+            |bbox, &(lat, lon)| BoundingBox {
+                min_lat: bbox.min_lat.min(lat),
+                min_lon: bbox.min_lon.min(lon),
+                max_lat: bbox.max_lat.max(lat),
+                max_lon: bbox.max_lon.max(lon),
+            },
+        ))
+    }
+
+    /// Parses the `<AIRSIGMET>` elements of a data server response
+    #[cfg(feature = "client")]
+    pub fn from_xml(xml: &str) -> Result<Vec<Advisory>, error::Error> {
+        adds::elements(xml, "AIRSIGMET")
+            .into_iter()
+            .map(|element| {
+                let time = |tag| -> Result<DateTime<Utc>, error::Error> {
+                    let text = adds::text(element, tag)
+                        .ok_or_else(|| error::Error::parse("AIRSIGMET", "missing valid time"))?;
+                    Ok(text.parse::<DateTime<Utc>>()?)
+                };
+                let altitude =
+                    |name| adds::attr(element, "altitude", name).and_then(|alt| alt.parse().ok());
+                let kind = match adds::text(element, "airsigmet_type").as_deref() {
+                    Some("AIRMET") => AdvisoryKind::Airmet,
+                    _ => AdvisoryKind::Sigmet,
+                };
+
+                Ok(Advisory {
+                    kind,
+                    hazard: Hazard::from_code(
+                        &adds::attr(element, "hazard", "type").unwrap_or_default(),
+                    ),
+                    severity: adds::attr(element, "hazard", "severity")
+                        .filter(|severity| !severity.is_empty()),
+                    valid_from: time("valid_time_from")?,
+                    valid_to: time("valid_time_to")?,
+                    min_altitude: altitude("min_ft_msl"),
+                    max_altitude: altitude("max_ft_msl"),
+                    area: adds::elements(element, "point")
+                        .into_iter()
+                        .filter_map(|point| {
+                            let lat = adds::text(point, "latitude")?.parse().ok()?;
+                            let lon = adds::text(point, "longitude")?.parse().ok()?;
+                            Some((lat, lon))
+                        })
+                        .collect(),
+                    raw: adds::text(element, "raw_text").unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Advisories whose area covers the station
+pub fn for_station<'a>(advisories: &'a [Advisory], icao: &str) -> Vec<&'a Advisory> {
+    match stations::lookup(icao) {
+        Some(station) => advisories
+            .iter()
+            .filter(|advisory| advisory.contains(station.lat, station.lon))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Advisories overlapping the box
+pub fn in_box<'a>(advisories: &'a [Advisory], bbox: &BoundingBox) -> Vec<&'a Advisory> {
+    advisories
+        .iter()
+        .filter(|advisory| advisory.intersects(bbox))
+        .collect()
+}
+
+/// AIRMETs and SIGMETs currently in effect over the US, from aviationweather.gov
+#[cfg(feature = "client")]
+pub fn get() -> Result<Vec<Advisory>, error::Error> {
+    fetch(client::default_transport())
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(transport: &T) -> Result<Vec<Advisory>, error::Error> {
+    let xml = adds::query(transport, "airsigmets", "hoursBeforeNow=1")?;
+    let now = Utc::now();
+
+    Ok(Advisory::from_xml(&xml)?
+        .into_iter()
+        .filter(|advisory| advisory.valid_to > now)
+        .collect())
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::client::{Endpoint, Response, Transport};
+use super::failover::Failover;
+use super::{error, Product, MOS};
+
+/// How hard a batch may hit its sources
+#[derive(Clone, Debug, PartialEq)]
+pub struct Limits {
+    /// Requests in flight at once across all hosts
+    pub max_in_flight: usize,
+    /// Requests in flight at once to any one host
+    pub per_host: usize,
+    /// Least time between the starts of two requests to the same host
+    pub delay: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_in_flight: 8,
+            per_host: 4,
+            delay: Duration::from_millis(0),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Host {
+    in_flight: usize,
+    last_start: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    in_flight: usize,
+    hosts: HashMap<String, Host>,
+}
+
+/// Transport holding requests back until they fit within the limits, so any number of
+/// threads can share it
+#[derive(Debug)]
+pub struct Throttle<T> {
+    transport: T,
+    limits: Limits,
+    state: Mutex<State>,
+    freed: Condvar,
+}
+
+impl<T: Transport> Throttle<T> {
+    pub fn new(transport: T, limits: Limits) -> Throttle<T> {
+        Throttle {
+            transport,
+            limits,
+            state: Mutex::new(State::default()),
+            freed: Condvar::new(),
+        }
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Blocks until a request to `host` may start, then counts it as in flight until the
+    /// returned slot is dropped
+    fn acquire<'a>(&'a self, host: &'a str) -> Slot<'a, T> {
+        let max_in_flight = self.limits.max_in_flight.max(1);
+        let per_host = self.limits.per_host.max(1);
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        loop {
+            let total = state.in_flight;
+            let entry = state.hosts.entry(host.to_string()).or_default();
+            if total < max_in_flight && entry.in_flight < per_host {
+                let wait = entry
+                    .last_start
+                    .map(|last| {
+                        (last + self.limits.delay).saturating_duration_since(Instant::now())
+                    })
+                    .unwrap_or_default();
+                if wait == Duration::from_millis(0) {
+                    entry.in_flight += 1;
+                    entry.last_start = Some(Instant::now());
+                    state.in_flight += 1;
+                    return Slot {
+                        throttle: self,
+                        host,
+                    };
+                }
+                state = match self.freed.wait_timeout(state, wait) {
+                    Ok((state, _)) => state,
+                    Err(err) => err.into_inner().0,
+                };
+            } else {
+                state = self
+                    .freed
+                    .wait(state)
+                    .unwrap_or_else(|err| err.into_inner());
+            }
+        }
+    }
+}
+
+/// A request counted as in flight, released when dropped so that a panicking transport
+/// doesn't keep its host's slot forever
+struct Slot<'a, T> {
+    throttle: &'a Throttle<T>,
+    host: &'a str,
+}
+
+impl<T> Drop for Slot<'_, T> {
+    fn drop(&mut self) {
+        let throttle = self.throttle;
+        let mut state = throttle.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.in_flight -= 1;
+        if let Some(entry) = state.hosts.get_mut(self.host) {
+            entry.in_flight -= 1;
+        }
+        throttle.freed.notify_all();
+    }
+}
+
+impl<T: Transport> Transport for Throttle<T> {
+    fn get(&self, url: &str) -> Result<Response, error::Error> {
+        let _slot = self.acquire(host(url));
+        self.transport.get(url)
+    }
+}
+
+/// "api.weather.gov" for "https://api.weather.gov/points/..."
+fn host(url: &str) -> &str {
+    let rest = url.find("://").map_or(url, |i| &url[i + 3..]);
+    rest.split(|c| c == '/' || c == '?').next().unwrap_or(rest)
+}
+
+type Results = Vec<Option<Result<MOS, error::Error>>>;
+
+/// Fetches many stations at once from worker threads sharing a `Throttle`, e.g. to
+/// refresh a whole region within a Lambda timeout without tripping rate limits
+pub struct Batch<T> {
+    failover: Arc<Failover<Throttle<T>>>,
+    workers: usize,
+}
+
+impl<T: Transport + Send + Sync + 'static> Batch<T> {
+    /// Fetching from the NWS MOS CGI, like `client::fetch`
+    pub fn new(transport: T, limits: Limits) -> Batch<T> {
+        Batch::with_endpoints(transport, limits, vec![Endpoint::nws()])
+    }
+
+    /// Fetching from the endpoints through a `Failover`, whose health the workers share
+    pub fn with_endpoints(transport: T, limits: Limits, endpoints: Vec<Endpoint>) -> Batch<T> {
+        let workers = limits.max_in_flight.max(1);
+        Batch {
+            failover: Arc::new(Failover::new(Throttle::new(transport, limits), endpoints)),
+            workers,
+        }
+    }
+
+    /// The result for each station, in the order given. One station failing doesn't
+    /// stop the others
+    pub fn fetch_all(
+        &self,
+        stations: &[String],
+        product: Product,
+    ) -> Vec<Result<MOS, error::Error>> {
+        let stations = Arc::new(stations.to_vec());
+        let next = Arc::new(AtomicUsize::new(0));
+        let results: Arc<Mutex<Results>> =
+            Arc::new(Mutex::new((0..stations.len()).map(|_| None).collect()));
+        let workers = self.workers.min(stations.len());
+
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let (failover, stations, next, results) = (
+                    Arc::clone(&self.failover),
+                    Arc::clone(&stations),
+                    Arc::clone(&next),
+                    Arc::clone(&results),
+                );
+                thread::spawn(move || loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let icao = match stations.get(i) {
+                        Some(icao) => icao,
+                        None => return,
+                    };
+                    let res = failover.fetch(icao, product);
+                    results.lock().unwrap_or_else(|err| err.into_inner())[i] = Some(res);
+                })
+            })
+            .collect();
+        for handle in handles {
+            let _ = handle.join();
+        }
+
+        // Only a worker that panicked leaves a station without a result
+        let mut results = results.lock().unwrap_or_else(|err| err.into_inner());
+        results
+            .iter_mut()
+            .map(|res| res.take().unwrap_or(Err(error::Error::MissingData)))
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+use chrono::{Duration, Utc};
+use rusoto_dynamodb::{
+    AttributeValue, DeleteItemInput, DynamoDb, DynamoDbClient, GetItemInput, PutItemInput,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{mpsc, Mutex};
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use super::super::{error, MOS};
+use super::Cache;
+
+/// Forecasts as JSON in a DynamoDB table with the partition key `key`. Items carry
+/// their expiry in `expires`, in seconds since the epoch, for the table's TTL setting
+/// to delete them by. That can take DynamoDB days, so expired items are also skipped
+pub struct DynamoDbCache {
+    client: DynamoDbClient,
+    table: String,
+    /// Runs the requests, so the cache can be used from inside another runtime
+    handle: Handle,
+    _runtime: Mutex<Runtime>,
+}
+
+impl DynamoDbCache {
+    pub fn new(client: DynamoDbClient, table: &str) -> Result<DynamoDbCache, error::Error> {
+        let runtime = Builder::new()
+            .threaded_scheduler()
+            .core_threads(1)
+            .enable_all()
+            .build()?;
+        Ok(DynamoDbCache {
+            client,
+            table: table.to_string(),
+            handle: runtime.handle().clone(),
+            _runtime: Mutex::new(runtime),
+        })
+    }
+
+    /// A cache on a client for the region and credentials in the environment
+    pub fn from_env(table: &str) -> Result<DynamoDbCache, error::Error> {
+        DynamoDbCache::new(DynamoDbClient::new(rusoto_core::Region::default()), table)
+    }
+
+    fn run<T: Send + 'static>(
+        &self,
+        fut: impl Future<Output = Result<T, error::Error>> + Send + 'static,
+    ) -> Result<T, error::Error> {
+        let (tx, rx) = mpsc::channel();
+        self.handle.spawn(async move {
+            let _ = tx.send(fut.await);
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(error::Error::Cache(String::from("request was dropped"))))
+    }
+
+    fn key(&self, key: &str) -> HashMap<String, AttributeValue> {
+        let mut item = HashMap::new();
+        item.insert(String::from("key"), string(key.to_string()));
+        item
+    }
+}
+
+impl Cache for DynamoDbCache {
+    fn get(&self, key: &str) -> Result<Option<MOS>, error::Error> {
+        let client = self.client.clone();
+        let input = GetItemInput {
+            table_name: self.table.clone(),
+            key: self.key(key),
+            ..GetItemInput::default()
+        };
+        let item = self.run(async move {
+            let output = client.get_item(input).await.map_err(cache_error)?;
+            Ok(output.item)
+        })?;
+
+        let item = match item {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let expires = item
+            .get("expires")
+            .and_then(|val| val.n.as_deref())
+            .and_then(|n| n.parse::<i64>().ok());
+        if expires.map_or(true, |expires| expires <= Utc::now().timestamp()) {
+            return Ok(None);
+        }
+        match item.get("forecast").and_then(|val| val.s.as_deref()) {
+            Some(json) => Ok(Some(serde_json::from_str(json)?)),
+            None => Err(error::Error::Cache(String::from(
+                "item without a forecast attribute",
+            ))),
+        }
+    }
+
+    fn put(&self, key: &str, mos: &MOS, ttl: Duration) -> Result<(), error::Error> {
+        let mut item = self.key(key);
+        item.insert(
+            String::from("forecast"),
+            string(serde_json::to_string(mos)?),
+        );
+        item.insert(
+            String::from("expires"),
+            AttributeValue {
+                n: Some((Utc::now() + ttl).timestamp().to_string()),
+                ..AttributeValue::default()
+            },
+        );
+
+        let client = self.client.clone();
+        let input = PutItemInput {
+            table_name: self.table.clone(),
+            item,
+            ..PutItemInput::default()
+        };
+        self.run(async move {
+            client.put_item(input).await.map_err(cache_error)?;
+            Ok(())
+        })
+    }
+
+    fn invalidate(&self, key: &str) -> Result<(), error::Error> {
+        let client = self.client.clone();
+        let input = DeleteItemInput {
+            table_name: self.table.clone(),
+            key: self.key(key),
+            ..DeleteItemInput::default()
+        };
+        self.run(async move {
+            client.delete_item(input).await.map_err(cache_error)?;
+            Ok(())
+        })
+    }
+}
+
+fn string(val: String) -> AttributeValue {
+    AttributeValue {
+        s: Some(val),
+        ..AttributeValue::default()
+    }
+}
+
+fn cache_error<E: std::error::Error + 'static>(err: rusoto_core::RusotoError<E>) -> error::Error {
+    error::Error::Cache(err.to_string())
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::storage::timestamp;
+use super::{error, Product, MOS};
+
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb;
+#[cfg(feature = "redis")]
+pub mod redis;
+
+/// Where `client::fetch_cached` keeps forecasts between fetches, under the keys of `key`
+pub trait Cache {
+    /// The forecast under the key, unless it expired
+    fn get(&self, key: &str) -> Result<Option<MOS>, error::Error>;
+
+    /// Stores the forecast under the key for `ttl`, replacing what was there
+    fn put(&self, key: &str, mos: &MOS, ttl: Duration) -> Result<(), error::Error>;
+
+    fn invalidate(&self, key: &str) -> Result<(), error::Error>;
+}
+
+/// A cache in the process's memory, which the forecasts of a warm Lambda container
+/// survive in. Expired entries are dropped as they're looked up
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, (MOS, DateTime<Utc>)>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> MemoryCache {
+        MemoryCache::default()
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Result<Option<MOS>, error::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        match entries.get(key) {
+            Some((mos, expires)) if *expires > Utc::now() => Ok(Some(mos.clone())),
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn put(&self, key: &str, mos: &MOS, ttl: Duration) -> Result<(), error::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.insert(key.to_string(), (mos.clone(), Utc::now() + ttl));
+        Ok(())
+    }
+
+    fn invalidate(&self, key: &str) -> Result<(), error::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.remove(key);
+        Ok(())
+    }
+}
+
+/// How long after its cycle time a bulletin usually shows up on the NWS site
+pub const PUBLICATION_DELAY_HOURS: i64 = 4;
+
+/// Shortest time an entry is kept, for bulletins fetched after the next cycle was due
+pub const MIN_TTL_SECS: i64 = 60;
+
+/// Hours between model cycles of the product
+pub fn cycle_hours(product: Product) -> i64 {
+    match product {
+        Product::Mav => 6,
+        Product::Mex => 12,
+    }
+}
+
+/// The newest cycle that should be published by `now`
+pub fn expected_cycle(product: Product, now: DateTime<Utc>) -> DateTime<Utc> {
+    let published = now - Duration::hours(PUBLICATION_DELAY_HOURS);
+    let hour = published.hour() as i64 / cycle_hours(product) * cycle_hours(product);
+    published.date().and_hms(hour as u32, 0, 0)
+}
+
+/// "mos:{icao}:{product}:{cycle}", the cycle being the one expected at the time of the
+/// lookup, so entries stop being found once a newer cycle is due
+pub fn key(icao: &str, product: Product, cycle: DateTime<Utc>) -> String {
+    let product = match product {
+        Product::Mav => "mav",
+        Product::Mex => "mex",
+    };
+    format!(
+        "mos:{}:{}:{}",
+        icao.to_uppercase(),
+        product,
+        timestamp(cycle)
+    )
+}
+
+/// Time from `now` until the cycle after `cycle` should be published, at least
+/// `MIN_TTL_SECS`
+pub fn ttl(product: Product, cycle: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    let next = cycle + Duration::hours(cycle_hours(product) + PUBLICATION_DELAY_HOURS);
+    (next - now).max(Duration::seconds(MIN_TTL_SECS))
+}
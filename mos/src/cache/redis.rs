@@ -0,0 +1,66 @@
+use ::redis::{Client, Commands, Connection, RedisError};
+use chrono::Duration;
+use std::sync::Mutex;
+
+use super::super::{error, MOS};
+use super::Cache;
+
+/// Forecasts as JSON in Redis or ElastiCache, expired by the server
+pub struct RedisCache {
+    client: Client,
+    /// Opened on first use and again after a failed command
+    conn: Mutex<Option<Connection>>,
+}
+
+impl RedisCache {
+    pub fn new(client: Client) -> RedisCache {
+        RedisCache {
+            client,
+            conn: Mutex::new(None),
+        }
+    }
+
+    /// A cache on the server at a URL like "redis://host:6379/0"
+    pub fn open(url: &str) -> Result<RedisCache, error::Error> {
+        Ok(RedisCache::new(Client::open(url).map_err(cache_error)?))
+    }
+
+    fn with_conn<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> Result<T, RedisError>,
+    ) -> Result<T, error::Error> {
+        let mut conn = self.conn.lock().unwrap_or_else(|err| err.into_inner());
+        if conn.is_none() {
+            *conn = Some(self.client.get_connection().map_err(cache_error)?);
+        }
+        let res = f(conn.as_mut().unwrap());
+        if res.is_err() {
+            *conn = None;
+        }
+        res.map_err(cache_error)
+    }
+}
+
+impl Cache for RedisCache {
+    fn get(&self, key: &str) -> Result<Option<MOS>, error::Error> {
+        let json: Option<String> = self.with_conn(|conn| conn.get(key))?;
+        match json {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put(&self, key: &str, mos: &MOS, ttl: Duration) -> Result<(), error::Error> {
+        let json = serde_json::to_string(mos)?;
+        let secs = ttl.num_seconds().max(1) as usize;
+        self.with_conn(|conn| conn.set_ex(key, json, secs))
+    }
+
+    fn invalidate(&self, key: &str) -> Result<(), error::Error> {
+        self.with_conn(|conn| conn.del(key))
+    }
+}
+
+fn cache_error(err: RedisError) -> error::Error {
+    error::Error::Cache(err.to_string())
+}
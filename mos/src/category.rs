@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::window::Window;
+use super::{MOSEntry, MOS};
+
+/// FAA flight category, ordered from worst to best so `min()` gives the worst
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FlightCategory {
+    /// Ceiling below 500 ft or visibility below 1 mi
+    Lifr,
+    /// Ceiling 500 to below 1000 ft or visibility 1 to below 3 mi
+    Ifr,
+    /// Ceiling 1000 to 3000 ft or visibility 3 to 5 mi
+    Mvfr,
+    /// Anything better
+    Vfr,
+}
+
+impl fmt::Display for FlightCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FlightCategory::Lifr => "LIFR",
+            FlightCategory::Ifr => "IFR",
+            FlightCategory::Mvfr => "MVFR",
+            FlightCategory::Vfr => "VFR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FlightCategory {
+    /// Category of an observed or forecast ceiling in feet AGL and visibility in statute
+    /// miles, where a missing element is unrestricted
+    pub fn from_conditions(ceiling: Option<u32>, visibility: Option<f64>) -> FlightCategory {
+        let ceiling = ceiling.unwrap_or(u32::max_value());
+        let visibility = visibility.unwrap_or(std::f64::INFINITY);
+
+        if ceiling < 500 || visibility < 1.0 {
+            FlightCategory::Lifr
+        } else if ceiling < 1000 || visibility < 3.0 {
+            FlightCategory::Ifr
+        } else if ceiling <= 3000 || visibility <= 5.0 {
+            FlightCategory::Mvfr
+        } else {
+            FlightCategory::Vfr
+        }
+    }
+}
+
+impl MOSEntry {
+    /// Derives the flight category from the CIG and VIS categorical forecasts.
+    ///
+    /// CIG categories: 1 <200 ft, 2 200–400, 3 500–900, 4 1000–1900, 5 2000–3000,
+    /// 6 3100–6500, 7 6600–12000, 8 >12000 ft or unlimited.
+    /// VIS categories: 1 <1/2 mi, 2 1/2–<1, 3 1–<2, 4 2–<3, 5 3–5, 6 6, 7 >6 mi.
+    ///
+    /// A missing element is treated as unrestricted; `None` only when both are missing
+    pub fn flight_category(&self) -> Option<FlightCategory> {
+        if self.cig.is_none() && self.vis.is_none() {
+            return None;
+        }
+        let cig = self.cig.unwrap_or(8);
+        let vis = self.vis.unwrap_or(7);
+
+        Some(if cig <= 2 || vis <= 2 {
+            FlightCategory::Lifr
+        } else if cig == 3 || vis <= 4 {
+            FlightCategory::Ifr
+        } else if cig <= 5 || vis == 5 {
+            FlightCategory::Mvfr
+        } else {
+            FlightCategory::Vfr
+        })
+    }
+}
+
+/// A value and the first valid time it's forecast
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Worst<T> {
+    pub value: T,
+    pub at: DateTime<Utc>,
+}
+
+/// The lowest forecast ceiling and visibility over a window
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WorstConditions {
+    /// CIG category
+    pub ceiling: Option<Worst<isize>>,
+    /// VIS category
+    pub visibility: Option<Worst<isize>>,
+    pub flight_category: Option<Worst<FlightCategory>>,
+}
+
+impl MOS {
+    /// Lowest CIG and VIS categories and the worst flight category at the valid times from
+    /// `start` up to `end`, each with the earliest time it occurs. None if no valid time
+    /// falls in the window
+    pub fn worst_conditions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Option<WorstConditions> {
+        let window = Window::new(start, end);
+        let entries: Vec<&MOSEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| window.contains(entry.timestamp))
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+
+        Some(WorstConditions {
+            ceiling: earliest_min(&entries, |entry| entry.cig),
+            visibility: earliest_min(&entries, |entry| entry.vis),
+            flight_category: earliest_min(&entries, MOSEntry::flight_category),
+        })
+    }
+
+    /// Flight category at every valid time that forecasts a ceiling or visibility
+    pub fn category_timeline(&self) -> Vec<(DateTime<Utc>, FlightCategory)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .flight_category()
+                    .map(|category| (entry.timestamp, category))
+            })
+            .collect()
+    }
+}
+
+fn earliest_min<T, F>(entries: &[&MOSEntry], value: F) -> Option<Worst<T>>
+where
+    T: Ord + Copy,
+    F: Fn(&MOSEntry) -> Option<T>,
+{
+    // min_by_key keeps the first of equal minimums
+    entries
+        .iter()
+        .filter_map(|entry| {
+            value(entry).map(|value| Worst {
+                value,
+                at: entry.timestamp,
+            })
+        })
+        .min_by_key(|worst| worst.value)
+}
@@ -0,0 +1,578 @@
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::thread;
+use std::time::{Duration as StdDuration, Instant};
+
+use super::cache::{self, Cache};
+use super::products;
+use super::stations::{self, Station};
+use super::{error, Product, MOS};
+
+impl Product {
+    fn name(self) -> &'static str {
+        match self {
+            Product::Mav => "mav",
+            Product::Mex => "mex",
+        }
+    }
+}
+
+/// Where a source serves its bulletins
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BodyFormat {
+    /// In the first `<pre>` block of an HTML page, like the NWS CGI
+    Html,
+    /// As the whole body
+    Text,
+    /// In a collective of many stations' bulletins making up the body, like the raw
+    /// products of the IEM AFOS archive
+    Collective,
+    /// In the collective of an api.weather.gov text product's `productText`
+    TextProduct,
+}
+
+/// A source of MOS bulletins
+#[derive(Clone, Debug, PartialEq)]
+pub struct Endpoint {
+    /// Reported as the service of `Error::Status`
+    pub name: &'static str,
+    /// URL template. `{icao}` and `{icao_lower}` are replaced by the station,
+    /// `{product}` by mav or mex and `{product_code}` by MAV or MEX. `{location}` is
+    /// replaced by the location of the collective carrying the station, see
+    /// `stations::collective()`. Stations without one are searched for in the products
+    /// API with `TextProduct` and skipped as not found otherwise
+    pub url: String,
+    pub format: BodyFormat,
+}
+
+impl Endpoint {
+    pub fn new(name: &'static str, url: &str, format: BodyFormat) -> Endpoint {
+        Endpoint {
+            name,
+            url: url.to_string(),
+            format,
+        }
+    }
+
+    /// The NWS MOS CGI, which `fetch` uses
+    pub fn nws() -> Endpoint {
+        Endpoint::new(
+            "nws",
+            "https://www.nws.noaa.gov/cgi-bin/mos/get{product}.pl?sta={icao}",
+            BodyFormat::Html,
+        )
+    }
+
+    /// The latest collectives of the api.weather.gov text product API
+    pub fn products_api() -> Endpoint {
+        Endpoint::new(
+            "api.weather.gov",
+            "https://api.weather.gov/products/types/{product_code}/locations/{location}/latest",
+            BodyFormat::TextProduct,
+        )
+    }
+
+    /// The Iowa Environmental Mesonet's archive of NWS text products, by AWIPS ID. Not
+    /// one of the `SOURCES` presets until data/mos_stations.txt gives the collective
+    /// locations its IDs are made of, which `cia-backed gen-stations` fills in
+    pub fn iem() -> Endpoint {
+        Endpoint::new(
+            "iem",
+            "https://mesonet.agron.iastate.edu/cgi-bin/afos/retrieve.py?pil={product_code}{location}&fmt=text&limit=1",
+            BodyFormat::Collective,
+        )
+    }
+
+    /// A preset by the name in `SOURCES`
+    pub fn named(name: &str) -> Result<Endpoint, error::Error> {
+        match name.trim().to_lowercase().as_str() {
+            "nws" => Ok(Endpoint::nws()),
+            "api.weather.gov" => Ok(Endpoint::products_api()),
+            _ => Err(error::Error::unknown("source", name)),
+        }
+    }
+
+    /// Whether the station's bulletin can be asked for here. Endpoints needing the
+    /// location of its collective can't where data/mos_stations.txt doesn't have it,
+    /// except the products API, which is searched for it
+    pub fn serves(&self, icao: &str, product: Product) -> bool {
+        self.format == BodyFormat::TextProduct || self.url_for(icao, product).is_some()
+    }
+
+    /// None when the template needs a collective location the station table doesn't
+    /// have for the station
+    fn url_for(&self, icao: &str, product: Product) -> Option<String> {
+        let mut url = self
+            .url
+            .replace("{icao}", icao)
+            .replace("{icao_lower}", &icao.to_lowercase())
+            .replace("{product_code}", product.code())
+            .replace("{product}", product.name());
+        if url.contains("{location}") {
+            url = url.replace("{location}", stations::collective(icao, product)?);
+        }
+        Some(url)
+    }
+}
+
+/// Names of the `Endpoint::named()` presets, in the order they're preferred
+pub const SOURCES: &[&str] = &["nws", "api.weather.gov"];
+
+/// A response as far as the client cares about it
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Performs the GET requests for the client, so tests can swap in canned responses and
+/// simulated failures instead of hitting the NWS
+pub trait Transport {
+    fn get(&self, url: &str) -> Result<Response, error::Error>;
+
+    /// A GET made conditional on the validators of an earlier response, returning the
+    /// new response's own. Unchanged pages come back as a 304. Transports without
+    /// support make a plain GET and return no validators, which only costs bandwidth
+    fn get_if_changed(
+        &self,
+        url: &str,
+        _validators: &Validators,
+    ) -> Result<(Response, Validators), error::Error> {
+        Ok((self.get(url)?, Validators::default()))
+    }
+}
+
+/// The `ETag` and `Last-Modified` of a response, sent back as `If-None-Match` and
+/// `If-Modified-Since`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// Sent with every request by the default transport. api.weather.gov refuses requests
+/// without one
+pub const USER_AGENT: &str = concat!(
+    "cia-backed/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/ajpauwels/cia-backed)"
+);
+
+/// The default transport over a reqwest client, reused across requests
+#[derive(Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            reqwest::header::HeaderValue::from_static(USER_AGENT),
+        );
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap_or_default();
+        ReqwestTransport { client }
+    }
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+static DEFAULT_TRANSPORT: Lazy<ReqwestTransport> = Lazy::new(ReqwestTransport::default);
+
+/// A transport built on first use and shared by every caller in the process, so
+/// connections and TLS sessions get reused, e.g. across invocations of a warm Lambda
+pub fn default_transport() -> &'static ReqwestTransport {
+    &DEFAULT_TRANSPORT
+}
+
+/// The client of the default transport, for posting to webhooks
+pub(crate) fn http_client() -> &'static reqwest::Client {
+    &DEFAULT_TRANSPORT.client
+}
+
+impl Transport for ReqwestTransport {
+    fn get(&self, url: &str) -> Result<Response, error::Error> {
+        let mut res = self.client.get(url).send().map_err(from_reqwest)?;
+        let status = res.status().as_u16();
+        let body = res.text().map_err(from_reqwest)?;
+        Ok(Response { status, body })
+    }
+
+    fn get_if_changed(
+        &self,
+        url: &str,
+        validators: &Validators,
+    ) -> Result<(Response, Validators), error::Error> {
+        use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+        let mut req = self.client.get(url);
+        if let Some(etag) = &validators.etag {
+            req = req.header(IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            req = req.header(IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+        let mut res = req.send().map_err(from_reqwest)?;
+        let header = |name| {
+            res.headers()
+                .get(name)
+                .and_then(|val| val.to_str().ok())
+                .map(String::from)
+        };
+        let validators = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let status = res.status().as_u16();
+        let body = res.text().map_err(from_reqwest)?;
+        Ok((Response { status, body }, validators))
+    }
+}
+
+fn from_reqwest(err: reqwest::Error) -> error::Error {
+    if err.is_timeout() {
+        error::Error::Timeout
+    } else {
+        error::Error::from(err)
+    }
+}
+
+pub fn get(icao: &str) -> Result<MOS, error::Error> {
+    get_product(icao, Product::Mav)
+}
+
+pub fn get_product(icao: &str, product: Product) -> Result<MOS, error::Error> {
+    fetch(default_transport(), icao, product)
+}
+
+/// Stations tried by `get_near` before giving up
+const NEAR_CANDIDATES: usize = 3;
+
+/// How far from the point `get_near` looks for a station. MOS guidance is for the
+/// station's own terminal; much past this it says little about the point
+pub const NEAR_MAX_KM: f64 = 100.0;
+
+/// A forecast for the station closest to a point
+#[derive(Clone, Debug, Serialize)]
+pub struct Nearby {
+    pub station: &'static Station,
+    /// From the point, in nautical miles
+    pub distance_nm: f64,
+    pub mos: MOS,
+}
+
+/// MAV guidance for the nearest supported station in the embedded table with a bulletin
+pub fn get_near(lat: f64, lon: f64) -> Result<Nearby, error::Error> {
+    fetch_near(default_transport(), lat, lon, NEAR_MAX_KM, Product::Mav)
+}
+
+/// Fetches the bulletin of the station closest to a point, moving on to the next
+/// closest while the NWS has none for it. Fails with `Error::UnknownStation` when the
+/// table has no station within `max_km`
+pub fn fetch_near<T: Transport + ?Sized>(
+    transport: &T,
+    lat: f64,
+    lon: f64,
+    max_km: f64,
+    product: Product,
+) -> Result<Nearby, error::Error> {
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(error::Error::unknown(
+            "coordinates",
+            &format!("{},{}", lat, lon),
+        ));
+    }
+
+    let candidates = stations::nearest(lat, lon, NEAR_CANDIDATES, max_km);
+    if candidates.is_empty() {
+        return Err(error::Error::UnknownStation(format!("{},{}", lat, lon)));
+    }
+    let mut last_err = error::Error::MissingData;
+    for (station, distance_nm) in candidates {
+        match fetch(transport, station.icao, product) {
+            Ok(mos) => {
+                return Ok(Nearby {
+                    station,
+                    distance_nm,
+                    mos,
+                })
+            }
+            Err(err @ error::Error::StationNotFound(_)) => last_err = err,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(last_err)
+}
+
+/// Fetches and parses the station's latest bulletin through the given transport
+pub fn fetch<T: Transport + ?Sized>(
+    transport: &T,
+    icao: &str,
+    product: Product,
+) -> Result<MOS, error::Error> {
+    fetch_from(transport, &Endpoint::nws(), icao, product)
+}
+
+/// Like `fetch`, taking the bulletin from the cache while it's the latest cycle and
+/// caching what gets fetched until the next one is due. A failing cache only costs
+/// the fetch it would have saved
+pub fn fetch_cached<T: Transport + ?Sized, C: Cache + ?Sized>(
+    transport: &T,
+    cache: &C,
+    icao: &str,
+    product: Product,
+) -> Result<MOS, error::Error> {
+    let now = Utc::now();
+    let key = cache::key(icao, product, cache::expected_cycle(product, now));
+    if let Ok(Some(mos)) = cache.get(&key) {
+        return Ok(mos);
+    }
+
+    let mos = fetch(transport, icao, product)?;
+    let _ = cache.put(&key, &mos, cache::ttl(product, mos.meta.timestamp, now));
+    Ok(mos)
+}
+
+/// Like `fetch`, from another source
+pub fn fetch_from<T: Transport + ?Sized>(
+    transport: &T,
+    endpoint: &Endpoint,
+    icao: &str,
+    product: Product,
+) -> Result<MOS, error::Error> {
+    let icao = icao.to_uppercase();
+    let url = match endpoint.url_for(&icao, product) {
+        Some(url) => url,
+        // The products API can be searched for the collective instead
+        None if endpoint.format == BodyFormat::TextProduct => {
+            return products::fetch(transport, &icao, product)
+        }
+        None => return Err(error::Error::StationNotFound(icao)),
+    };
+    let res = transport.get(&url)?;
+    from_response(endpoint, &icao, &res)
+}
+
+/// The bulletin in a response to a request for the station's page
+fn from_response(endpoint: &Endpoint, icao: &str, res: &Response) -> Result<MOS, error::Error> {
+    let not_found = || error::Error::StationNotFound(icao.to_string());
+    if res.status == 404 {
+        return Err(not_found());
+    }
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: endpoint.name,
+            status: res.status,
+        });
+    }
+
+    // Unknown stations get the same page with no bulletin in it, either without a pre
+    // block at all or with one that doesn't open with the station's header. So do
+    // outages, but those say so
+    let body = match endpoint.format {
+        BodyFormat::Html => extract_pre(&res.body),
+        BodyFormat::Text => Ok(res
+            .body
+            .trim_start_matches(|c| c == '\n' || c == '\r')
+            .to_string()),
+        BodyFormat::Collective => Ok(products::bulletin(&res.body, icao).unwrap_or_default()),
+        BodyFormat::TextProduct => products::product_text(&res.body)
+            .map(|text| products::bulletin(&text, icao).unwrap_or_default()),
+    };
+    let raw_mos = match body {
+        Ok(raw_mos) if is_bulletin_for(&raw_mos, icao) => raw_mos,
+        Ok(_) | Err(error::Error::MissingData) => {
+            return Err(match unavailable_notice(&res.body) {
+                Some(notice) => error::Error::UpstreamUnavailable(notice),
+                None => not_found(),
+            })
+        }
+        Err(err) => return Err(err),
+    };
+
+    MOS::from_string(raw_mos)
+}
+
+/// Wait before the first poll that misses the next cycle, doubled after each miss
+const MIN_POLL_SECS: u64 = 60;
+
+/// Longest wait between polls for the next cycle
+const MAX_POLL_SECS: u64 = 600;
+
+/// How long before a cycle usually shows up polling for it starts
+const EARLY_POLL_HOURS: i64 = 1;
+
+/// MAV guidance for a newer cycle than `current`, blocking until it's published
+pub fn await_next_cycle(
+    icao: &str,
+    current: DateTime<Utc>,
+    timeout: StdDuration,
+) -> Result<MOS, error::Error> {
+    wait_for_cycle(default_transport(), icao, Product::Mav, current, timeout)
+}
+
+/// Polls the NWS until it has a bulletin for a newer cycle than `current`, so each run
+/// can be processed exactly once as soon as it lands. Sleeps until shortly before the
+/// next cycle is usually published, then polls with conditional GETs, backing off
+/// after each miss. Outages and failed requests are polled through. Fails with
+/// `Error::NoNewCycle`, without sleeping it out, once the next poll would come after
+/// `timeout`
+pub fn wait_for_cycle<T: Transport + ?Sized>(
+    transport: &T,
+    icao: &str,
+    product: Product,
+    current: DateTime<Utc>,
+    timeout: StdDuration,
+) -> Result<MOS, error::Error> {
+    let icao = icao.to_uppercase();
+    let endpoint = Endpoint::nws();
+    let url = match endpoint.url_for(&icao, product) {
+        Some(url) => url,
+        None => return Err(error::Error::StationNotFound(icao)),
+    };
+    let deadline = Instant::now() + timeout;
+    let due = current
+        + Duration::hours(
+            cache::cycle_hours(product) + cache::PUBLICATION_DELAY_HOURS - EARLY_POLL_HOURS,
+        );
+    let mut wait = (due - Utc::now()).to_std().unwrap_or_default();
+    let mut backoff = StdDuration::from_secs(MIN_POLL_SECS);
+    let mut validators = Validators::default();
+    loop {
+        if wait > deadline.saturating_duration_since(Instant::now()) {
+            return Err(error::Error::NoNewCycle(current));
+        }
+        thread::sleep(wait);
+        wait = backoff;
+        backoff = (backoff * 2).min(StdDuration::from_secs(MAX_POLL_SECS));
+
+        let res = match transport.get_if_changed(&url, &validators) {
+            Ok((res, _)) if res.status == 304 => continue,
+            Ok((res, fresh)) => {
+                validators = fresh;
+                res
+            }
+            Err(err) if is_transient(&err) => continue,
+            Err(err) => return Err(err),
+        };
+        match from_response(&endpoint, &icao, &res) {
+            Ok(mos) if mos.meta.timestamp > current => return Ok(mos),
+            Ok(_) => (),
+            Err(err) if is_transient(&err) => (),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Failures worth polling through, because the next try may well work
+fn is_transient(err: &error::Error) -> bool {
+    matches!(
+        err,
+        error::Error::Http { .. }
+            | error::Error::Timeout
+            | error::Error::Status { .. }
+            | error::Error::UpstreamUnavailable(_)
+    )
+}
+
+fn is_bulletin_for(raw_mos: &str, icao: &str) -> bool {
+    raw_mos
+        .split_whitespace()
+        .next()
+        .map_or(false, |first| first.eq_ignore_ascii_case(icao))
+}
+
+/// Words that only show up on maintenance and error pages
+const OUTAGE_WORDS: [&str; 7] = [
+    "maintenance",
+    "temporarily unavailable",
+    "service unavailable",
+    "internal server error",
+    "outage",
+    "try again later",
+    "an error occurred",
+];
+
+/// The first line of a page without a bulletin that reads like an outage notice, with
+/// its tags stripped, e.g. "The MOS service is down for scheduled maintenance"
+fn unavailable_notice(body: &str) -> Option<String> {
+    let text = strip_tags(body);
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .find(|line| {
+            let lower = line.to_ascii_lowercase();
+            OUTAGE_WORDS.iter().any(|word| lower.contains(word))
+        })
+}
+
+/// Tags become line breaks so headings and paragraphs stay on their own lines
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push('\n');
+            }
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    unescape(&text)
+}
+
+/// Text of the first `<pre>` block up to its first nested tag. The CGI pages are simple
+/// enough that a tag scan does the job of a full HTML parser
+pub(crate) fn extract_pre(html: &str) -> Result<String, error::Error> {
+    let not_found = || error::Error::MissingData;
+    // ASCII lowercasing keeps byte offsets the same
+    let lower = html.to_ascii_lowercase();
+
+    let mut from = 0;
+    let open = loop {
+        let at = from + lower[from..].find("<pre").ok_or_else(not_found)?;
+        match lower[at + 4..].chars().next() {
+            Some('>') | Some(' ') | Some('\t') | Some('\r') | Some('\n') => break at,
+            _ => from = at + 4,
+        }
+    };
+    let start = open + lower[open..].find('>').ok_or_else(not_found)? + 1;
+    let end = lower[start..]
+        .find('<')
+        .map(|len| start + len)
+        .unwrap_or_else(|| html.len());
+
+    // A newline right after the opening tag isn't part of the content
+    let text = &html[start..end];
+    let text = text
+        .strip_prefix("\r\n")
+        .or_else(|| text.strip_prefix('\n'))
+        .unwrap_or(text);
+
+    Ok(unescape(text))
+}
+
+/// Decodes the character references that show up in bulletin text
+pub(crate) fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use super::category::FlightCategory;
+use super::metar::Metar;
+use super::units::Units;
+use super::wind::turn;
+use super::{MOSEntry, MOS};
+
+/// The MOS entry nearest an observation and how far off it is. Errors are forecast
+/// minus observed, in the units of the MOS
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Comparison {
+    pub valid: DateTime<Utc>,
+    pub observed: DateTime<Utc>,
+    pub temperature_error: Option<f64>,
+    pub dew_point_error: Option<f64>,
+    pub wind_speed_error: Option<f64>,
+    /// Degrees from the observed direction to the forecast one, positive clockwise and
+    /// at most 180 either way. None for calm or variable winds
+    pub wind_direction_error: Option<isize>,
+    pub forecast_category: Option<FlightCategory>,
+    pub observed_category: FlightCategory,
+}
+
+/// Furthest a valid time can be from the observation and still be compared
+const MAX_OFFSET_HOURS: i64 = 3;
+
+/// Lines up the MOS entry nearest the observation time with the observation. None when
+/// no entry is within three hours of it
+pub fn mos_vs_obs(mos: &MOS, metar: &Metar) -> Option<Comparison> {
+    let entry = mos
+        .entries
+        .iter()
+        .min_by_key(|entry| (entry.timestamp - metar.time).num_seconds().abs())?;
+    if (entry.timestamp - metar.time).num_seconds().abs()
+        > Duration::hours(MAX_OFFSET_HOURS).num_seconds()
+    {
+        return None;
+    }
+
+    let (temperature, dew_point) = match entry.units {
+        Units::Imperial => (metar.temperature_f(), metar.dew_point_f()),
+        Units::Metric => (
+            metar.temperature.map(|t| t as f64),
+            metar.dew_point.map(|td| td as f64),
+        ),
+    };
+    let wind_speed = metar.wind.map(|wind| match entry.units {
+        Units::Imperial => wind.speed as f64,
+        Units::Metric => wind.speed as f64 * 1.852,
+    });
+
+    Some(Comparison {
+        valid: entry.timestamp,
+        observed: metar.time,
+        temperature_error: error(entry.tmp, temperature),
+        dew_point_error: error(entry.dpt, dew_point),
+        wind_speed_error: error(entry.wsp, wind_speed),
+        wind_direction_error: direction_error(entry, metar),
+        forecast_category: entry.flight_category(),
+        observed_category: metar.flight_category(),
+    })
+}
+
+impl Comparison {
+    /// True when the forecast and observed flight categories differ
+    pub fn category_mismatch(&self) -> bool {
+        match self.forecast_category {
+            Some(category) => category != self.observed_category,
+            None => false,
+        }
+    }
+
+    /// E.g. "MOS is running 3° warm"
+    pub fn temperature_bias(&self) -> Option<String> {
+        let error = self.temperature_error?.round() as isize;
+        Some(match error {
+            0 => String::from("MOS temperature is on target"),
+            e if e > 0 => format!("MOS is running {}° warm", e),
+            e => format!("MOS is running {}° cold", -e),
+        })
+    }
+}
+
+fn error(forecast: Option<isize>, observed: Option<f64>) -> Option<f64> {
+    Some(forecast? as f64 - observed?)
+}
+
+fn direction_error(entry: &MOSEntry, metar: &Metar) -> Option<isize> {
+    let wind = metar.wind?;
+    if wind.speed == 0 {
+        return None;
+    }
+    Some(turn(wind.direction?, entry.wdr? * 10))
+}
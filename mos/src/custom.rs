@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{ParseOptions, MOS};
+
+type Value = Arc<dyn Any + Send + Sync>;
+type Handler = Arc<dyn Fn(&str) -> Option<Value> + Send + Sync>;
+
+/// Parsers for rows by bulletin label, e.g. for experimental or regional rows the
+/// crate doesn't model
+#[derive(Clone, Default)]
+pub struct RowHandlers(HashMap<String, Handler>);
+
+impl RowHandlers {
+    /// Parses every non-blank cell of the row labelled `label` with `parse`. What it
+    /// returns is kept in `MOS::custom` under the label
+    pub fn register<T, F>(&mut self, label: &str, parse: F)
+    where
+        T: Any + Send + Sync,
+        F: Fn(&str) -> Option<T> + Send + Sync + 'static,
+    {
+        let handler: Handler =
+            Arc::new(move |cell: &str| parse(cell).map(|val| Arc::new(val) as Value));
+        self.0.insert(label.trim().to_uppercase(), handler);
+    }
+
+    pub(crate) fn get(&self, label: &str) -> Option<&Handler> {
+        self.0.get(label)
+    }
+}
+
+impl fmt::Debug for RowHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// Values of the rows parsed by `RowHandlers`, one per entry. They aren't serialized,
+/// and `MOS`'s `PartialEq` leaves them out
+#[derive(Clone, Default)]
+pub struct CustomRows(HashMap<String, Vec<Option<Value>>>);
+
+impl CustomRows {
+    /// Labels of the rows with values
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = self.0.keys().map(String::as_str).collect();
+        labels.sort();
+        labels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, label: &str, values: Vec<Option<Value>>) {
+        self.0.insert(label.to_string(), values);
+    }
+}
+
+impl fmt::Debug for CustomRows {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.labels()).finish()
+    }
+}
+
+impl ParseOptions {
+    /// With `parse` registered for the row labelled `label`, see `RowHandlers::register`
+    pub fn with_row<T, F>(mut self, label: &str, parse: F) -> ParseOptions
+    where
+        T: Any + Send + Sync,
+        F: Fn(&str) -> Option<T> + Send + Sync + 'static,
+    {
+        self.rows.register(label, parse);
+        self
+    }
+}
+
+impl MOS {
+    /// Values of a row parsed by a handler in `ParseOptions::rows`, one per entry. None
+    /// if no handler ran for the label or it produced values of another type
+    pub fn custom<T: Any>(&self, label: &str) -> Option<Vec<Option<&T>>> {
+        let values = self.custom.0.get(&label.trim().to_uppercase())?;
+        let mut typed = Vec::with_capacity(values.len());
+        for val in values {
+            match val {
+                Some(val) => typed.push(Some(val.downcast_ref::<T>()?)),
+                None => typed.push(None),
+            }
+        }
+        Some(typed)
+    }
+}
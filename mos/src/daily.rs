@@ -0,0 +1,105 @@
+use chrono::{NaiveDate, Timelike};
+use serde::Serialize;
+
+use super::precip::{PrecipType, QpfRange};
+use super::units::Units;
+use super::{MOSEntry, MOS};
+
+/// PoP from which a period's precipitation type counts toward the day's
+const MENTION_POP: isize = 20;
+
+/// What a day's forecast comes down to, for a local calendar day
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    /// The daytime maximum from N/X, or the highest TMP without one
+    pub high: Option<isize>,
+    /// The nighttime minimum from N/X, or the lowest TMP without one
+    pub low: Option<isize>,
+    /// Highest P06 or P12 valid during the day
+    pub max_pop: Option<isize>,
+    /// Sum of the day's Q06 periods, or its Q12 periods where there's no Q06
+    pub qpf: Option<QpfRange>,
+    /// The most common CLD code
+    pub sky: Option<String>,
+    /// The most common type among periods with a PoP of 20% or more
+    pub precip_type: Option<PrecipType>,
+    pub units: Units,
+}
+
+impl MOS {
+    /// One summary per day of `by_day()`. Days cut short by the end of the forecast only
+    /// cover the hours forecast
+    pub fn daily_summaries(&self) -> Vec<DailySummary> {
+        let mut start = 0;
+        self.by_day()
+            .into_iter()
+            .map(|day| {
+                let range = start..start + day.entries.len();
+                start = range.end;
+                summarize_day(self, day.date, range)
+            })
+            .collect()
+    }
+}
+
+fn summarize_day(mos: &MOS, date: NaiveDate, range: std::ops::Range<usize>) -> DailySummary {
+    let entries = &mos.entries[range.clone()];
+
+    // Maxima verify at 00Z and minima at 12Z, as in `summarize()`
+    let maxima = entries.iter().filter(|e| is_max(e)).filter_map(|e| e.nx);
+    let minima = entries.iter().filter(|e| !is_max(e)).filter_map(|e| e.nx);
+    let temps = || entries.iter().filter_map(|e| e.tmp);
+
+    let qpf = sum_qpf(entries.iter().filter_map(|e| e.q06))
+        .or_else(|| sum_qpf(entries.iter().filter_map(|e| e.q12)));
+
+    let precip_type = predominant(
+        range
+            .clone()
+            .filter(|&i| mos.period_pop(i).map_or(false, |pop| pop >= MENTION_POP))
+            .map(|i| mos.entries[i].precip_type()),
+    );
+
+    DailySummary {
+        date,
+        high: maxima.max().or_else(|| temps().max()),
+        low: minima.min().or_else(|| temps().min()),
+        max_pop: entries.iter().filter_map(|e| e.p06.max(e.p12)).max(),
+        qpf,
+        sky: predominant(entries.iter().filter_map(|e| e.cld.clone())),
+        precip_type,
+        units: entries.first().map_or(mos.units, |e| e.units),
+    }
+}
+
+fn is_max(entry: &MOSEntry) -> bool {
+    !(6..18).contains(&entry.timestamp.hour())
+}
+
+fn sum_qpf<I: Iterator<Item = isize>>(categories: I) -> Option<QpfRange> {
+    categories
+        .filter_map(QpfRange::from_category)
+        .fold(None, |total: Option<QpfRange>, qpf| {
+            Some(total.map_or(qpf, |total| total.add(qpf)))
+        })
+}
+
+/// Most frequent item, the earliest of those tied
+fn predominant<T: PartialEq, I: Iterator<Item = T>>(items: I) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for item in items {
+        match counts.iter_mut().find(|(seen, _)| *seen == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+
+    let mut best: Option<(T, usize)> = None;
+    for (item, count) in counts {
+        if best.as_ref().map_or(true, |(_, most)| count > *most) {
+            best = Some((item, count));
+        }
+    }
+    best.map(|(item, _)| item)
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+use super::row::change;
+use super::{MOSEntry, MOS};
+
+/// All element changes at one valid time between two cycles
+#[derive(Debug, Serialize)]
+pub struct EntryDiff {
+    pub timestamp: DateTime<Utc>,
+    pub changes: Vec<Change>,
+}
+
+/// A single element that differs between cycles. `delta` is set for numeric elements
+/// present in both, so a ceiling category drop shows up as a negative CIG delta
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub element: &'static str,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub delta: Option<isize>,
+}
+
+impl MOS {
+    /// Compares this cycle against a newer one for every valid time both forecast, in
+    /// valid time order. Valid times without any change are left out
+    pub fn diff(&self, newer: &MOS) -> Vec<EntryDiff> {
+        self.entries
+            .iter()
+            .filter_map(|old| {
+                newer
+                    .entries
+                    .iter()
+                    .find(|new| new.timestamp == old.timestamp)
+                    .map(|new| EntryDiff {
+                        timestamp: old.timestamp,
+                        changes: entry_changes(old, new),
+                    })
+            })
+            .filter(|diff| !diff.changes.is_empty())
+            .collect()
+    }
+}
+
+fn entry_changes(old: &MOSEntry, new: &MOSEntry) -> Vec<Change> {
+    let text: [(&'static str, &Option<String>, &Option<String>); 3] = [
+        ("CLD", &old.cld, &new.cld),
+        ("TYP", &old.typ, &new.typ),
+        ("OBV", &old.obv, &new.obv),
+    ];
+
+    let mut changes: Vec<Change> = old
+        .numeric_elements()
+        .iter()
+        .zip(new.numeric_elements().iter())
+        .filter(|((_, from), (_, to))| from != to)
+        .map(|((element, from), (_, to))| Change {
+            element: *element,
+            from: from.map(|v| v.to_string()),
+            to: to.map(|v| v.to_string()),
+            delta: match (from, to) {
+                (Some(from), Some(to)) => Some(change(element, *from, *to)),
+                _ => None,
+            },
+        })
+        .collect();
+    changes.extend(
+        text.iter()
+            .filter(|(_, from, to)| from != to)
+            .map(|(element, from, to)| Change {
+                element: *element,
+                from: (*from).clone(),
+                to: (*to).clone(),
+                delta: None,
+            }),
+    );
+
+    changes
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let from = self.from.as_deref().unwrap_or("-");
+        let to = self.to.as_deref().unwrap_or("-");
+        match self.delta {
+            Some(delta) => write!(f, "{} {:+} ({} → {})", self.element, delta, from, to),
+            None => write!(f, "{} {} → {}", self.element, from, to),
+        }
+    }
+}
+
+impl fmt::Display for EntryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let changes = self
+            .changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "{}: {}",
+            self.timestamp.format("%a %d %HZ"),
+            changes.join(", ")
+        )
+    }
+}
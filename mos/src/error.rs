@@ -0,0 +1,208 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Everything that can go wrong fetching, parsing or exporting a bulletin
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The request to the NWS or a webhook failed before getting a response
+    #[cfg(feature = "client")]
+    #[error("request failed: {source}")]
+    Http {
+        #[from]
+        source: reqwest::Error,
+    },
+
+    #[error("request timed out")]
+    Timeout,
+
+    /// A service answered with a non-success status
+    #[error("{service} returned {status}")]
+    Status { service: &'static str, status: u16 },
+
+    /// The source answered with a maintenance or error notice instead of a bulletin,
+    /// holding the notice's text
+    #[error("upstream unavailable: {0}")]
+    UpstreamUnavailable(String),
+
+    /// No cycle newer than the one held was published before the deadline
+    #[cfg(feature = "client")]
+    #[error("no cycle after {0} was published in time")]
+    NoNewCycle(chrono::DateTime<chrono::Utc>),
+
+    /// The NWS has no bulletin for the station
+    #[error("station not found: {0}")]
+    StationNotFound(String),
+
+    /// The station is missing from the embedded station table, or no station in it is
+    /// near enough the point
+    #[error("no metadata for station {0}")]
+    UnknownStation(String),
+
+    /// The page came back without a data block
+    #[error("did not find a pre block containing the data")]
+    MissingData,
+
+    #[error("mos string is empty")]
+    EmptyBulletin,
+
+    /// A row of the bulletin couldn't be read
+    #[error("could not parse {row}: {msg}")]
+    Parse { row: String, msg: String },
+
+    /// A cell that doesn't hold a value of its row, in strict mode
+    #[error("could not parse {row} in column {column} ({hour}): {text:?}")]
+    Cell {
+        row: String,
+        column: usize,
+        /// The column's label on the hour line
+        hour: String,
+        text: String,
+    },
+
+    #[error("invalid timestamp: {0}")]
+    Timestamp(#[from] chrono::format::ParseError),
+
+    /// A name that doesn't match any variant of a `FromStr` enum
+    #[error("unknown {kind}: {value}")]
+    Unknown { kind: &'static str, value: String },
+
+    /// Reading a bulletin failed, with the file it came from if there was one
+    #[error("could not read {}: {source}", source_name(.path))]
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+
+    #[error("json serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Arrow(#[from] arrow::error::ArrowError),
+
+    #[cfg(feature = "parquet")]
+    #[error(transparent)]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[cfg(feature = "polars")]
+    #[error(transparent)]
+    Polars(#[from] polars::prelude::PolarsError),
+
+    #[cfg(feature = "rmp-serde")]
+    #[error("msgpack serialization failed: {0}")]
+    MsgpackEncode(#[from] rmp_serde::encode::Error),
+
+    #[cfg(feature = "rmp-serde")]
+    #[error("msgpack deserialization failed: {0}")]
+    MsgpackDecode(#[from] rmp_serde::decode::Error),
+
+    #[cfg(feature = "serde_cbor")]
+    #[error("cbor serialization failed: {0}")]
+    Cbor(#[from] serde_cbor::Error),
+
+    #[cfg(feature = "quick-xml")]
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("parquet column does not match the schema")]
+    SchemaMismatch,
+
+    /// Reading or writing the forecast archive failed
+    #[cfg(any(feature = "dynamodb", feature = "s3"))]
+    #[error("storage: {0}")]
+    Storage(String),
+
+    /// Reading or writing the forecast cache failed
+    #[cfg(any(feature = "redis", feature = "dynamodb"))]
+    #[error("cache: {0}")]
+    Cache(String),
+
+    /// Connecting or publishing to the MQTT broker failed
+    #[cfg(feature = "mqtt")]
+    #[error("mqtt: {0}")]
+    Mqtt(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl Error {
+    /// Stable identifier of the kind of failure, for matching in alerting rules and
+    /// clients. Unlike the messages, these don't change between releases
+    pub fn code(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "client")]
+            Error::Http { .. } => "E_UPSTREAM",
+            Error::Timeout => "E_UPSTREAM_TIMEOUT",
+            Error::Status { .. } => "E_UPSTREAM_STATUS",
+            Error::UpstreamUnavailable(_) => "E_UPSTREAM_UNAVAILABLE",
+            #[cfg(feature = "client")]
+            Error::NoNewCycle(_) => "E_NO_NEW_CYCLE",
+            Error::StationNotFound(_) => "E_STATION_NOT_FOUND",
+            Error::UnknownStation(_) => "E_UNKNOWN_STATION",
+            Error::MissingData => "E_NO_DATA",
+            Error::EmptyBulletin => "E_EMPTY_BULLETIN",
+            Error::Parse { row, .. } if row == "HR" => "E_PARSE_HOURLINE",
+            Error::Parse { row, .. } if row == "header" => "E_PARSE_HEADER",
+            Error::Parse { .. } => "E_PARSE",
+            Error::Cell { .. } => "E_PARSE_CELL",
+            Error::Timestamp(_) => "E_PARSE_TIMESTAMP",
+            Error::Unknown { .. } => "E_INVALID_ARGUMENT",
+            Error::Io { .. } => "E_IO",
+            Error::Json(_) => "E_SERIALIZE",
+            #[cfg(feature = "rmp-serde")]
+            Error::MsgpackEncode(_) | Error::MsgpackDecode(_) => "E_SERIALIZE",
+            #[cfg(feature = "arrow")]
+            Error::Arrow(_) => "E_EXPORT",
+            #[cfg(feature = "parquet")]
+            Error::Parquet(_) => "E_EXPORT",
+            #[cfg(feature = "polars")]
+            Error::Polars(_) => "E_EXPORT",
+            #[cfg(feature = "serde_cbor")]
+            Error::Cbor(_) => "E_SERIALIZE",
+            #[cfg(feature = "quick-xml")]
+            Error::Xml(_) => "E_EXPORT",
+            Error::SchemaMismatch => "E_EXPORT_SCHEMA",
+            #[cfg(any(feature = "dynamodb", feature = "s3"))]
+            Error::Storage(_) => "E_STORAGE",
+            #[cfg(any(feature = "redis", feature = "dynamodb"))]
+            Error::Cache(_) => "E_CACHE",
+            #[cfg(feature = "mqtt")]
+            Error::Mqtt(_) => "E_PUBLISH",
+        }
+    }
+
+    pub(crate) fn parse(row: &str, msg: &str) -> Error {
+        Error::Parse {
+            row: row.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    pub(crate) fn cell(row: &str, column: usize, hour: &str, text: &str) -> Error {
+        Error::Cell {
+            row: row.to_string(),
+            column,
+            hour: hour.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    pub(crate) fn unknown(kind: &'static str, value: &str) -> Error {
+        Error::Unknown {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+fn source_name(path: &Option<PathBuf>) -> String {
+    match path {
+        Some(path) => path.display().to_string(),
+        None => String::from("bulletin"),
+    }
+}
@@ -0,0 +1,141 @@
+use arrow::array::{ArrayRef, Int32Array, StringArray, TimestampMillisecondArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use std::io::Write;
+use std::sync::Arc;
+
+use super::super::{error, MOSEntry, MOS};
+
+const INT_COLUMNS: &[&str] = &[
+    "nx",
+    "tmp",
+    "dpt",
+    "wdr",
+    "wsp",
+    "p06",
+    "p12",
+    "q06",
+    "q12",
+    "t06",
+    "t06_severe",
+    "t12",
+    "t12_severe",
+    "poz",
+    "pos",
+    "snw",
+    "cig",
+    "vis",
+];
+const STR_COLUMNS: &[&str] = &["cld", "typ", "obv"];
+
+/// Arrow schema of `MOS::to_record_batch()`, the same columns as `to_csv()` with the
+/// integer elements first and the categorical text elements last
+pub fn schema() -> SchemaRef {
+    let time = DataType::Timestamp(TimeUnit::Millisecond, None);
+    let mut fields = vec![
+        Field::new("icao", DataType::Utf8, false),
+        Field::new("cycle", time.clone(), false),
+        Field::new("timestamp", time, false),
+    ];
+    fields.extend(
+        INT_COLUMNS
+            .iter()
+            .map(|name| Field::new(name, DataType::Int32, true)),
+    );
+    fields.extend(
+        STR_COLUMNS
+            .iter()
+            .map(|name| Field::new(name, DataType::Utf8, true)),
+    );
+
+    Arc::new(Schema::new(fields))
+}
+
+fn int_value(entry: &MOSEntry, column: &str) -> Option<i32> {
+    let val = match column {
+        "nx" => entry.nx,
+        "tmp" => entry.tmp,
+        "dpt" => entry.dpt,
+        "wdr" => entry.wdr,
+        "wsp" => entry.wsp,
+        "p06" => entry.p06,
+        "p12" => entry.p12,
+        "q06" => entry.q06,
+        "q12" => entry.q12,
+        "t06" => entry.t06.map(|t| t.0),
+        "t06_severe" => entry.t06.map(|t| t.1),
+        "t12" => entry.t12.map(|t| t.0),
+        "t12_severe" => entry.t12.map(|t| t.1),
+        "poz" => entry.poz,
+        "pos" => entry.pos,
+        "snw" => entry.snw,
+        "cig" => entry.cig,
+        "vis" => entry.vis,
+        _ => None,
+    };
+    val.map(|v| v as i32)
+}
+
+fn str_value<'a>(entry: &'a MOSEntry, column: &str) -> Option<&'a str> {
+    match column {
+        "cld" => entry.cld.as_deref(),
+        "typ" => entry.typ.as_deref(),
+        "obv" => entry.obv.as_deref(),
+        _ => None,
+    }
+}
+
+impl MOS {
+    /// Converts the entries into a single Arrow record batch, one row per valid time
+    pub fn to_record_batch(&self) -> Result<RecordBatch, error::Error> {
+        let cycle = self.meta.timestamp.timestamp_millis();
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(
+                self.entries
+                    .iter()
+                    .map(|_| self.meta.icao.as_str())
+                    .collect::<Vec<&str>>(),
+            )),
+            Arc::new(TimestampMillisecondArray::from_vec(
+                self.entries.iter().map(|_| cycle).collect(),
+                None,
+            )),
+            Arc::new(TimestampMillisecondArray::from_vec(
+                self.entries
+                    .iter()
+                    .map(|e| e.timestamp.timestamp_millis())
+                    .collect(),
+                None,
+            )),
+        ];
+        columns.extend(INT_COLUMNS.iter().map(|column| {
+            Arc::new(Int32Array::from(
+                self.entries
+                    .iter()
+                    .map(|e| int_value(e, column))
+                    .collect::<Vec<Option<i32>>>(),
+            )) as ArrayRef
+        }));
+        columns.extend(STR_COLUMNS.iter().map(|column| {
+            Arc::new(StringArray::from(
+                self.entries
+                    .iter()
+                    .map(|e| str_value(e, column))
+                    .collect::<Vec<Option<&str>>>(),
+            )) as ArrayRef
+        }));
+
+        Ok(RecordBatch::try_new(schema(), columns)?)
+    }
+
+    /// Writes the record batch as an Arrow IPC stream
+    pub fn to_arrow_ipc<W: Write>(&self, writer: W) -> Result<(), error::Error> {
+        let batch = self.to_record_batch()?;
+        let mut stream = StreamWriter::try_new(writer, &batch.schema())?;
+        stream.write(&batch)?;
+        stream.finish()?;
+
+        Ok(())
+    }
+}
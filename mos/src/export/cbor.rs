@@ -0,0 +1,12 @@
+use super::super::{error, MOS};
+
+impl MOS {
+    /// CBOR encoding of the same structure as the JSON, with field names as map keys
+    pub fn to_cbor(&self) -> Result<Vec<u8>, error::Error> {
+        Ok(serde_cbor::to_vec(self)?)
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<MOS, error::Error> {
+        Ok(serde_cbor::from_slice(bytes)?)
+    }
+}
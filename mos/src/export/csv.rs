@@ -0,0 +1,88 @@
+use super::super::{MOSEntry, MOS};
+
+const COLUMNS: &[&str] = &[
+    "icao",
+    "cycle",
+    "timestamp",
+    "nx",
+    "tmp",
+    "dpt",
+    "cld",
+    "wdr",
+    "wsp",
+    "p06",
+    "p12",
+    "q06",
+    "q12",
+    "t06",
+    "t06_severe",
+    "t12",
+    "t12_severe",
+    "poz",
+    "pos",
+    "typ",
+    "snw",
+    "cig",
+    "vis",
+    "obv",
+];
+
+impl MOS {
+    /// One row per valid time with every element as a column. Missing values are empty
+    /// cells and the T06/T12 pairs are split into thunderstorm and severe columns
+    pub fn to_csv(&self) -> String {
+        let mut out = COLUMNS.join(",");
+        out.push('\n');
+        for entry in &self.entries {
+            out.push_str(&self.csv_row(entry).join(","));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn csv_row(&self, entry: &MOSEntry) -> Vec<String> {
+        vec![
+            escape(&self.meta.icao),
+            self.meta.timestamp.to_rfc3339(),
+            entry.timestamp.to_rfc3339(),
+            num(entry.nx),
+            num(entry.tmp),
+            num(entry.dpt),
+            text(&entry.cld),
+            num(entry.wdr),
+            num(entry.wsp),
+            num(entry.p06),
+            num(entry.p12),
+            num(entry.q06),
+            num(entry.q12),
+            num(entry.t06.map(|t| t.0)),
+            num(entry.t06.map(|t| t.1)),
+            num(entry.t12.map(|t| t.0)),
+            num(entry.t12.map(|t| t.1)),
+            num(entry.poz),
+            num(entry.pos),
+            text(&entry.typ),
+            num(entry.snw),
+            num(entry.cig),
+            num(entry.vis),
+            text(&entry.obv),
+        ]
+    }
+}
+
+fn num(val: Option<isize>) -> String {
+    val.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn text(val: &Option<String>) -> String {
+    val.as_ref().map(|v| escape(v)).unwrap_or_default()
+}
+
+fn escape(val: &str) -> String {
+    if val.contains(|c| c == ',' || c == '"' || c == '\n') {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_string()
+    }
+}
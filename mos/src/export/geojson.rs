@@ -0,0 +1,60 @@
+use chrono::Duration;
+use serde_json::{json, Value};
+
+use super::super::{error, stations, MOS};
+
+impl MOS {
+    /// GeoJSON point feature at the station with a summary of the first 24 hours as its
+    /// properties. Fails for stations missing from the embedded station table
+    pub fn to_geojson(&self) -> Result<Value, error::Error> {
+        let station = match stations::lookup(&self.meta.icao) {
+            Some(station) => station,
+            None => return Err(error::Error::UnknownStation(self.meta.icao.clone())),
+        };
+
+        Ok(json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "Point",
+                "coordinates": [station.lon, station.lat],
+            },
+            "properties": self.geojson_properties(station.name),
+        }))
+    }
+
+    fn geojson_properties(&self, name: &str) -> Value {
+        let end = self.meta.timestamp + Duration::hours(30);
+        let day = self.entries.iter().filter(|entry| entry.timestamp <= end);
+        let temps: Vec<isize> = day
+            .clone()
+            .flat_map(|entry| entry.tmp.into_iter().chain(entry.nx))
+            .collect();
+        let first = self.entries.first();
+
+        json!({
+            "icao": self.meta.icao,
+            "name": name,
+            "cycle": self.meta.timestamp.to_rfc3339(),
+            "valid": first.map(|entry| entry.timestamp.to_rfc3339()),
+            "tmp": first.and_then(|entry| entry.tmp),
+            "dpt": first.and_then(|entry| entry.dpt),
+            "cld": first.and_then(|entry| entry.cld.clone()),
+            "wdr": first.and_then(|entry| entry.wdr).map(|wdr| wdr * 10),
+            "wsp": first.and_then(|entry| entry.wsp),
+            "high": temps.iter().max(),
+            "low": temps.iter().min(),
+            "pop": day.filter_map(|entry| entry.p06.max(entry.p12)).max(),
+        })
+    }
+}
+
+/// FeatureCollection of every forecast that has station coordinates
+pub fn feature_collection(forecasts: &[MOS]) -> Value {
+    json!({
+        "type": "FeatureCollection",
+        "features": forecasts
+            .iter()
+            .filter_map(|mos| mos.to_geojson().ok())
+            .collect::<Vec<Value>>(),
+    })
+}
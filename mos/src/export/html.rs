@@ -0,0 +1,145 @@
+use super::super::daily::DailySummary;
+use super::super::precip::{PrecipType, QpfRange};
+use super::super::MOS;
+
+/// Inline so the page renders the same in mail clients, which drop linked stylesheets
+const STYLE: &str = "body{font-family:sans-serif;color:#222;margin:1em}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #ccc;padding:.25em .5em;text-align:right}\
+th{background:#f0f0f0}\
+caption{text-align:left;font-weight:bold;padding:.25em 0}";
+
+impl MOS {
+    /// Renders a self-contained HTML page with the daily summaries and a table of every
+    /// period, with inline styles and no scripts so it can go in an email body as is
+    pub fn to_html(&self) -> String {
+        let title = format!(
+            "{} MOS {}",
+            escape(&self.meta.icao),
+            self.meta.timestamp.format("%Y-%m-%d %HZ")
+        );
+        let mut html = vec![
+            String::from("<!DOCTYPE html>"),
+            String::from("<html>"),
+            String::from("<head>"),
+            String::from("<meta charset=\"utf-8\">"),
+            format!("<title>{}</title>", title),
+            format!("<style>{}</style>", STYLE),
+            String::from("</head>"),
+            String::from("<body>"),
+            format!("<h1>{}</h1>", title),
+        ];
+
+        let temp = self.units.temperature_symbol();
+        html.push(String::from("<table>"));
+        html.push(String::from("<caption>Daily summary</caption>"));
+        html.push(header(&[
+            "Day", "High", "Low", "PoP", "QPF (in)", "Sky", "Precip",
+        ]));
+        for day in self.daily_summaries() {
+            html.push(daily_row(&day, temp));
+        }
+        html.push(String::from("</table>"));
+
+        let speed = self.units.speed_symbol();
+        html.push(String::from("<table>"));
+        html.push(String::from("<caption>Forecast periods (UTC)</caption>"));
+        html.push(header(&[
+            "Valid",
+            "Temp",
+            "Dew point",
+            "Sky",
+            "Wind",
+            "P06",
+            "P12",
+            "Type",
+            "CIG",
+            "VIS",
+            "OBV",
+        ]));
+        for entry in &self.entries {
+            let wind = match (entry.wdr, entry.wsp) {
+                (Some(wdr), Some(wsp)) => format!("{:03}° {} {}", wdr * 10, wsp, speed),
+                _ => String::new(),
+            };
+            html.push(row(&[
+                entry.timestamp.format("%a %d %HZ").to_string(),
+                num(entry.tmp, temp),
+                num(entry.dpt, temp),
+                text(&entry.cld),
+                wind,
+                num(entry.p06, "%"),
+                num(entry.p12, "%"),
+                text(&entry.typ),
+                num(entry.cig, ""),
+                num(entry.vis, ""),
+                text(&entry.obv),
+            ]));
+        }
+        html.push(String::from("</table>"));
+
+        html.push(String::from("</body>"));
+        html.push(String::from("</html>"));
+        let mut out = html.join("\n");
+        out.push('\n');
+        out
+    }
+}
+
+fn daily_row(day: &DailySummary, temp: &str) -> String {
+    let precip = match day.precip_type {
+        Some(PrecipType::Rain) => "Rain",
+        Some(PrecipType::Snow) => "Snow",
+        Some(PrecipType::FreezingRain) => "Freezing rain",
+        None => "",
+    };
+    row(&[
+        day.date.format("%a %b %-d").to_string(),
+        num(day.high, temp),
+        num(day.low, temp),
+        num(day.max_pop, "%"),
+        day.qpf.as_ref().map(qpf).unwrap_or_default(),
+        text(&day.sky),
+        precip.to_string(),
+    ])
+}
+
+/// E.g. "0.10–0.24", or "2.00+" for the open-ended top category
+fn qpf(range: &QpfRange) -> String {
+    match range.max {
+        Some(max) if max <= range.min => format!("{:.2}", max),
+        Some(max) => format!("{:.2}–{:.2}", range.min, max),
+        None => format!("{:.2}+", range.min),
+    }
+}
+
+fn header(names: &[&str]) -> String {
+    let cells: Vec<String> = names
+        .iter()
+        .map(|name| format!("<th>{}</th>", name))
+        .collect();
+    format!("<tr>{}</tr>", cells.concat())
+}
+
+fn row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| format!("<td>{}</td>", escape(cell)))
+        .collect();
+    format!("<tr>{}</tr>", cells.concat())
+}
+
+fn num(val: Option<isize>, unit: &str) -> String {
+    val.map(|v| format!("{}{}", v, unit)).unwrap_or_default()
+}
+
+fn text(val: &Option<String>) -> String {
+    val.clone().unwrap_or_default()
+}
+
+fn escape(val: &str) -> String {
+    val.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
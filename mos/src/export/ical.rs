@@ -0,0 +1,97 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::super::{MOSEntry, MOS};
+
+impl MOS {
+    /// Renders an iCalendar feed with one event per forecast period, running from each
+    /// valid time to the next, titled with a short condition summary
+    pub fn to_ical(&self) -> String {
+        let mut lines = vec![
+            String::from("BEGIN:VCALENDAR"),
+            String::from("VERSION:2.0"),
+            String::from("PRODID:-//cia-backed//MOS//EN"),
+            format!("X-WR-CALNAME:{} MOS forecast", escape(&self.meta.icao)),
+        ];
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let end = match self.entries.get(i + 1) {
+                Some(next) => next.timestamp,
+                None => entry.timestamp + Duration::hours(3),
+            };
+            lines.push(String::from("BEGIN:VEVENT"));
+            lines.push(format!(
+                "UID:{}-{}-{}@cia-backed",
+                self.meta.icao,
+                self.meta.timestamp.format("%Y%m%d%H"),
+                entry.timestamp.format("%Y%m%d%H")
+            ));
+            lines.push(format!("DTSTAMP:{}", ical_time(self.meta.timestamp)));
+            lines.push(format!("DTSTART:{}", ical_time(entry.timestamp)));
+            lines.push(format!("DTEND:{}", ical_time(end)));
+            lines.push(format!("SUMMARY:{}", escape(&period_summary(entry))));
+            lines.push(String::from("END:VEVENT"));
+        }
+        lines.push(String::from("END:VCALENDAR"));
+
+        let mut out = lines.join("\r\n");
+        out.push_str("\r\n");
+        out
+    }
+}
+
+/// E.g. "34°F, OVC, 60% rain"
+fn period_summary(entry: &MOSEntry) -> String {
+    let mut parts = Vec::new();
+    if let Some(tmp) = entry.tmp {
+        parts.push(format!("{}{}", tmp, entry.units.temperature_symbol()));
+    }
+    if let Some(sky) = entry.cld.as_deref().and_then(sky_cover) {
+        parts.push(sky.to_string());
+    }
+    if let Some(pop) = entry.p06.or(entry.p12) {
+        let kind = entry
+            .typ
+            .as_deref()
+            .and_then(precip_word)
+            .unwrap_or("precip");
+        parts.push(format!("{}% {}", pop, kind));
+    }
+
+    if parts.is_empty() {
+        String::from("No forecast")
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn sky_cover(cld: &str) -> Option<&'static str> {
+    match cld {
+        "CL" => Some("CLR"),
+        "FW" => Some("FEW"),
+        "SC" => Some("SCT"),
+        "BK" => Some("BKN"),
+        "OV" => Some("OVC"),
+        _ => None,
+    }
+}
+
+fn precip_word(typ: &str) -> Option<&'static str> {
+    match typ {
+        "R" => Some("rain"),
+        "S" => Some("snow"),
+        "Z" => Some("freezing rain"),
+        _ => None,
+    }
+}
+
+fn ical_time(ts: DateTime<Utc>) -> String {
+    ts.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// TEXT value escaping from RFC 5545 section 3.3.11
+fn escape(val: &str) -> String {
+    val.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use super::super::{error, MOS};
+
+/// How timestamps are written in JSON output
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampFormat {
+    /// RFC 3339 in UTC, e.g. "2020-04-12T18:00:00Z". The default serde representation
+    Rfc3339,
+    /// Seconds since the Unix epoch
+    Epoch,
+    /// ISO 8601 with the station's local offset, e.g. "2020-04-12T14:00:00-04:00". Falls
+    /// back to UTC for stations missing from the embedded station table
+    Local,
+}
+
+impl Default for TimestampFormat {
+    fn default() -> Self {
+        TimestampFormat::Rfc3339
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rfc3339" | "utc" => Ok(TimestampFormat::Rfc3339),
+            "epoch" | "unix" => Ok(TimestampFormat::Epoch),
+            "local" => Ok(TimestampFormat::Local),
+            _ => Err(error::Error::unknown("timestamp format", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SerializeOptions {
+    #[serde(default)]
+    pub timestamps: TimestampFormat,
+}
+
+impl MOS {
+    /// JSON value of the forecast with the cycle and valid times written per `opts`, and
+    /// `meta.age_minutes` set from `age_minutes()` so UIs can flag stale runs
+    pub fn to_json_with(&self, opts: &SerializeOptions) -> Result<Value, error::Error> {
+        let mut val = serde_json::to_value(self)?;
+        val["meta"]["age_minutes"] = Value::from(self.age_minutes());
+        if opts.timestamps == TimestampFormat::Rfc3339 {
+            return Ok(val);
+        }
+
+        val["meta"]["timestamp"] = self.timestamp_value(self.meta.timestamp, opts.timestamps);
+        if let Some(entries) = val["entries"].as_array_mut() {
+            for (entry, val) in self.entries.iter().zip(entries.iter_mut()) {
+                val["timestamp"] = self.timestamp_value(entry.timestamp, opts.timestamps);
+            }
+        }
+
+        Ok(val)
+    }
+
+    fn timestamp_value(&self, ts: DateTime<Utc>, format: TimestampFormat) -> Value {
+        match format {
+            TimestampFormat::Rfc3339 => Value::String(ts.to_rfc3339()),
+            TimestampFormat::Epoch => Value::from(ts.timestamp()),
+            TimestampFormat::Local => match self.timezone() {
+                Some(tz) => Value::String(ts.with_timezone(&tz).to_rfc3339()),
+                None => Value::String(ts.to_rfc3339()),
+            },
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::super::{MOSEntry, MOS};
+
+const COLUMNS: &[&str] = &[
+    "Valid", "TMP", "DPT", "CLD", "Wind", "P06", "P12", "TYP", "CIG", "VIS", "OBV",
+];
+
+impl MOS {
+    /// Renders a heading, the worded forecast of `summarize()` as a bullet list and a
+    /// table of every period, for GitHub issues, wikis and chat tools
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "### {} MOS {}",
+                self.meta.icao,
+                self.meta.timestamp.format("%Y-%m-%d %HZ")
+            ),
+            String::new(),
+        ];
+
+        let summary = self.summarize();
+        for line in summary.lines() {
+            match line.find(": ") {
+                Some(i) => lines.push(format!("- **{}:** {}", &line[..i], &line[i + 2..])),
+                None => lines.push(format!("- {}", line)),
+            }
+        }
+        if !summary.is_empty() {
+            lines.push(String::new());
+        }
+
+        lines.push(format!("| {} |", COLUMNS.join(" | ")));
+        let align: Vec<&str> = COLUMNS
+            .iter()
+            .enumerate()
+            .map(|(i, _)| if i == 0 { "---" } else { "--:" })
+            .collect();
+        lines.push(format!("| {} |", align.join(" | ")));
+        lines.extend(self.entries.iter().map(row));
+
+        let mut out = lines.join("\n");
+        out.push('\n');
+        out
+    }
+}
+
+fn row(entry: &MOSEntry) -> String {
+    let wind = match (entry.wdr, entry.wsp) {
+        (Some(wdr), Some(wsp)) => format!("{:03}/{:02}", wdr * 10, wsp),
+        _ => String::new(),
+    };
+    let cells = [
+        entry.timestamp.format("%a %HZ").to_string(),
+        num(entry.tmp),
+        num(entry.dpt),
+        text(&entry.cld),
+        wind,
+        num(entry.p06),
+        num(entry.p12),
+        text(&entry.typ),
+        num(entry.cig),
+        num(entry.vis),
+        text(&entry.obv),
+    ];
+    format!("| {} |", cells.join(" | "))
+}
+
+fn num(val: Option<isize>) -> String {
+    val.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Pipes would end the cell early
+fn text(val: &Option<String>) -> String {
+    val.as_deref().unwrap_or("").replace('|', "\\|")
+}
@@ -0,0 +1,18 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "serde_cbor")]
+pub mod cbor;
+pub mod csv;
+pub mod geojson;
+pub mod html;
+pub mod ical;
+pub mod json;
+pub mod markdown;
+#[cfg(feature = "rmp-serde")]
+pub mod msgpack;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "quick-xml")]
+pub mod xml;
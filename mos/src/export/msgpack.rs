@@ -0,0 +1,24 @@
+use super::super::{error, MOS};
+use super::json::SerializeOptions;
+
+impl MOS {
+    /// MessagePack encoding with field names kept, so it decodes back into `MOS` and
+    /// reads like the JSON in other languages
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, error::Error> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+
+    /// `to_json_with()` encoded as MessagePack
+    pub fn to_msgpack_with(&self, opts: &SerializeOptions) -> Result<Vec<u8>, error::Error> {
+        Ok(rmp_serde::to_vec_named(&self.to_json_with(opts)?)?)
+    }
+
+    pub fn from_msgpack(bytes: &[u8]) -> Result<MOS, error::Error> {
+        Ok(rmp_serde::from_read_ref(bytes)?)
+    }
+}
+
+/// Several forecasts as one MessagePack array, for multi-station responses
+pub fn encode_all(forecasts: &[MOS]) -> Result<Vec<u8>, error::Error> {
+    Ok(rmp_serde::to_vec_named(forecasts)?)
+}
@@ -0,0 +1,210 @@
+use parquet::column::writer::ColumnWriter;
+use parquet::data_type::ByteArray;
+use parquet::file::properties::{WriterProperties, WriterPropertiesPtr};
+use parquet::file::writer::{FileWriter, ParquetWriter, RowGroupWriter, SerializedFileWriter};
+use parquet::schema::parser::parse_message_type;
+use parquet::schema::types::TypePtr;
+
+use super::super::{error, MOSEntry, MOS};
+
+const SCHEMA: &str = "
+message mos_entry {
+    REQUIRED BYTE_ARRAY icao (UTF8);
+    REQUIRED INT64 cycle (TIMESTAMP_MILLIS);
+    REQUIRED INT64 timestamp (TIMESTAMP_MILLIS);
+    OPTIONAL INT32 nx;
+    OPTIONAL INT32 tmp;
+    OPTIONAL INT32 dpt;
+    OPTIONAL BYTE_ARRAY cld (UTF8);
+    OPTIONAL INT32 wdr;
+    OPTIONAL INT32 wsp;
+    OPTIONAL INT32 p06;
+    OPTIONAL INT32 p12;
+    OPTIONAL INT32 q06;
+    OPTIONAL INT32 q12;
+    OPTIONAL INT32 t06;
+    OPTIONAL INT32 t06_severe;
+    OPTIONAL INT32 t12;
+    OPTIONAL INT32 t12_severe;
+    OPTIONAL INT32 poz;
+    OPTIONAL INT32 pos;
+    OPTIONAL BYTE_ARRAY typ (UTF8);
+    OPTIONAL INT32 snw;
+    OPTIONAL INT32 cig;
+    OPTIONAL INT32 vis;
+    OPTIONAL BYTE_ARRAY obv (UTF8);
+}
+";
+
+/// Column values in schema order
+pub(crate) enum Column {
+    Label(Vec<String>),
+    Time(Vec<i64>),
+    Int(Vec<Option<i32>>),
+    Str(Vec<Option<String>>),
+    Float(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+}
+
+/// A Parquet file of many forecasts, written a row group per forecast as they're
+/// appended, e.g. to convert an archive of bulletins without holding it in memory
+pub struct ParquetArchive<W: ParquetWriter> {
+    writer: SerializedFileWriter<W>,
+    rows: usize,
+}
+
+impl<W: ParquetWriter + 'static> ParquetArchive<W> {
+    pub fn new(writer: W) -> Result<ParquetArchive<W>, error::Error> {
+        Ok(ParquetArchive {
+            writer: file_writer(writer, SCHEMA)?,
+            rows: 0,
+        })
+    }
+
+    /// Writes the forecast's entries as a row group
+    pub fn append(&mut self, mos: &MOS) -> Result<(), error::Error> {
+        let mut row_group = self.writer.next_row_group()?;
+        write_columns(row_group.as_mut(), mos.parquet_columns())?;
+        self.writer.close_row_group(row_group)?;
+        self.rows += mos.entries.len();
+        Ok(())
+    }
+
+    /// Rows written so far
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Writes the footer. The file isn't readable until then
+    pub fn close(mut self) -> Result<(), error::Error> {
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+impl MOS {
+    /// Writes the entries as a single Parquet row group, one row per valid time, with
+    /// typed columns matching `to_csv()`
+    pub fn to_parquet<W: ParquetWriter + 'static>(&self, writer: W) -> Result<(), error::Error> {
+        let mut archive = ParquetArchive::new(writer)?;
+        archive.append(self)?;
+        archive.close()
+    }
+
+    fn parquet_columns(&self) -> Vec<Column> {
+        let int = |f: &dyn Fn(&MOSEntry) -> Option<isize>| {
+            Column::Int(
+                self.entries
+                    .iter()
+                    .map(|e| f(e).map(|v| v as i32))
+                    .collect(),
+            )
+        };
+        let string = |f: &dyn Fn(&MOSEntry) -> Option<String>| {
+            Column::Str(self.entries.iter().map(f).collect())
+        };
+        let cycle = self.meta.timestamp.timestamp_millis();
+
+        vec![
+            Column::Label(
+                self.entries
+                    .iter()
+                    .map(|_| self.meta.icao.clone())
+                    .collect(),
+            ),
+            Column::Time(self.entries.iter().map(|_| cycle).collect()),
+            Column::Time(
+                self.entries
+                    .iter()
+                    .map(|e| e.timestamp.timestamp_millis())
+                    .collect(),
+            ),
+            int(&|e| e.nx),
+            int(&|e| e.tmp),
+            int(&|e| e.dpt),
+            string(&|e| e.cld.clone()),
+            int(&|e| e.wdr),
+            int(&|e| e.wsp),
+            int(&|e| e.p06),
+            int(&|e| e.p12),
+            int(&|e| e.q06),
+            int(&|e| e.q12),
+            int(&|e| e.t06.map(|t| t.0)),
+            int(&|e| e.t06.map(|t| t.1)),
+            int(&|e| e.t12.map(|t| t.0)),
+            int(&|e| e.t12.map(|t| t.1)),
+            int(&|e| e.poz),
+            int(&|e| e.pos),
+            string(&|e| e.typ.clone()),
+            int(&|e| e.snw),
+            int(&|e| e.cig),
+            int(&|e| e.vis),
+            string(&|e| e.obv.clone()),
+        ]
+    }
+}
+
+/// A writer of files with the schema, given in Parquet's message type syntax
+pub(crate) fn file_writer<W: ParquetWriter + 'static>(
+    writer: W,
+    schema: &str,
+) -> Result<SerializedFileWriter<W>, error::Error> {
+    let schema = TypePtr::new(parse_message_type(schema)?);
+    let props: WriterPropertiesPtr = WriterPropertiesPtr::new(WriterProperties::builder().build());
+    Ok(SerializedFileWriter::new(writer, schema, props)?)
+}
+
+/// Writes the columns, which must be in the row group's schema order
+pub(crate) fn write_columns(
+    row_group: &mut dyn RowGroupWriter,
+    columns: Vec<Column>,
+) -> Result<(), error::Error> {
+    let mut columns = columns.into_iter();
+    while let Some(mut col_writer) = row_group.next_column()? {
+        match (&mut col_writer, columns.next()) {
+            (ColumnWriter::Int64ColumnWriter(w), Some(Column::Time(vals))) => {
+                w.write_batch(&vals, None, None)?;
+            }
+            (ColumnWriter::Int32ColumnWriter(w), Some(Column::Int(vals))) => {
+                let def_levels = definition_levels(&vals);
+                let present: Vec<i32> = vals.into_iter().flatten().collect();
+                w.write_batch(&present, Some(&def_levels), None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(w), Some(Column::Label(vals))) => {
+                let present: Vec<ByteArray> = vals
+                    .iter()
+                    .map(|val| ByteArray::from(val.as_str()))
+                    .collect();
+                w.write_batch(&present, None, None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(w), Some(Column::Str(vals))) => {
+                let def_levels = definition_levels(&vals);
+                let present: Vec<ByteArray> = vals
+                    .into_iter()
+                    .flatten()
+                    .map(|val| ByteArray::from(val.as_str()))
+                    .collect();
+                w.write_batch(&present, Some(&def_levels), None)?;
+            }
+            (ColumnWriter::DoubleColumnWriter(w), Some(Column::Float(vals))) => {
+                let def_levels = definition_levels(&vals);
+                let present: Vec<f64> = vals.into_iter().flatten().collect();
+                w.write_batch(&present, Some(&def_levels), None)?;
+            }
+            (ColumnWriter::BoolColumnWriter(w), Some(Column::Bool(vals))) => {
+                let def_levels = definition_levels(&vals);
+                let present: Vec<bool> = vals.into_iter().flatten().collect();
+                w.write_batch(&present, Some(&def_levels), None)?;
+            }
+            _ => return Err(error::Error::SchemaMismatch),
+        }
+        row_group.close_column(col_writer)?;
+    }
+    Ok(())
+}
+
+fn definition_levels<T>(vals: &[Option<T>]) -> Vec<i16> {
+    vals.iter()
+        .map(|v| if v.is_some() { 1 } else { 0 })
+        .collect()
+}
@@ -0,0 +1,117 @@
+use chrono::Datelike;
+use polars::prelude::{DataFrame, Date32Type, Date64Type, NamedFrom, Series};
+
+use super::super::daily::DailySummary;
+use super::super::precip::PrecipType;
+use super::super::{error, MOSEntry, MOS};
+
+/// Days from the epoch to the Common Era, for Date32 columns
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+impl MOS {
+    /// One row per valid time with the columns of `to_csv()`, the cycle and valid time
+    /// as Date64
+    pub fn to_dataframe(&self) -> Result<DataFrame, error::Error> {
+        let int = |name: &str, f: &dyn Fn(&MOSEntry) -> Option<isize>| {
+            let vals: Vec<Option<i32>> = self
+                .entries
+                .iter()
+                .map(|e| f(e).map(|v| v as i32))
+                .collect();
+            Series::new(name, vals.as_slice())
+        };
+        let string = |name: &str, f: &dyn Fn(&MOSEntry) -> Option<&str>| {
+            let vals: Vec<Option<&str>> = self.entries.iter().map(f).collect();
+            Series::new(name, vals.as_slice())
+        };
+        let cycle: Vec<i64> = self
+            .entries
+            .iter()
+            .map(|_| self.meta.timestamp.timestamp_millis())
+            .collect();
+        let timestamp: Vec<i64> = self
+            .entries
+            .iter()
+            .map(|e| e.timestamp.timestamp_millis())
+            .collect();
+        let icao: Vec<&str> = self
+            .entries
+            .iter()
+            .map(|_| self.meta.icao.as_str())
+            .collect();
+
+        let columns = vec![
+            Series::new("icao", icao.as_slice()),
+            Series::new("cycle", cycle.as_slice()).cast::<Date64Type>()?,
+            Series::new("timestamp", timestamp.as_slice()).cast::<Date64Type>()?,
+            int("nx", &|e| e.nx),
+            int("tmp", &|e| e.tmp),
+            int("dpt", &|e| e.dpt),
+            string("cld", &|e| e.cld.as_deref()),
+            int("wdr", &|e| e.wdr),
+            int("wsp", &|e| e.wsp),
+            int("p06", &|e| e.p06),
+            int("p12", &|e| e.p12),
+            int("q06", &|e| e.q06),
+            int("q12", &|e| e.q12),
+            int("t06", &|e| e.t06.map(|t| t.0)),
+            int("t06_severe", &|e| e.t06.map(|t| t.1)),
+            int("t12", &|e| e.t12.map(|t| t.0)),
+            int("t12_severe", &|e| e.t12.map(|t| t.1)),
+            int("poz", &|e| e.poz),
+            int("pos", &|e| e.pos),
+            string("typ", &|e| e.typ.as_deref()),
+            int("snw", &|e| e.snw),
+            int("cig", &|e| e.cig),
+            int("vis", &|e| e.vis),
+            string("obv", &|e| e.obv.as_deref()),
+        ];
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+impl DailySummary {
+    /// One row per day, with the date as Date32 and the QPF range split into
+    /// `qpf_min` and `qpf_max`
+    pub fn to_dataframe(days: &[DailySummary]) -> Result<DataFrame, error::Error> {
+        let int = |name: &str, f: &dyn Fn(&DailySummary) -> Option<isize>| {
+            let vals: Vec<Option<i32>> = days.iter().map(|d| f(d).map(|v| v as i32)).collect();
+            Series::new(name, vals.as_slice())
+        };
+        let date: Vec<i32> = days
+            .iter()
+            .map(|d| d.date.num_days_from_ce() - EPOCH_DAYS_FROM_CE)
+            .collect();
+        let qpf_min: Vec<Option<f64>> = days
+            .iter()
+            .map(|d| d.qpf.as_ref().map(|qpf| qpf.min))
+            .collect();
+        let qpf_max: Vec<Option<f64>> = days
+            .iter()
+            .map(|d| d.qpf.as_ref().and_then(|qpf| qpf.max))
+            .collect();
+        let sky: Vec<Option<&str>> = days.iter().map(|d| d.sky.as_deref()).collect();
+        let precip_type: Vec<Option<&str>> = days
+            .iter()
+            .map(|d| {
+                d.precip_type.map(|precip| match precip {
+                    PrecipType::Rain => "rain",
+                    PrecipType::Snow => "snow",
+                    PrecipType::FreezingRain => "freezing_rain",
+                })
+            })
+            .collect();
+
+        let columns = vec![
+            Series::new("date", date.as_slice()).cast::<Date32Type>()?,
+            int("high", &|d| d.high),
+            int("low", &|d| d.low),
+            int("max_pop", &|d| d.max_pop),
+            Series::new("qpf_min", qpf_min.as_slice()),
+            Series::new("qpf_max", qpf_max.as_slice()),
+            Series::new("sky", sky.as_slice()),
+            Series::new("precip_type", precip_type.as_slice()),
+        ];
+        Ok(DataFrame::new(columns)?)
+    }
+}
@@ -0,0 +1,93 @@
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use std::io::Cursor;
+
+use super::super::units::Units;
+use super::super::{error, MOSEntry, MOS};
+
+type XmlWriter = Writer<Cursor<Vec<u8>>>;
+
+impl MOS {
+    /// XML document with a `<mos icao="KFIT" cycle="...">` root and an `<entry
+    /// valid="...">` per valid time, holding one element per forecast value named after
+    /// its JSON field. Missing values are left out and T06/T12 pairs are written as
+    /// `<t06 thunder="13" severe="2"/>`
+    pub fn to_xml(&self) -> Result<String, error::Error> {
+        let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new(b"1.0", Some(b"UTF-8"), None)))?;
+
+        let cycle = self.meta.timestamp.to_rfc3339();
+        let mut root = BytesStart::borrowed_name(b"mos");
+        root.push_attribute(("icao", self.meta.icao.as_str()));
+        root.push_attribute(("cycle", cycle.as_str()));
+        if self.units == Units::Metric {
+            root.push_attribute(("units", "metric"));
+        }
+        writer.write_event(Event::Start(root))?;
+        for entry in &self.entries {
+            write_entry(&mut writer, entry)?;
+        }
+        writer.write_event(Event::End(BytesEnd::borrowed(b"mos")))?;
+
+        Ok(String::from_utf8_lossy(&writer.into_inner().into_inner()).into_owned())
+    }
+}
+
+fn write_entry(writer: &mut XmlWriter, entry: &MOSEntry) -> Result<(), error::Error> {
+    let valid = entry.timestamp.to_rfc3339();
+    let mut start = BytesStart::borrowed_name(b"entry");
+    start.push_attribute(("valid", valid.as_str()));
+    writer.write_event(Event::Start(start))?;
+
+    let numbers: [(&str, Option<isize>); 14] = [
+        ("nx", entry.nx),
+        ("tmp", entry.tmp),
+        ("dpt", entry.dpt),
+        ("wdr", entry.wdr),
+        ("wsp", entry.wsp),
+        ("p06", entry.p06),
+        ("p12", entry.p12),
+        ("q06", entry.q06),
+        ("q12", entry.q12),
+        ("poz", entry.poz),
+        ("pos", entry.pos),
+        ("snw", entry.snw),
+        ("cig", entry.cig),
+        ("vis", entry.vis),
+    ];
+    for (name, val) in numbers.iter() {
+        if let Some(val) = val {
+            write_text(writer, name, &val.to_string())?;
+        }
+    }
+    for (name, val) in [
+        ("cld", &entry.cld),
+        ("typ", &entry.typ),
+        ("obv", &entry.obv),
+    ]
+    .iter()
+    {
+        if let Some(val) = val {
+            write_text(writer, name, val)?;
+        }
+    }
+    for (name, val) in [("t06", entry.t06), ("t12", entry.t12)].iter() {
+        if let Some((thunder, severe)) = val {
+            let (thunder, severe) = (thunder.to_string(), severe.to_string());
+            let mut elem = BytesStart::borrowed_name(name.as_bytes());
+            elem.push_attribute(("thunder", thunder.as_str()));
+            elem.push_attribute(("severe", severe.as_str()));
+            writer.write_event(Event::Empty(elem))?;
+        }
+    }
+
+    writer.write_event(Event::End(BytesEnd::borrowed(b"entry")))?;
+    Ok(())
+}
+
+fn write_text(writer: &mut XmlWriter, name: &str, val: &str) -> Result<(), error::Error> {
+    writer.write_event(Event::Start(BytesStart::borrowed_name(name.as_bytes())))?;
+    writer.write_event(Event::Text(BytesText::from_plain_str(val)))?;
+    writer.write_event(Event::End(BytesEnd::borrowed(name.as_bytes())))?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::client::{self, Endpoint, Transport};
+use super::{error, Product, MOS};
+
+/// How long a failed endpoint stays behind the others
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// Recent results of fetching from one endpoint
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Health {
+    pub successes: u64,
+    pub failures: u64,
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    pub last_success: Option<Instant>,
+    pub last_failure: Option<Instant>,
+}
+
+impl Health {
+    fn failing(&self, now: Instant, cooldown: Duration) -> bool {
+        self.consecutive_failures > 0
+            && self
+                .last_failure
+                .map_or(false, |at| now.duration_since(at) < cooldown)
+    }
+}
+
+/// Fetches from an ordered list of endpoints, trying the next one when a fetch fails.
+/// Endpoints that failed within the cooldown are tried after the rest, so requests go
+/// to whichever source has been working until the preferred one has had time to recover
+pub struct Failover<T> {
+    transport: T,
+    endpoints: Vec<Endpoint>,
+    health: Mutex<Vec<Health>>,
+    pub cooldown: Duration,
+}
+
+impl<T: Transport> Failover<T> {
+    pub fn new(transport: T, endpoints: Vec<Endpoint>) -> Failover<T> {
+        let health = Mutex::new(vec![Health::default(); endpoints.len()]);
+        Failover {
+            transport,
+            endpoints,
+            health,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Tries each endpoint until one returns the bulletin. An endpoint that doesn't
+    /// have the station isn't counted as failing. With none left, the last failure is
+    /// returned, or `Error::StationNotFound` if none had the station
+    pub fn fetch(&self, icao: &str, product: Product) -> Result<MOS, error::Error> {
+        let mut last_err = error::Error::MissingData;
+        for i in self.order() {
+            match client::fetch_from(&self.transport, &self.endpoints[i], icao, product) {
+                Ok(mos) => {
+                    self.record(i, true);
+                    return Ok(mos);
+                }
+                Err(err @ error::Error::StationNotFound(_)) => {
+                    if let error::Error::MissingData = last_err {
+                        last_err = err;
+                    }
+                }
+                Err(err) => {
+                    self.record(i, false);
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    /// The endpoints with their health, in configured order
+    pub fn health(&self) -> Vec<(&Endpoint, Health)> {
+        let health = self.health.lock().unwrap_or_else(|err| err.into_inner());
+        self.endpoints.iter().zip(health.iter().copied()).collect()
+    }
+
+    /// Endpoint indexes to try, healthy ones first, each group in configured order
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap_or_else(|err| err.into_inner());
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by_key(|&i| health[i].failing(now, self.cooldown));
+        order
+    }
+
+    fn record(&self, i: usize, ok: bool) {
+        let now = Instant::now();
+        let mut health = self.health.lock().unwrap_or_else(|err| err.into_inner());
+        let health = &mut health[i];
+        if ok {
+            health.successes += 1;
+            health.consecutive_failures = 0;
+            health.last_success = Some(now);
+        } else {
+            health.failures += 1;
+            health.consecutive_failures += 1;
+            health.last_failure = Some(now);
+        }
+    }
+}
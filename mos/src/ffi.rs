@@ -0,0 +1,49 @@
+use serde::Serialize;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use super::MOS;
+
+#[derive(Serialize)]
+struct FfiError {
+    error: &'static str,
+    message: String,
+}
+
+/// Parses a NUL-terminated bulletin and returns its JSON, or `{"error": CODE, "message":
+/// ...}` if it doesn't parse. Null only if `text` is null. Free the result with
+/// `mos_free`
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string that stays valid for the call
+#[no_mangle]
+pub unsafe extern "C" fn mos_parse(text: *const c_char) -> *mut c_char {
+    if text.is_null() {
+        return ptr::null_mut();
+    }
+    let text = CStr::from_ptr(text).to_string_lossy();
+    let json = match MOS::new(&text) {
+        Ok(mos) => serde_json::to_string(&mos),
+        Err(err) => serde_json::to_string(&FfiError {
+            error: err.code(),
+            message: err.to_string(),
+        }),
+    };
+
+    // JSON escapes control characters, so there's no NUL for CString to reject
+    json.ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Frees a string returned by `mos_parse`. Null is ignored
+///
+/// # Safety
+/// `json` must be null or a pointer from `mos_parse` that hasn't been freed yet
+#[no_mangle]
+pub unsafe extern "C" fn mos_free(json: *mut c_char) {
+    if !json.is_null() {
+        drop(CString::from_raw(json));
+    }
+}
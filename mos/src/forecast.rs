@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::category::FlightCategory;
+use super::units::Units;
+use super::{MOSEntry, MOS};
+
+/// What a forecast source says about one valid time, for code that shouldn't care
+/// which product it came from. Elements a product doesn't forecast are None
+pub trait ForecastEntry {
+    fn valid_time(&self) -> DateTime<Utc>;
+
+    /// Units of `temp`, `dew_point` and the wind speed
+    fn units(&self) -> Units;
+
+    fn temp(&self) -> Option<isize>;
+
+    fn dew_point(&self) -> Option<isize>;
+
+    fn wind(&self) -> Option<ForecastWind>;
+
+    /// Probability of precipitation in percent, over whatever period the product uses
+    fn pop(&self) -> Option<isize>;
+
+    /// Lowest broken or overcast layer in feet AGL, None also when there's no ceiling
+    fn ceiling(&self) -> Option<u32>;
+
+    /// Visibility in statute miles
+    fn visibility(&self) -> Option<f64>;
+
+    fn flight_category(&self) -> Option<FlightCategory>;
+}
+
+/// A forecast for one location from any source
+pub trait Forecast {
+    type Entry: ForecastEntry;
+
+    /// Station or grid the forecast is for
+    fn location(&self) -> &str;
+
+    fn issued(&self) -> DateTime<Utc>;
+
+    /// Entries in valid time order
+    fn entries(&self) -> &[Self::Entry];
+
+    /// The entry with the valid time closest to `at`
+    fn nearest(&self, at: DateTime<Utc>) -> Option<&Self::Entry> {
+        self.entries()
+            .iter()
+            .min_by_key(|entry| (entry.valid_time() - at).num_seconds().abs())
+    }
+}
+
+/// Forecast wind with the direction in degrees, None when calm or variable
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ForecastWind {
+    pub direction: Option<isize>,
+    pub speed: isize,
+}
+
+// MAV, MEX and api.weather.gov forecasts all come back as a MOS, so this covers every
+// source the crate has
+impl Forecast for MOS {
+    type Entry = MOSEntry;
+
+    fn location(&self) -> &str {
+        &self.meta.icao
+    }
+
+    fn issued(&self) -> DateTime<Utc> {
+        self.meta.timestamp
+    }
+
+    fn entries(&self) -> &[MOSEntry] {
+        &self.entries
+    }
+}
+
+impl ForecastEntry for MOSEntry {
+    fn valid_time(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn units(&self) -> Units {
+        self.units
+    }
+
+    fn temp(&self) -> Option<isize> {
+        self.tmp
+    }
+
+    fn dew_point(&self) -> Option<isize> {
+        self.dpt
+    }
+
+    fn wind(&self) -> Option<ForecastWind> {
+        let speed = self.wsp?;
+        let direction = match self.wdr {
+            Some(wdr) if wdr > 0 && speed > 0 => Some(wdr * 10),
+            _ => None,
+        };
+        Some(ForecastWind { direction, speed })
+    }
+
+    fn pop(&self) -> Option<isize> {
+        self.p06.or(self.p12)
+    }
+
+    /// The bottom of the CIG category. Category 8, above 12000 ft or unlimited, is no
+    /// ceiling
+    fn ceiling(&self) -> Option<u32> {
+        match self.cig? {
+            1 => Some(0),
+            2 => Some(200),
+            3 => Some(500),
+            4 => Some(1000),
+            5 => Some(2000),
+            6 => Some(3100),
+            7 => Some(6600),
+            _ => None,
+        }
+    }
+
+    /// The bottom of the VIS category, so above 6 mi reads as 6 like a P6SM
+    fn visibility(&self) -> Option<f64> {
+        match self.vis? {
+            1 => Some(0.0),
+            2 => Some(0.5),
+            3 => Some(1.0),
+            4 => Some(2.0),
+            5 => Some(3.0),
+            6 | 7 => Some(6.0),
+            _ => None,
+        }
+    }
+
+    fn flight_category(&self) -> Option<FlightCategory> {
+        MOSEntry::flight_category(self)
+    }
+}
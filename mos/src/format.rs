@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use super::ROWS;
+
+/// Layout generation of a bulletin. Current bulletins come from the GFS-era MOS
+/// products; legacy ones are the NGM-style messages of the early-2000s archive, which
+/// label the hour line HOUR and spell out their row labels in up to five letters. LAMP
+/// bulletins label it UTC and run hourly from the cycle, with no date line. Column
+/// positions always come from the hour line, so wider labels don't shift the cells
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Current,
+    Legacy,
+    Lamp,
+}
+
+/// Legacy row labels and the current ones they're read as
+const LEGACY_ROWS: &[(&str, &str)] = &[
+    ("MX/MN", "X/N"),
+    ("MN/MX", "N/X"),
+    ("TEMP", "TMP"),
+    ("DEWPT", "DPT"),
+    ("CLDS", "CLD"),
+    ("WDIR", "WDR"),
+    ("WSPD", "WSP"),
+    ("POP06", "P06"),
+    ("POP12", "P12"),
+    ("QPF06", "Q06"),
+    ("QPF12", "Q12"),
+    ("TSV06", "T06"),
+    ("TSV12", "T12"),
+    ("POZP", "POZ"),
+    ("POSN", "POS"),
+    ("PTYPE", "TYP"),
+    ("SNOW", "SNW"),
+    ("OBVIS", "OBV"),
+];
+
+impl Format {
+    /// Tells the generation of a bulletin from its hour line label
+    pub fn detect(raw_mos: &str) -> Format {
+        let label = |label| {
+            raw_mos
+                .lines()
+                .any(|line| line.split_whitespace().next() == Some(label))
+        };
+        if label("HOUR") {
+            Format::Legacy
+        } else if label("UTC") {
+            Format::Lamp
+        } else {
+            Format::Current
+        }
+    }
+
+    /// Labels the hour line goes by
+    pub(crate) fn hour_labels(self) -> &'static [&'static str] {
+        match self {
+            Format::Current => &["HR", "FHR"],
+            Format::Legacy => &["HOUR"],
+            Format::Lamp => &["UTC"],
+        }
+    }
+
+    /// Label of the line with the date of each day. LAMP bulletins have none, their
+    /// columns being placed from the cycle time alone
+    pub(crate) fn date_label(self) -> &'static str {
+        match self {
+            Format::Current | Format::Lamp => "DT",
+            Format::Legacy => "DAY",
+        }
+    }
+
+    /// Where the first column of an hour of the day layout ends, with columns three
+    /// wide from there
+    pub(crate) fn first_column_end(self) -> usize {
+        match self {
+            Format::Current | Format::Lamp => 8,
+            Format::Legacy => 10,
+        }
+    }
+
+    /// The current label of a row, for its spelling in this format. None for rows the
+    /// parser doesn't read
+    pub(crate) fn row(self, label: &str) -> Option<&'static str> {
+        if let Some(&row) = ROWS.iter().find(|&&row| row == label) {
+            return Some(row);
+        }
+        match self {
+            Format::Current | Format::Lamp => None,
+            Format::Legacy => LEGACY_ROWS
+                .iter()
+                .find(|(legacy, _)| *legacy == label)
+                .map(|(_, row)| *row),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{Duration, Utc};
+
+use super::{cache, Product, MOS};
+
+/// Slack on top of the cycle interval and publication delay before a bulletin counts
+/// as stale, since NOAA runs are often a little late
+pub const STALE_GRACE_HOURS: i64 = 2;
+
+impl MOS {
+    /// Time since the model cycle the bulletin was issued for
+    pub fn age(&self) -> Duration {
+        Utc::now() - self.meta.timestamp
+    }
+
+    /// Whole minutes since the model cycle, as written in `to_json_with()` output
+    pub fn age_minutes(&self) -> i64 {
+        self.age().num_minutes()
+    }
+
+    /// Whether a newer cycle should have been published by now: the bulletin is older
+    /// than its product's cycle interval, the usual publication delay and
+    /// `STALE_GRACE_HOURS` together
+    pub fn is_stale(&self) -> bool {
+        let hours =
+            cache::cycle_hours(self.product()) + cache::PUBLICATION_DELAY_HOURS + STALE_GRACE_HOURS;
+        self.age() > Duration::hours(hours)
+    }
+
+    /// The product the bulletin most likely came from, going by the spacing of its
+    /// first two entries: MEX is 12-hourly, MAV 3-hourly
+    pub fn product(&self) -> Product {
+        match (self.entries.get(0), self.entries.get(1)) {
+            (Some(first), Some(second))
+                if second.timestamp - first.timestamp >= Duration::hours(12) =>
+            {
+                Product::Mex
+            }
+            _ => Product::Mav,
+        }
+    }
+}
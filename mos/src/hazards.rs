@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::window::Window;
+use super::{MOSEntry, MOS};
+
+/// Heuristic surface icing risk, see `MOSEntry::icing_risk()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IcingRisk {
+    Low,
+    Moderate,
+    High,
+}
+
+impl MOSEntry {
+    /// Heuristic icing risk from the surface guidance. It is not an icing forecast aloft.
+    ///
+    /// Only temperatures from 20°F to 36°F can carry a risk; outside that it is `Low`.
+    /// Within it, points are added for:
+    /// - PoP (P06, else P12): 2 at 50% or more, 1 at 30% or more
+    /// - POZ (conditional freezing precip): 2 at 30% or more, 1 at 10% or more
+    /// - freezing rain as the expected precip type: 2
+    /// - ceiling category 4 or lower (below 2000 ft): 1
+    ///
+    /// 4 points or more is `High`, 2 or more `Moderate`, otherwise `Low`. `None` when there
+    /// is no temperature
+    pub fn icing_risk(&self) -> Option<IcingRisk> {
+        let tmp = self.tmp?;
+        if tmp < 20 || tmp > 36 {
+            return Some(IcingRisk::Low);
+        }
+
+        let mut score = 0;
+        score += match self.p06.or(self.p12) {
+            Some(pop) if pop >= 50 => 2,
+            Some(pop) if pop >= 30 => 1,
+            _ => 0,
+        };
+        score += match self.poz {
+            Some(poz) if poz >= 30 => 2,
+            Some(poz) if poz >= 10 => 1,
+            _ => 0,
+        };
+        if self.typ.as_deref() == Some("Z") {
+            score += 2;
+        }
+        if self.cig.map_or(false, |cig| cig <= 4) {
+            score += 1;
+        }
+
+        Some(if score >= 4 {
+            IcingRisk::High
+        } else if score >= 2 {
+            IcingRisk::Moderate
+        } else {
+            IcingRisk::Low
+        })
+    }
+}
+
+/// A contiguous span of elevated thunderstorm probability
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StormWindow {
+    pub window: Window,
+    /// Highest thunderstorm probability within the window
+    pub peak: isize,
+    /// End of the period that carried the peak probability
+    pub peak_time: DateTime<Utc>,
+}
+
+impl MOS {
+    /// Merges consecutive thunderstorm periods at or above `threshold` percent into
+    /// windows. T06 values cover the 6 hours ending at their valid time; products without
+    /// T06 fall back to 12-hour T12 periods
+    pub fn thunderstorm_windows(&self, threshold: isize) -> Vec<StormWindow> {
+        let has_t06 = self.entries.iter().any(|entry| entry.t06.is_some());
+        let periods = self.entries.iter().filter_map(|entry| {
+            let (prob, hours) = if has_t06 {
+                (entry.t06?.0, 6)
+            } else {
+                (entry.t12?.0, 12)
+            };
+            Some((
+                entry.timestamp - Duration::hours(hours),
+                entry.timestamp,
+                prob,
+            ))
+        });
+
+        let mut windows: Vec<StormWindow> = Vec::new();
+        for (start, end, prob) in periods.filter(|(_, _, prob)| *prob >= threshold) {
+            match windows.last_mut() {
+                Some(last) if start <= last.window.end => {
+                    if end > last.window.end {
+                        last.window.end = end;
+                    }
+                    if prob > last.peak {
+                        last.peak = prob;
+                        last.peak_time = end;
+                    }
+                }
+                _ => windows.push(StormWindow {
+                    window: Window::new(start, end),
+                    peak: prob,
+                    peak_time: end,
+                }),
+            }
+        }
+
+        windows
+    }
+}
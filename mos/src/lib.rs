@@ -0,0 +1,735 @@
+use chrono::prelude::*;
+use chrono::{DateTime, Duration};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use format::Format;
+
+#[cfg(feature = "client")]
+mod adds;
+pub mod afd;
+pub mod airsigmet;
+#[cfg(feature = "client")]
+pub mod batch;
+pub mod cache;
+pub mod category;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod compare;
+pub mod custom;
+pub mod daily;
+pub mod diff;
+pub mod error;
+pub mod export;
+#[cfg(feature = "client")]
+pub mod failover;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod forecast;
+pub mod format;
+mod freshness;
+pub mod hazards;
+pub mod local;
+pub mod metar;
+pub mod minimums;
+pub mod notify;
+pub mod pirep;
+pub mod planning;
+pub mod precip;
+#[cfg(feature = "client")]
+pub mod products;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "python")]
+mod python;
+mod row;
+pub mod stations;
+pub mod stats;
+pub mod storage;
+pub mod stream;
+mod summary;
+pub mod taf;
+pub mod testing;
+mod thermo;
+pub mod training;
+pub mod trend;
+pub mod units;
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod weather_gov;
+pub mod wind;
+pub mod window;
+pub mod winds_aloft;
+
+#[cfg(feature = "client")]
+pub use client::{get, get_near, get_product};
+pub use stream::{parse_stream, parse_stream_with};
+
+/// Label of the hour line, HR or FHR
+static HOUR_PREFIX_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^ *([^ ]+) +.*$").unwrap());
+/// One column of the hour line, with its leading padding
+static COLUMN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"([ |]*[0-9]{2,3})").unwrap());
+/// Cycle date in the header, e.g. 4/12/2020, or 4/12/00 in legacy bulletins
+static HEADER_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9]{1,2}/[0-9]{1,2}/(?:[0-9]{2}|[0-9]{4})$").unwrap());
+/// Cycle time in the header, e.g. 1200, or 1200UTC with the spacing lost
+static HEADER_TIME_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([0-9]{4})(?:UTC|Z)?$").unwrap());
+/// Label of any row
+static ROW_LABEL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^ *([^ ]+)").unwrap());
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct MOSMeta {
+    pub icao: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Default for MOSMeta {
+    fn default() -> Self {
+        MOSMeta {
+            icao: String::from(""),
+            timestamp: Utc.ymd(1970, 1, 1).and_hms(0, 0, 0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct MOSEntry {
+    pub timestamp: DateTime<Utc>,
+    pub nx: Option<isize>,
+    pub tmp: Option<isize>,
+    pub dpt: Option<isize>,
+    pub cld: Option<String>,
+    pub wdr: Option<isize>,
+    pub wsp: Option<isize>,
+    pub p06: Option<isize>,
+    pub p12: Option<isize>,
+    pub q06: Option<isize>,
+    pub q12: Option<isize>,
+    pub t06: Option<(isize, isize)>,
+    pub t12: Option<(isize, isize)>,
+    pub poz: Option<isize>,
+    pub pos: Option<isize>,
+    pub typ: Option<String>,
+    pub snw: Option<isize>,
+    pub cig: Option<isize>,
+    pub vis: Option<isize>,
+    pub obv: Option<String>,
+    /// `wind_chill()` rounded, set by the parser when it applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_chill: Option<isize>,
+    /// `heat_index()` rounded, set by the parser when it applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heat_index: Option<isize>,
+    /// Set on copies converted with `MOS::to_metric()`
+    #[serde(default, skip_serializing_if = "units::Units::is_imperial")]
+    pub units: units::Units,
+}
+
+impl MOSEntry {
+    /// An entry with every element blank, to be filled in with struct update syntax or
+    /// the `with_*` setters
+    pub fn at(timestamp: DateTime<Utc>) -> MOSEntry {
+        MOSEntry {
+            timestamp,
+            ..MOSEntry::default()
+        }
+    }
+
+    pub fn with_tmp(mut self, tmp: isize) -> MOSEntry {
+        self.tmp = Some(tmp);
+        self
+    }
+
+    pub fn with_dpt(mut self, dpt: isize) -> MOSEntry {
+        self.dpt = Some(dpt);
+        self
+    }
+
+    /// Direction in tens of degrees and speed in knots, as in the bulletin
+    pub fn with_wind(mut self, wdr: isize, wsp: isize) -> MOSEntry {
+        self.wdr = Some(wdr);
+        self.wsp = Some(wsp);
+        self
+    }
+
+    pub fn with_p06(mut self, p06: isize) -> MOSEntry {
+        self.p06 = Some(p06);
+        self
+    }
+
+    pub fn with_p12(mut self, p12: isize) -> MOSEntry {
+        self.p12 = Some(p12);
+        self
+    }
+
+    /// CIG and VIS category codes
+    pub fn with_ceiling_visibility(mut self, cig: isize, vis: isize) -> MOSEntry {
+        self.cig = Some(cig);
+        self.vis = Some(vis);
+        self
+    }
+
+    pub fn with_cld(mut self, cld: &str) -> MOSEntry {
+        self.cld = Some(cld.to_string());
+        self
+    }
+}
+
+impl Default for MOSEntry {
+    fn default() -> Self {
+        MOSEntry {
+            timestamp: Utc.ymd(1970, 1, 1).and_hms(0, 0, 0),
+            nx: None,
+            tmp: None,
+            dpt: None,
+            cld: None,
+            wdr: None,
+            wsp: None,
+            p06: None,
+            p12: None,
+            q06: None,
+            q12: None,
+            t06: None,
+            t12: None,
+            poz: None,
+            pos: None,
+            typ: None,
+            snw: None,
+            cig: None,
+            vis: None,
+            obv: None,
+            wind_chill: None,
+            heat_index: None,
+            units: units::Units::Imperial,
+        }
+    }
+}
+
+/// Deserializing the JSON gives back an equal `MOS`, so cached copies can stand in for a
+/// fresh parse. `custom` is the exception: it isn't serialized, so it's empty on the copy
+/// and equality doesn't look at it
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MOS {
+    pub meta: MOSMeta,
+    pub entries: Vec<MOSEntry>,
+    pub raw: String,
+    /// Set on copies converted with `to_metric()`
+    #[serde(default, skip_serializing_if = "units::Units::is_imperial")]
+    pub units: units::Units,
+    /// Cells of the rows the parser doesn't read, by label, one per entry with blanks
+    /// as empty strings
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extras: HashMap<String, Vec<String>>,
+    /// Rows parsed by the handlers in `ParseOptions::rows`, read with `custom()`
+    #[serde(skip)]
+    pub custom: custom::CustomRows,
+}
+
+impl PartialEq for MOS {
+    fn eq(&self, other: &MOS) -> bool {
+        self.meta == other.meta
+            && self.entries == other.entries
+            && self.raw == other.raw
+            && self.units == other.units
+            && self.extras == other.extras
+    }
+}
+
+impl MOS {
+    pub fn new(raw_mos: &str) -> Result<MOS, error::Error> {
+        MOS::new_with(raw_mos, &ParseOptions::default())
+    }
+
+    pub fn new_with(raw_mos: &str, opts: &ParseOptions) -> Result<MOS, error::Error> {
+        Ok(MOS::new_with_diagnostics(raw_mos, opts)?.0)
+    }
+
+    /// Like `new_with`, also telling how the bulletin was read
+    pub fn new_with_diagnostics(
+        raw_mos: &str,
+        opts: &ParseOptions,
+    ) -> Result<(MOS, Diagnostics), error::Error> {
+        let (mut mos, diagnostics) = MOS::parse(raw_mos, opts)?;
+        mos.raw = raw_mos.to_string();
+        Ok((mos, diagnostics))
+    }
+
+    /// Parses a bulletin saved to disk, e.g. by `cia-backed fetch --format raw`
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<MOS, error::Error> {
+        let path = path.as_ref();
+        let raw_mos = fs::read_to_string(path).map_err(|source| error::Error::Io {
+            path: Some(path.to_path_buf()),
+            source,
+        })?;
+        MOS::from_string(raw_mos)
+    }
+
+    /// Like `new`, but keeps the given bulletin as `raw` instead of copying it
+    pub fn from_string(raw_mos: String) -> Result<MOS, error::Error> {
+        let (mut mos, _) = MOS::parse(&raw_mos, &ParseOptions::default())?;
+        mos.raw = raw_mos;
+        Ok(mos)
+    }
+
+    /// A forecast built in code instead of parsed, e.g. for tests. Entries are put in
+    /// valid time order and `raw` is left empty
+    pub fn from_entries(icao: &str, timestamp: DateTime<Utc>, mut entries: Vec<MOSEntry>) -> MOS {
+        entries.sort_by_key(|entry| entry.timestamp);
+        MOS {
+            meta: MOSMeta {
+                icao: icao.to_uppercase(),
+                timestamp,
+            },
+            entries,
+            ..MOS::default()
+        }
+    }
+
+    /// Everything but `raw`. Cells are sliced out of the bulletin and only the string
+    /// fields that are kept get allocated
+    fn parse(raw_mos: &str, opts: &ParseOptions) -> Result<(MOS, Diagnostics), error::Error> {
+        let lines: Vec<&str> = raw_mos
+            .split('\n')
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+        let mut mos = MOS::default();
+
+        // Metadata
+        let meta_line = match lines.iter().next() {
+            Some(line) => line,
+            None => return Err(error::Error::EmptyBulletin),
+        };
+        mos.meta = MOS::parse_meta(meta_line)?;
+        let format = Format::detect(raw_mos);
+
+        let (hour_line, chunks, columns) = layout(&lines, format, opts.strict)?;
+        let hour_label = hour_line.and_then(|line| line.split_whitespace().next());
+        let hours = chunks
+            .iter()
+            .map(|chunk| {
+                hour_line
+                    .and_then(|line| line.get(chunk.0..chunk.1))
+                    .unwrap_or("")
+                    .trim_matches(is_padding)
+            })
+            .collect::<Vec<&str>>();
+        let base_ts = mos.meta.timestamp;
+        let projections = match hour_label {
+            Some("FHR") => {
+                let mut projections = Vec::with_capacity(hours.len());
+                for (i, hour) in hours.iter().enumerate() {
+                    match hour.parse::<i64>() {
+                        Ok(projection) => projections.push(Duration::hours(projection)),
+                        Err(_) if opts.strict => {
+                            return Err(error::Error::cell("FHR", i, hour, hour))
+                        }
+                        Err(_) => projections.push(Duration::zero()),
+                    }
+                }
+                Some(projections)
+            }
+            Some("UTC") => {
+                // LAMP columns are the hours of the day following the cycle, which is
+                // issued half past the hour
+                let mut valid = base_ts - Duration::minutes(i64::from(base_ts.minute()));
+                let mut projections = Vec::with_capacity(hours.len());
+                for (i, hour) in hours.iter().enumerate() {
+                    let ahead = match hour.parse::<u32>() {
+                        Ok(hour) if hour < 24 => (hour + 23 - valid.hour()) % 24 + 1,
+                        _ if opts.strict => return Err(error::Error::cell("UTC", i, hour, hour)),
+                        _ => 1,
+                    };
+                    valid = valid + Duration::hours(i64::from(ahead));
+                    projections.push(valid - base_ts);
+                }
+                Some(projections)
+            }
+            _ => None,
+        };
+
+        // Fill the entries row by row, slicing each labelled line into all of its columns
+        // in one sweep. Cells are found by the hour line's columns alone, so rows a station
+        // doesn't have stay None without shifting the others, and lines cut short just
+        // leave their last cells blank
+        let mut entries = vec![MOSEntry::default(); chunks.len()];
+        for line in lines.iter().skip(1) {
+            let label = match ROW_LABEL_RE.find(line) {
+                Some(label) => label,
+                None => continue,
+            };
+            let found = line[label.start()..label.end()].trim();
+            let handled = match opts.rows.get(found) {
+                Some(handler) => {
+                    let values = (0..chunks.len())
+                        .map(|i| match cell(line, label.end(), &chunks, i) {
+                            "" => None,
+                            data => handler(data),
+                        })
+                        .collect();
+                    mos.custom.insert(found, values);
+                    true
+                }
+                None => false,
+            };
+            let label_str = match format.row(found) {
+                Some(label_str) => label_str,
+                None => {
+                    // Rows nothing reads are kept as text rather than dropped
+                    if !handled && Some(*line) != hour_line && !LAYOUT_LABELS.contains(&found) {
+                        let cells = (0..chunks.len())
+                            .map(|i| cell(line, label.end(), &chunks, i).to_string())
+                            .collect();
+                        mos.extras.insert(found.to_string(), cells);
+                    }
+                    continue;
+                }
+            };
+
+            for (i, entry) in entries.iter_mut().enumerate() {
+                let data = cell(line, label.end(), &chunks, i);
+                if !fill(entry, label_str, data, line, &chunks, i) && opts.strict {
+                    return Err(error::Error::cell(label_str, i, hours[i], data));
+                }
+            }
+        }
+        mos.entries = entries;
+
+        // Add timestamps for all entries
+        let num_entries = mos.entries.len();
+        for (i, entry) in mos.entries.iter_mut().enumerate() {
+            let projection = match &projections {
+                Some(projections) => projections[i],
+                None => {
+                    let mut add_hours: i64 = i as i64 * 3i64 + 6;
+                    if num_entries > 2 && i >= num_entries - 2 {
+                        let mult = (3 - (num_entries - i)) as i64;
+                        add_hours += 3 * mult;
+                    }
+                    Duration::hours(add_hours)
+                }
+            };
+            entry.timestamp = base_ts + projection;
+            entry.wind_chill = entry.wind_chill().map(|wc| wc.round() as isize);
+            entry.heat_index = entry.heat_index().map(|hi| hi.round() as isize);
+        }
+
+        Ok((mos, Diagnostics { format, columns }))
+    }
+
+    /// The header names the model in a few ways ("GFS MOS GUIDANCE", "GFSX MOS
+    /// GUIDANCE", "NAM MOS GUIDANCE", ...), so only the station is read by position and
+    /// the cycle's date and time are found by their shape
+    fn parse_meta(meta_line: &str) -> Result<MOSMeta, error::Error> {
+        let mut all_meta = meta_line.split_whitespace();
+        let icao = match all_meta.next() {
+            Some(icao) => icao,
+            None => {
+                return Err(error::Error::parse(
+                    "header",
+                    "no icao in the first line of the mos",
+                ))
+            }
+        };
+        let mut rest = all_meta.skip_while(|token| !HEADER_DATE_RE.is_match(token));
+        let date = match rest.next() {
+            Some(date) => date,
+            None => {
+                return Err(error::Error::parse(
+                    "header",
+                    "no date in the first line of the mos",
+                ))
+            }
+        };
+        let time = match rest.find_map(|token| HEADER_TIME_RE.captures(token)) {
+            Some(time) => time.get(1).map_or("", |time| time.as_str()),
+            None => {
+                return Err(error::Error::parse(
+                    "header",
+                    "no time in the first line of the mos",
+                ))
+            }
+        };
+        let date_format = if date.len() - date.rfind('/').unwrap_or(0) == 3 {
+            "%m/%d/%y %H%M"
+        } else {
+            "%m/%d/%Y %H%M"
+        };
+        Ok(MOSMeta {
+            icao: icao.to_string(),
+            timestamp: Utc.datetime_from_str(&format!("{} {}", date, time), date_format)?,
+        })
+    }
+}
+
+/// Options for `MOS::new_with`
+#[derive(Clone, Debug, Default)]
+pub struct ParseOptions {
+    /// Fail on cells that don't hold a value of their row instead of leaving them empty
+    pub strict: bool,
+    /// Parsers for rows by label, which can be registered with `with_row()`
+    pub rows: custom::RowHandlers,
+}
+
+/// Where the parser took the columns of the data from
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnSource {
+    HourLine,
+    /// Three wide, lined up with the last day boundary of the DT line
+    DateLine,
+    /// Three wide, where bulletins of the format put them
+    FixedWidths,
+}
+
+/// How `MOS::new_with_diagnostics` read a bulletin
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Diagnostics {
+    pub format: Format,
+    pub columns: ColumnSource,
+}
+
+/// MOS products served by the NWS CGI
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Product {
+    /// GFS MOS, 3-hourly out to 72 hours
+    Mav,
+    /// GFS extended MOS, 12-hourly out to 192 hours
+    Mex,
+}
+
+impl Default for Product {
+    fn default() -> Self {
+        Product::Mav
+    }
+}
+
+impl FromStr for Product {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mav" | "gfs" => Ok(Product::Mav),
+            "mex" | "gfsx" => Ok(Product::Mex),
+            _ => Err(error::Error::unknown("mos product", s)),
+        }
+    }
+}
+
+/// Labels of the lines that lay the bulletin out rather than hold a row: the date line,
+/// and the day of the week line of MEX bulletins
+const LAYOUT_LABELS: &[&str] = &["DT", "DAY", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+/// Row labels the parser reads, N/X being X/N in bulletins issued at 12Z
+const ROWS: &[&str] = &[
+    "N/X", "X/N", "TMP", "DPT", "CLD", "WDR", "WSP", "P06", "P12", "Q06", "Q12", "T06", "T12",
+    "POZ", "POS", "TYP", "SNW", "CIG", "VIS", "OBV",
+];
+
+/// The hour line, if any, and the columns of the cells with where they were found
+fn layout<'a>(
+    lines: &[&'a str],
+    format: Format,
+    strict: bool,
+) -> Result<(Option<&'a str>, Vec<(usize, usize)>, ColumnSource), error::Error> {
+    // Get the start and end indices of the data in the text. MAV bulletins label the
+    // hour line HR with hours of the day, MEX bulletins label it FHR with projections,
+    // legacy bulletins HOUR and LAMP bulletins UTC
+    let hour_line = lines.iter().copied().find(|line| {
+        let prefix_captures = match HOUR_PREFIX_RE.captures_iter(line).next() {
+            Some(prefix) => prefix,
+            None => return false,
+        };
+        let prefix = prefix_captures.get(1).map_or("", |prefix| prefix.as_str());
+
+        format.hour_labels().contains(&prefix)
+    });
+    let hour_chunks = hour_line.map(|line| {
+        COLUMN_RE
+            .find_iter(line)
+            .map(|time| (time.start(), time.end()))
+            .collect::<Vec<(usize, usize)>>()
+    });
+
+    // Columns of an hour line with a damaged hour don't tile it. Outside strict mode
+    // bulletins laid out by hour of the day get columns from elsewhere instead
+    let hour_label = hour_line.and_then(|line| line.split_whitespace().next());
+    let (chunks, columns) = match hour_chunks {
+        Some(chunks) if tiles(&chunks) || hour_label == Some("FHR") => {
+            (chunks, ColumnSource::HourLine)
+        }
+        Some(_) if strict => {
+            return Err(error::Error::parse(
+                "HR",
+                "the columns of the hour line don't line up",
+            ))
+        }
+        _ if strict => {
+            return Err(error::Error::parse(
+                "HR",
+                "no HR or FHR line in the bulletin",
+            ))
+        }
+        hour_chunks => match fallback_columns(lines, format, hour_chunks.is_some()) {
+            Some(columns) => columns,
+            None => {
+                return Err(error::Error::parse(
+                    "HR",
+                    "no HR or FHR line in the bulletin",
+                ))
+            }
+        },
+    };
+
+    Ok((hour_line, chunks, columns))
+}
+
+/// Text of column `i` of a row whose label ends at `label_end`, without padding. The
+/// first column starts right after the label
+fn cell<'a>(line: &'a str, label_end: usize, chunks: &[(usize, usize)], i: usize) -> &'a str {
+    let start = if i == 0 { label_end } else { chunks[i].0 };
+    line.get(start..chunks[i].1.min(line.len()))
+        .unwrap_or("")
+        .trim_matches(is_padding)
+}
+
+/// Whether each column starts where the one before it ends
+fn tiles(chunks: &[(usize, usize)]) -> bool {
+    !chunks.is_empty() && chunks.windows(2).all(|pair| pair[0].1 == pair[1].0)
+}
+
+/// Columns for a bulletin laid out by hour of the day whose hour line is missing or
+/// damaged, as far right as its rows go. None if there's neither an hour line nor a
+/// date line, i.e. it isn't such a bulletin
+fn fallback_columns(
+    lines: &[&str],
+    format: Format,
+    has_hour_line: bool,
+) -> Option<(Vec<(usize, usize)>, ColumnSource)> {
+    let date_line = lines
+        .iter()
+        .find(|line| line.split_whitespace().next() == Some(format.date_label()));
+    if date_line.is_none() && !has_hour_line {
+        return None;
+    }
+
+    // Day boundaries on the date line sit two left of the first hour of the day
+    let mut first_end = format.first_column_end();
+    let mut source = ColumnSource::FixedWidths;
+    if let Some(slash) = date_line.and_then(|line| line.rfind('/')) {
+        if slash + 4 >= first_end {
+            first_end += (slash + 4 - first_end) % 3;
+            source = ColumnSource::DateLine;
+        }
+    }
+
+    let width = lines
+        .iter()
+        .filter(|line| {
+            line.split_whitespace()
+                .next()
+                .map_or(false, |label| format.row(label).is_some())
+        })
+        .map(|line| line.len())
+        .max()?;
+    if width < first_end {
+        return None;
+    }
+    let chunks = (0..=(width - first_end) / 3)
+        .map(|i| {
+            let end = first_end + 3 * i;
+            let start = if i == 0 {
+                end.saturating_sub(5)
+            } else {
+                end - 3
+            };
+            (start, end)
+        })
+        .collect();
+    Some((chunks, source))
+}
+
+/// Sets the field for one row label from the cell of column `i`. False if the cell holds
+/// something that isn't a value of the row
+fn fill(
+    entry: &mut MOSEntry,
+    label: &str,
+    data: &str,
+    line: &str,
+    chunks: &[(usize, usize)],
+    i: usize,
+) -> bool {
+    match label {
+        "N/X" | "X/N" => set(&mut entry.nx, data),
+        "TMP" => set(&mut entry.tmp, data),
+        "DPT" => set(&mut entry.dpt, data),
+        "CLD" => set_text(&mut entry.cld, data),
+        "WDR" => set(&mut entry.wdr, data),
+        "WSP" => set(&mut entry.wsp, data),
+        "P06" => set(&mut entry.p06, data),
+        "P12" => set(&mut entry.p12, data),
+        "Q06" => set(&mut entry.q06, data),
+        "Q12" => set(&mut entry.q12, data),
+        "T06" => {
+            entry.t06 = parse_pair(line, chunks, i);
+            entry.t06.is_some() || !opens_pair(line, chunks, i)
+        }
+        "T12" => {
+            entry.t12 = parse_pair(line, chunks, i);
+            entry.t12.is_some() || !opens_pair(line, chunks, i)
+        }
+        "POZ" => set(&mut entry.poz, data),
+        "POS" => set(&mut entry.pos, data),
+        "TYP" => set_text(&mut entry.typ, data),
+        "SNW" => set(&mut entry.snw, data),
+        "CIG" => set(&mut entry.cig, data),
+        "VIS" => set(&mut entry.vis, data),
+        "OBV" => set_text(&mut entry.obv, data),
+        _ => true,
+    }
+}
+
+/// Parses a numeric cell, where blank means no value
+fn set(field: &mut Option<isize>, data: &str) -> bool {
+    *field = data.parse().ok();
+    field.is_some() || data.is_empty()
+}
+
+/// Keeps a text cell, where blank means no value like a row the station doesn't have
+fn set_text(field: &mut Option<String>, data: &str) -> bool {
+    *field = if data.is_empty() {
+        None
+    } else {
+        Some(data.to_string())
+    };
+    true
+}
+
+/// Thunderstorm rows hold "tt/ss" pairs right-aligned so that the slash opens the column
+/// the pair is valid for and the first number sits in the column before it
+fn parse_pair(line: &str, chunks: &[(usize, usize)], i: usize) -> Option<(isize, isize)> {
+    if i == 0 {
+        return None;
+    }
+    let (prev, cur) = (chunks[i - 1], chunks[i]);
+    let right = line.get(cur.0..cur.1)?.trim_start_matches('|');
+    let right = right.strip_prefix('/')?;
+    let left = line.get(prev.0..prev.1)?.trim_matches(is_padding);
+
+    Some((left.parse().ok()?, right.trim().parse().ok()?))
+}
+
+fn opens_pair(line: &str, chunks: &[(usize, usize)], i: usize) -> bool {
+    line.get(chunks[i].0..chunks[i].1)
+        .map_or(false, |cell| cell.trim_start_matches('|').starts_with('/'))
+}
+
+fn is_padding(c: char) -> bool {
+    c == ' ' || c == '|'
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use serde::ser::{self, Serialize, Serializer};
+
+use super::{stations, MOSEntry, MOS};
+
+/// A forecast entry with its valid time in the station's local zone
+#[derive(Debug)]
+pub struct LocalEntry<'a> {
+    pub timestamp: DateTime<Tz>,
+    pub entry: &'a MOSEntry,
+}
+
+/// Serializes like `MOSEntry`, with the timestamp carrying the local offset
+impl<'a> Serialize for LocalEntry<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut val = serde_json::to_value(self.entry).map_err(ser::Error::custom)?;
+        if let Some(obj) = val.as_object_mut() {
+            obj.insert(
+                String::from("timestamp"),
+                serde_json::Value::String(self.timestamp.to_rfc3339()),
+            );
+        }
+        val.serialize(serializer)
+    }
+}
+
+/// The entries valid on one calendar day at the station
+#[derive(Debug, Serialize)]
+pub struct Day<'a> {
+    pub date: NaiveDate,
+    pub entries: Vec<LocalEntry<'a>>,
+}
+
+impl MOS {
+    /// The station's time zone, if it is in the embedded station table
+    pub fn timezone(&self) -> Option<Tz> {
+        stations::lookup(&self.meta.icao).and_then(|station| station.timezone())
+    }
+
+    /// Entries with their valid times converted to the station's local zone. None if the
+    /// station isn't in the embedded table
+    pub fn in_local_time(&self) -> Option<Vec<LocalEntry>> {
+        let tz = self.timezone()?;
+        Some(
+            self.entries
+                .iter()
+                .map(|entry| LocalEntry {
+                    timestamp: entry.timestamp.with_timezone(&tz),
+                    entry,
+                })
+                .collect(),
+        )
+    }
+
+    /// Entries grouped by the local calendar day of their valid time, in order. Stations
+    /// that aren't in the embedded table are grouped by UTC day
+    pub fn by_day(&self) -> Vec<Day> {
+        let tz = self.timezone().unwrap_or(Tz::UTC);
+        let mut days: Vec<Day> = Vec::new();
+        for entry in &self.entries {
+            let timestamp = entry.timestamp.with_timezone(&tz);
+            let date = timestamp.date().naive_local();
+            let local = LocalEntry { timestamp, entry };
+            match days.last_mut() {
+                Some(day) if day.date == date => day.entries.push(local),
+                _ => days.push(Day {
+                    date,
+                    entries: vec![local],
+                }),
+            }
+        }
+        days
+    }
+}
@@ -0,0 +1,298 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::iter::Peekable;
+
+#[cfg(feature = "client")]
+use super::adds;
+use super::category::FlightCategory;
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+use super::error;
+
+static TIME_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{2})(\d{2})(\d{2})Z$").unwrap());
+static WIND_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{3}|VRB)(\d{2,3})(?:G(\d{2,3}))?(KT|MPS)$").unwrap());
+static VARIABLE_WIND_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{3}V\d{3}$").unwrap());
+static VIS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([MP])?(?:(\d+)|(\d+)/(\d+))SM$").unwrap());
+static WHOLE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d$").unwrap());
+static METERS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{4})(?:NDV)?$").unwrap());
+static RVR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^R\d{2}[LRC]?/").unwrap());
+static WEATHER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:[-+]|VC)?(?:MI|PR|BC|DR|BL|SH|TS|FZ)?(?:DZ|RA|SN|SG|IC|PL|GR|GS|UP|BR|FG|FU|VA|DU|SA|HZ|PY|PO|SQ|FC|SS|DS)*$",
+    )
+    .unwrap()
+});
+static SKY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(SKC|CLR|NSC|NCD|FEW|SCT|BKN|OVC|VV)(\d{3}|///)?(CB|TCU)?$").unwrap()
+});
+static TEMP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(M?\d{2})/(M?\d{2})?$").unwrap());
+static ALTIMETER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([AQ])(\d{4})$").unwrap());
+
+/// A METAR or SPECI observation
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Metar {
+    pub icao: String,
+    pub time: DateTime<Utc>,
+    pub wind: Option<Wind>,
+    /// Prevailing visibility in statute miles. "P6SM" reads as 6 and "M1/4SM" as 1/4
+    pub visibility: Option<f64>,
+    /// Present weather groups as reported, e.g. "-RA" or "BR"
+    pub weather: Vec<String>,
+    pub sky: Vec<SkyLayer>,
+    /// °C
+    pub temperature: Option<isize>,
+    /// °C
+    pub dew_point: Option<isize>,
+    /// Inches of mercury
+    pub altimeter: Option<f64>,
+    pub raw: String,
+}
+
+/// Observed wind in knots
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Wind {
+    /// Degrees true, None when variable
+    pub direction: Option<isize>,
+    pub speed: isize,
+    pub gust: Option<isize>,
+}
+
+/// One sky condition group
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SkyLayer {
+    /// FEW, SCT, BKN, OVC, VV (vertical visibility) or a clear-sky code
+    pub cover: String,
+    /// Feet AGL
+    pub base: Option<u32>,
+    /// CB or TCU
+    pub convective: Option<String>,
+}
+
+impl Metar {
+    /// Parses a raw report, taking the month and year of its day-of-month timestamp from
+    /// the current time
+    pub fn parse(raw: &str) -> Result<Metar, error::Error> {
+        Metar::parse_at(raw, Utc::now())
+    }
+
+    /// Parses a raw report issued at or shortly before `now`
+    pub fn parse_at(raw: &str, now: DateTime<Utc>) -> Result<Metar, error::Error> {
+        let raw = raw.trim();
+        let body = raw.split(" RMK").next().unwrap_or(raw);
+        let mut tokens = body
+            .split_whitespace()
+            .skip_while(|token| *token == "METAR" || *token == "SPECI")
+            .peekable();
+
+        let icao = match tokens.next() {
+            Some(icao) => icao.to_string(),
+            None => return Err(error::Error::parse("METAR", "report is empty")),
+        };
+        let time = match tokens.next().and_then(|token| observation_time(token, now)) {
+            Some(time) => time,
+            None => return Err(error::Error::parse("METAR", "no observation time")),
+        };
+
+        let mut metar = Metar {
+            icao,
+            time,
+            wind: None,
+            visibility: None,
+            weather: Vec::new(),
+            sky: Vec::new(),
+            temperature: None,
+            dew_point: None,
+            altimeter: None,
+            raw: raw.to_string(),
+        };
+        while let Some(token) = tokens.next() {
+            if token == "AUTO" || token == "COR" || VARIABLE_WIND_RE.is_match(token) {
+                continue;
+            }
+            if RVR_RE.is_match(token) {
+                continue;
+            }
+            if token == "CAVOK" {
+                metar.visibility = Some(6.0);
+                continue;
+            }
+            if let Some(wind) = wind(token) {
+                metar.wind = Some(wind);
+                continue;
+            }
+            if let Some(vis) = visibility_group(token, &mut tokens) {
+                metar.visibility = Some(vis);
+                continue;
+            }
+            if metar.visibility.is_none() && metar.sky.is_empty() {
+                if let Some(caps) = METERS_RE.captures(token) {
+                    let meters = caps[1].parse::<f64>().unwrap_or(0.0);
+                    metar.visibility = Some((meters / 1609.344).min(6.0));
+                    continue;
+                }
+            }
+            if let Some(layer) = sky_layer(token) {
+                metar.sky.push(layer);
+                continue;
+            }
+            if let Some(caps) = TEMP_RE.captures(token) {
+                metar.temperature = celsius(&caps[1]);
+                metar.dew_point = caps.get(2).and_then(|dpt| celsius(dpt.as_str()));
+                continue;
+            }
+            if let Some(caps) = ALTIMETER_RE.captures(token) {
+                let val = caps[2].parse::<f64>().unwrap_or(0.0);
+                metar.altimeter = Some(if &caps[1] == "A" {
+                    val / 100.0
+                } else {
+                    val * 0.029_53
+                });
+                continue;
+            }
+            if is_weather(token) {
+                metar.weather.push(token.to_string());
+            }
+        }
+
+        Ok(metar)
+    }
+
+    /// Height of the lowest broken or overcast layer or vertical visibility, in feet AGL
+    pub fn ceiling(&self) -> Option<u32> {
+        ceiling(&self.sky)
+    }
+
+    pub fn flight_category(&self) -> FlightCategory {
+        FlightCategory::from_conditions(self.ceiling(), self.visibility)
+    }
+
+    /// Temperature in °F, to compare with MOS
+    pub fn temperature_f(&self) -> Option<f64> {
+        self.temperature.map(|t| t as f64 * 9.0 / 5.0 + 32.0)
+    }
+
+    /// Dew point in °F, to compare with MOS
+    pub fn dew_point_f(&self) -> Option<f64> {
+        self.dew_point.map(|td| td as f64 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+/// Resolves DDHHMMZ against the current time, stepping back a month when the day is
+/// ahead of today
+pub(crate) fn observation_time(token: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let caps = TIME_RE.captures(token)?;
+    let (day, hour, minute) = (
+        caps[1].parse::<u32>().ok()?,
+        caps[2].parse::<u32>().ok()?,
+        caps[3].parse::<u32>().ok()?,
+    );
+
+    let (mut year, mut month) = (now.year(), now.month());
+    if day > now.day() + 1 {
+        if month == 1 {
+            year -= 1;
+            month = 12;
+        } else {
+            month -= 1;
+        }
+    }
+    let date = Utc.ymd_opt(year, month, day).single()?;
+    let time = date.and_hms_opt(hour, minute, 0)?;
+
+    // A report stamped just after midnight of the next day, from a clock a little ahead
+    if time > now + Duration::days(1) {
+        return None;
+    }
+    Some(time)
+}
+
+pub(crate) fn wind(token: &str) -> Option<Wind> {
+    let caps = WIND_RE.captures(token)?;
+    let scale = if &caps[4] == "MPS" { 1.943_84 } else { 1.0 };
+    let knots = |val: &str| (val.parse::<f64>().unwrap_or(0.0) * scale).round() as isize;
+    Some(Wind {
+        direction: caps[1].parse().ok(),
+        speed: knots(&caps[2]),
+        gust: caps.get(3).map(|gust| knots(gust.as_str())),
+    })
+}
+
+/// Statute mile visibility, taking the following token too when it's the fraction of
+/// a "1 1/2SM" group
+pub(crate) fn visibility_group<'a, I>(token: &str, tokens: &mut Peekable<I>) -> Option<f64>
+where
+    I: Iterator<Item = &'a str>,
+{
+    if WHOLE_RE.is_match(token) {
+        let frac = tokens.peek().and_then(|next| visibility(next))?;
+        tokens.next();
+        return Some(token.parse::<f64>().unwrap_or(0.0) + frac);
+    }
+    visibility(token)
+}
+
+fn visibility(token: &str) -> Option<f64> {
+    let caps = VIS_RE.captures(token)?;
+    let miles = match (caps.get(2), caps.get(3), caps.get(4)) {
+        (Some(whole), _, _) => whole.as_str().parse::<f64>().ok()?,
+        (None, Some(num), Some(den)) => {
+            num.as_str().parse::<f64>().ok()? / den.as_str().parse::<f64>().ok()?
+        }
+        _ => return None,
+    };
+    Some(miles)
+}
+
+pub(crate) fn sky_layer(token: &str) -> Option<SkyLayer> {
+    let caps = SKY_RE.captures(token)?;
+    Some(SkyLayer {
+        cover: caps[1].to_string(),
+        base: caps
+            .get(2)
+            .and_then(|base| base.as_str().parse::<u32>().ok())
+            .map(|hundreds| hundreds * 100),
+        convective: caps.get(3).map(|kind| kind.as_str().to_string()),
+    })
+}
+
+pub(crate) fn is_weather(token: &str) -> bool {
+    WEATHER_RE.is_match(token)
+}
+
+pub(crate) fn ceiling(sky: &[SkyLayer]) -> Option<u32> {
+    sky.iter()
+        .filter(|layer| layer.cover == "BKN" || layer.cover == "OVC" || layer.cover == "VV")
+        .filter_map(|layer| layer.base)
+        .min()
+}
+
+fn celsius(val: &str) -> Option<isize> {
+    match val.strip_prefix('M') {
+        Some(neg) => neg.parse::<isize>().ok().map(|c| -c),
+        None => val.parse().ok(),
+    }
+}
+
+/// Latest METAR for the station from aviationweather.gov
+#[cfg(feature = "client")]
+pub fn get(icao: &str) -> Result<Metar, error::Error> {
+    fetch(client::default_transport(), icao)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(transport: &T, icao: &str) -> Result<Metar, error::Error> {
+    let icao = icao.to_uppercase();
+    let xml = adds::query(
+        transport,
+        "metars",
+        &format!("hoursBeforeNow=3&mostRecent=true&stationString={}", icao),
+    )?;
+
+    match adds::text(&xml, "raw_text") {
+        Some(raw) => Metar::parse(&raw),
+        None => Err(error::Error::StationNotFound(icao)),
+    }
+}
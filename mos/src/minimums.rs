@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::category::FlightCategory;
+use super::hazards::IcingRisk;
+use super::{MOSEntry, MOS};
+
+/// Personal minimums. Every limit is optional and unset limits always pass.
+///
+/// MOS has no gust forecast, so there is no gust limit; `max_wind` applies to the
+/// sustained WSP forecast
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Minimums {
+    /// Maximum sustained wind in knots
+    pub max_wind: Option<isize>,
+    /// Maximum crosswind in knots on the best of `runways`
+    pub max_crosswind: Option<f64>,
+    /// True runway headings in degrees, needed for `max_crosswind`
+    pub runways: Vec<f64>,
+    /// Lowest acceptable CIG category
+    pub min_ceiling: Option<isize>,
+    /// Lowest acceptable VIS category
+    pub min_visibility: Option<isize>,
+    /// Worst acceptable flight category
+    pub min_category: Option<FlightCategory>,
+    /// Reject any icing risk above `Low`
+    pub no_icing: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    Wind,
+    Crosswind,
+    Ceiling,
+    Visibility,
+    Category,
+    Icing,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Violation {
+    pub rule: Rule,
+    pub detail: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Verdict {
+    pub timestamp: DateTime<Utc>,
+    pub go: bool,
+    /// The first rule that failed, in the order of the `Minimums` fields
+    pub violation: Option<Violation>,
+}
+
+impl Minimums {
+    /// Checks one entry and returns the first violated rule
+    pub fn check(&self, entry: &MOSEntry) -> Option<Violation> {
+        let fail = |rule, detail: String| Some(Violation { rule, detail });
+
+        if let (Some(max), Some(wsp)) = (self.max_wind, entry.wsp) {
+            if wsp > max {
+                return fail(Rule::Wind, format!("wind {} kt over {} kt", wsp, max));
+            }
+        }
+
+        if let Some(max) = self.max_crosswind {
+            let best = self
+                .runways
+                .iter()
+                .filter_map(|heading| entry.wind_components(*heading))
+                .map(|components| components.crosswind.abs())
+                .fold(None, |best: Option<f64>, xw| match best {
+                    Some(best) if best <= xw => Some(best),
+                    _ => Some(xw),
+                });
+            if let Some(xw) = best {
+                if xw > max {
+                    return fail(
+                        Rule::Crosswind,
+                        format!("crosswind {:.0} kt over {:.0} kt", xw, max),
+                    );
+                }
+            }
+        }
+
+        if let (Some(min), Some(cig)) = (self.min_ceiling, entry.cig) {
+            if cig < min {
+                return fail(
+                    Rule::Ceiling,
+                    format!("ceiling category {} below {}", cig, min),
+                );
+            }
+        }
+
+        if let (Some(min), Some(vis)) = (self.min_visibility, entry.vis) {
+            if vis < min {
+                return fail(
+                    Rule::Visibility,
+                    format!("visibility category {} below {}", vis, min),
+                );
+            }
+        }
+
+        if let (Some(min), Some(category)) = (self.min_category, entry.flight_category()) {
+            if category < min {
+                return fail(Rule::Category, format!("{} worse than {}", category, min));
+            }
+        }
+
+        if self.no_icing {
+            if let Some(risk) = entry.icing_risk() {
+                if risk > IcingRisk::Low {
+                    return fail(Rule::Icing, format!("{:?} icing risk", risk).to_lowercase());
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Go/no-go verdict for every valid time in the forecast
+    pub fn evaluate(&self, mos: &MOS) -> Vec<Verdict> {
+        mos.entries
+            .iter()
+            .map(|entry| {
+                let violation = self.check(entry);
+                Verdict {
+                    timestamp: entry.timestamp,
+                    go: violation.is_none(),
+                    violation,
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let time = self.timestamp.format("%a %d %HZ");
+        match &self.violation {
+            Some(violation) => write!(f, "{}: NO-GO, {}", time, violation.detail),
+            None => write!(f, "{}: GO", time),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::Duration;
+use serde_json::{json, Value};
+
+use super::super::category::FlightCategory;
+#[cfg(feature = "client")]
+use super::super::{client, error};
+use super::super::{MOSEntry, MOS};
+
+/// Hours ahead of the first valid time the embed covers
+const WINDOW_HOURS: i64 = 24;
+
+/// Embed color of a flight category, in the usual aviation weather map colors
+pub fn color(category: FlightCategory) -> u32 {
+    match category {
+        FlightCategory::Lifr => 0xff00ff,
+        FlightCategory::Ifr => 0xff0000,
+        FlightCategory::Mvfr => 0x0066ff,
+        FlightCategory::Vfr => 0x00cc00,
+    }
+}
+
+/// An embed with the temperatures, strongest wind, highest PoP and worst flight category
+/// of the next 24 hours, colored by that category
+pub fn embed(mos: &MOS) -> Value {
+    let window: Vec<&MOSEntry> = match mos.entries.first() {
+        Some(first) => {
+            let end = first.timestamp + Duration::hours(WINDOW_HOURS);
+            mos.entries
+                .iter()
+                .filter(|entry| entry.timestamp < end)
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    let worst = window
+        .iter()
+        .filter_map(|entry| entry.flight_category())
+        .min();
+
+    let mut embed = json!({
+        "title": format!("{} MOS guidance", mos.meta.icao),
+        "description": format!(
+            "Next {}h from the {} cycle",
+            WINDOW_HOURS,
+            mos.meta.timestamp.format("%b %-d %HZ")
+        ),
+        "timestamp": mos.meta.timestamp.to_rfc3339(),
+        "fields": [
+            { "name": "Temperature", "value": temps(&window), "inline": true },
+            { "name": "Wind", "value": wind(&window), "inline": true },
+            { "name": "PoP", "value": pop(&window), "inline": true },
+            {
+                "name": "Flight category",
+                "value": worst.map_or_else(|| String::from("n/a"), |worst| worst.to_string()),
+                "inline": true,
+            },
+        ],
+    });
+    if let Some(worst) = worst {
+        embed["color"] = Value::from(color(worst));
+    }
+    embed
+}
+
+/// The webhook body carrying the embed
+pub fn message(mos: &MOS) -> Value {
+    json!({ "embeds": [embed(mos)] })
+}
+
+/// Posts the embed to a Discord webhook
+#[cfg(feature = "client")]
+pub fn post(webhook_url: &str, mos: &MOS) -> Result<(), error::Error> {
+    let res = client::http_client()
+        .post(webhook_url)
+        .json(&message(mos))
+        .send()?;
+
+    if !res.status().is_success() {
+        return Err(error::Error::Status {
+            service: "discord webhook",
+            status: res.status().as_u16(),
+        });
+    }
+
+    Ok(())
+}
+
+fn temps(window: &[&MOSEntry]) -> String {
+    let temps: Vec<isize> = window
+        .iter()
+        .flat_map(|entry| entry.tmp.into_iter().chain(entry.nx))
+        .collect();
+    let symbol = window
+        .first()
+        .map_or("°F", |entry| entry.units.temperature_symbol());
+    match (temps.iter().min(), temps.iter().max()) {
+        (Some(low), Some(high)) => format!("{}–{}{}", low, high, symbol),
+        _ => String::from("n/a"),
+    }
+}
+
+fn wind(window: &[&MOSEntry]) -> String {
+    let strongest = window
+        .iter()
+        .filter(|entry| entry.wsp.is_some())
+        .max_by_key(|entry| entry.wsp);
+    match strongest {
+        Some(entry) => {
+            let symbol = entry.units.speed_symbol();
+            match (entry.wdr, entry.wsp) {
+                (Some(wdr), Some(wsp)) => format!("{:03}° {}{}", wdr * 10, wsp, symbol),
+                (None, Some(wsp)) => format!("{}{}", wsp, symbol),
+                _ => String::from("n/a"),
+            }
+        }
+        None => String::from("n/a"),
+    }
+}
+
+fn pop(window: &[&MOSEntry]) -> String {
+    window
+        .iter()
+        .filter_map(|entry| entry.p06.max(entry.p12))
+        .max()
+        .map_or_else(|| String::from("n/a"), |pop| format!("{}%", pop))
+}
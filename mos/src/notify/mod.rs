@@ -0,0 +1,4 @@
+pub mod discord;
+pub mod mqtt;
+pub mod slack;
+pub mod webhook;
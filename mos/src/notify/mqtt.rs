@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mqtt")]
+use super::super::error;
+use super::super::MOS;
+
+/// Topic templates, where `{icao}` is replaced with the station
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Topics {
+    /// Every entry of the forecast as a JSON array
+    pub entries: String,
+    /// Temperature of the next valid time
+    pub temperature: String,
+    /// Next probability of precipitation in percent
+    pub pop: String,
+    /// Flight category of the next valid time
+    pub flight_category: String,
+}
+
+impl Default for Topics {
+    fn default() -> Self {
+        Topics {
+            entries: String::from("mos/{icao}/entries"),
+            temperature: String::from("mos/{icao}/next/temperature"),
+            pop: String::from("mos/{icao}/next/pop"),
+            flight_category: String::from("mos/{icao}/next/flight_category"),
+        }
+    }
+}
+
+/// The (topic, payload) pairs to publish for a forecast. Derived values come from the
+/// first valid time at or after `now`; the PoP from the first one that forecasts it.
+/// Values the forecast doesn't have are left out rather than sent empty
+pub fn messages(mos: &MOS, topics: &Topics, now: DateTime<Utc>) -> Vec<(String, String)> {
+    let topic = |template: &str| template.replace("{icao}", &mos.meta.icao);
+    let mut out = Vec::new();
+
+    if let Ok(entries) = serde_json::to_string(&mos.entries) {
+        out.push((topic(&topics.entries), entries));
+    }
+
+    let mut upcoming = mos.entries.iter().filter(|entry| entry.timestamp >= now);
+    let next = upcoming.clone().next();
+    if let Some(tmp) = next.and_then(|entry| entry.tmp) {
+        out.push((topic(&topics.temperature), tmp.to_string()));
+    }
+    if let Some(pop) = upcoming.find_map(|entry| entry.p06.or(entry.p12)) {
+        out.push((topic(&topics.pop), pop.to_string()));
+    }
+    if let Some(category) = next.and_then(|entry| entry.flight_category()) {
+        out.push((topic(&topics.flight_category), category.to_string()));
+    }
+
+    out
+}
+
+/// A connection to a broker that publishes each forecast it's given
+#[cfg(feature = "mqtt")]
+pub struct Publisher {
+    client: rumqtt::MqttClient,
+    topics: Topics,
+    /// Publish with the retain flag so new subscribers get the latest values at once
+    pub retain: bool,
+}
+
+#[cfg(feature = "mqtt")]
+impl Publisher {
+    pub fn connect(
+        client_id: &str,
+        host: &str,
+        port: u16,
+        topics: Topics,
+    ) -> Result<Publisher, error::Error> {
+        let options = rumqtt::MqttOptions::new(client_id, host, port);
+        let (client, _notifications) = rumqtt::MqttClient::start(options)
+            .map_err(|err| error::Error::Mqtt(err.to_string()))?;
+
+        Ok(Publisher {
+            client,
+            topics,
+            retain: true,
+        })
+    }
+
+    pub fn publish(&mut self, mos: &MOS) -> Result<(), error::Error> {
+        for (topic, payload) in messages(mos, &self.topics, Utc::now()) {
+            self.client
+                .publish(topic, rumqtt::QoS::AtLeastOnce, self.retain, payload)
+                .map_err(|err| error::Error::Mqtt(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
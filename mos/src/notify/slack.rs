@@ -0,0 +1,103 @@
+use chrono::Duration;
+use serde_json::{json, Value};
+
+#[cfg(feature = "client")]
+use super::super::{client, error};
+use super::super::{MOSEntry, MOS};
+
+/// Renders a compact Block Kit message summarizing the next 12 and 24 hours
+pub fn message(mos: &MOS) -> Value {
+    let title = format!(
+        "*{}* MOS guidance, {} cycle",
+        mos.meta.icao,
+        mos.meta.timestamp.format("%b %-d %HZ")
+    );
+
+    json!({
+        "text": format!("{} MOS guidance", mos.meta.icao),
+        "blocks": [
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": title },
+            },
+            {
+                "type": "section",
+                "fields": [
+                    { "type": "mrkdwn", "text": window_summary(mos, 12) },
+                    { "type": "mrkdwn", "text": window_summary(mos, 24) },
+                ],
+            },
+        ],
+    })
+}
+
+/// Posts the summary message to a Slack incoming webhook
+#[cfg(feature = "client")]
+pub fn post(webhook_url: &str, mos: &MOS) -> Result<(), error::Error> {
+    let res = client::http_client()
+        .post(webhook_url)
+        .json(&message(mos))
+        .send()?;
+
+    if !res.status().is_success() {
+        return Err(error::Error::Status {
+            service: "slack webhook",
+            status: res.status().as_u16(),
+        });
+    }
+
+    Ok(())
+}
+
+fn window_summary(mos: &MOS, hours: i64) -> String {
+    let start = match mos.entries.first() {
+        Some(entry) => entry.timestamp,
+        None => return format!("*Next {}h*\nNo data", hours),
+    };
+    let end = start + Duration::hours(hours);
+    let window: Vec<&MOSEntry> = mos
+        .entries
+        .iter()
+        .filter(|entry| entry.timestamp < end)
+        .collect();
+
+    let temps: Vec<isize> = window
+        .iter()
+        .flat_map(|entry| entry.tmp.into_iter().chain(entry.nx))
+        .collect();
+    let temp = match (temps.iter().min(), temps.iter().max()) {
+        (Some(low), Some(high)) => format!("{}–{}°F", low, high),
+        _ => String::from("n/a"),
+    };
+
+    let wind = match window
+        .iter()
+        .filter(|entry| entry.wsp.is_some())
+        .max_by_key(|entry| entry.wsp)
+    {
+        Some(entry) => match (entry.wdr, entry.wsp) {
+            (Some(wdr), Some(wsp)) => format!("{:03}° {}kt", wdr * 10, wsp),
+            (None, Some(wsp)) => format!("{}kt", wsp),
+            _ => String::from("n/a"),
+        },
+        None => String::from("n/a"),
+    };
+
+    let pop = window
+        .iter()
+        .filter_map(|entry| entry.p06.max(entry.p12))
+        .max()
+        .unwrap_or(0);
+
+    let category = window
+        .iter()
+        .filter_map(|entry| entry.flight_category())
+        .min()
+        .map(|category| category.to_string())
+        .unwrap_or_else(|| String::from("n/a"));
+
+    format!(
+        "*Next {}h*\nTemp {}\nWind {}\nPoP {}%\n{}",
+        hours, temp, wind, pop, category
+    )
+}
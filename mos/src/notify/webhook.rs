@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(feature = "client")]
+use super::super::client;
+use super::super::{error, MOSEntry, MOS};
+
+/// A destination and the rules that fire it
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Webhook {
+    pub url: String,
+    pub triggers: Vec<Trigger>,
+}
+
+/// When a webhook fires. Rules are only checked against cycles newer than the last one
+/// seen for the station
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Trigger {
+    /// Every new model cycle
+    NewCycle,
+    /// The first valid time where an element, named as in the JSON output (e.g. "wsp"
+    /// or "p06"), goes above or below a bound. Fires once when a cycle starts
+    /// forecasting the crossing and again only after a cycle that doesn't
+    Threshold {
+        element: String,
+        #[serde(default)]
+        above: Option<isize>,
+        #[serde(default)]
+        below: Option<isize>,
+    },
+}
+
+/// The JSON body POSTed to a webhook
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Payload {
+    pub station: String,
+    pub cycle: DateTime<Utc>,
+    pub trigger: Trigger,
+    /// For threshold triggers, the valid time and value that crossed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<isize>,
+}
+
+/// Checks each incoming forecast against the configured webhooks and posts the ones
+/// that fire. Keeps the last cycle and threshold state per station between calls
+#[derive(Debug, Default)]
+pub struct Dispatcher {
+    hooks: Vec<Webhook>,
+    last_cycle: HashMap<String, DateTime<Utc>>,
+    /// (station, hook, trigger) of thresholds the latest cycle crossed
+    crossed: HashMap<(String, usize, usize), bool>,
+}
+
+impl Dispatcher {
+    pub fn new(hooks: Vec<Webhook>) -> Dispatcher {
+        Dispatcher {
+            hooks,
+            ..Dispatcher::default()
+        }
+    }
+
+    /// Reads a JSON array of webhooks
+    pub fn from_json(json: &str) -> Result<Dispatcher, error::Error> {
+        Ok(Dispatcher::new(serde_json::from_str(json)?))
+    }
+
+    pub fn hooks(&self) -> &[Webhook] {
+        &self.hooks
+    }
+
+    /// The payloads due for a forecast, with the URL each goes to. Nothing fires for a
+    /// cycle at or before the last one seen for the station
+    pub fn evaluate(&mut self, mos: &MOS) -> Vec<(String, Payload)> {
+        let station = mos.meta.icao.clone();
+        let cycle = mos.meta.timestamp;
+        if let Some(last) = self.last_cycle.get(&station) {
+            if cycle <= *last {
+                return Vec::new();
+            }
+        }
+        self.last_cycle.insert(station.clone(), cycle);
+
+        let mut due = Vec::new();
+        for (h, hook) in self.hooks.iter().enumerate() {
+            for (t, trigger) in hook.triggers.iter().enumerate() {
+                let payload = |valid, value| Payload {
+                    station: station.clone(),
+                    cycle,
+                    trigger: trigger.clone(),
+                    valid,
+                    value,
+                };
+                match trigger {
+                    Trigger::NewCycle => due.push((hook.url.clone(), payload(None, None))),
+                    Trigger::Threshold {
+                        element,
+                        above,
+                        below,
+                    } => {
+                        let crossing = mos.entries.iter().find_map(|entry| {
+                            let value = element_value(entry, element)?;
+                            let crosses = above.map_or(false, |above| value > above)
+                                || below.map_or(false, |below| value < below);
+                            if crosses {
+                                Some((entry.timestamp, value))
+                            } else {
+                                None
+                            }
+                        });
+                        let was_crossed = self
+                            .crossed
+                            .insert((station.clone(), h, t), crossing.is_some())
+                            .unwrap_or(false);
+                        if let (Some((valid, value)), false) = (crossing, was_crossed) {
+                            due.push((hook.url.clone(), payload(Some(valid), Some(value))));
+                        }
+                    }
+                }
+            }
+        }
+        due
+    }
+
+    /// Evaluates the forecast and POSTs every payload due, returning how many were
+    /// sent. Stops at the first failed delivery
+    #[cfg(feature = "client")]
+    pub fn dispatch(&mut self, mos: &MOS) -> Result<usize, error::Error> {
+        let due = self.evaluate(mos);
+        let client = client::http_client();
+        for (url, payload) in &due {
+            let res = client.post(url.as_str()).json(payload).send()?;
+            if !res.status().is_success() {
+                return Err(error::Error::Status {
+                    service: "webhook",
+                    status: res.status().as_u16(),
+                });
+            }
+        }
+        Ok(due.len())
+    }
+}
+
+fn element_value(entry: &MOSEntry, element: &str) -> Option<isize> {
+    match element.to_lowercase().as_str() {
+        "nx" | "n/x" => entry.nx,
+        "tmp" => entry.tmp,
+        "dpt" => entry.dpt,
+        "wdr" => entry.wdr,
+        "wsp" => entry.wsp,
+        "p06" => entry.p06,
+        "p12" => entry.p12,
+        "q06" => entry.q06,
+        "q12" => entry.q12,
+        "t06" => entry.t06.map(|t| t.0),
+        "t12" => entry.t12.map(|t| t.0),
+        "poz" => entry.poz,
+        "pos" => entry.pos,
+        "snw" => entry.snw,
+        "cig" => entry.cig,
+        "vis" => entry.vis,
+        "wind_chill" => entry.wind_chill,
+        "heat_index" => entry.heat_index,
+        _ => None,
+    }
+}
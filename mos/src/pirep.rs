@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use super::adds;
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+#[cfg(feature = "client")]
+use super::error;
+use super::stations;
+
+/// A pilot report
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Pirep {
+    pub time: DateTime<Utc>,
+    /// Aircraft type, e.g. "C172"
+    pub aircraft: Option<String>,
+    pub lat: f64,
+    pub lon: f64,
+    /// Feet MSL
+    pub altitude: Option<u32>,
+    /// UUA rather than UA
+    pub urgent: bool,
+    pub turbulence: Option<Condition>,
+    pub icing: Option<Condition>,
+    pub raw: String,
+}
+
+/// A reported icing or turbulence encounter
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Condition {
+    /// As reported, e.g. "LGT", "MOD-SEV" or "NEG"
+    pub intensity: String,
+    /// Icing type (RIME, CLEAR, MIXED) or turbulence type (CAT, CHOP)
+    pub kind: Option<String>,
+    /// Feet MSL
+    pub base: Option<u32>,
+    /// Feet MSL
+    pub top: Option<u32>,
+}
+
+impl Pirep {
+    /// Distance from a point, in nautical miles
+    pub fn distance_to(&self, lat: f64, lon: f64) -> f64 {
+        stations::distance_nm(self.lat, self.lon, lat, lon)
+    }
+
+    /// Age of the report at a time
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        now - self.time
+    }
+
+    /// Parses the `<AircraftReport>` elements of a data server response. Reports
+    /// without a time or position are skipped
+    #[cfg(feature = "client")]
+    pub fn from_xml(xml: &str) -> Vec<Pirep> {
+        adds::elements(xml, "AircraftReport")
+            .into_iter()
+            .filter_map(|element| {
+                let number = |tag| adds::text(element, tag).and_then(|val| val.parse().ok());
+                let raw = adds::text(element, "raw_text").unwrap_or_default();
+
+                Some(Pirep {
+                    time: adds::text(element, "observation_time")?.parse().ok()?,
+                    aircraft: adds::text(element, "aircraft_ref")
+                        .filter(|aircraft| !aircraft.is_empty()),
+                    lat: number("latitude")?,
+                    lon: number("longitude")?,
+                    altitude: adds::text(element, "altitude_ft_msl")
+                        .and_then(|alt| alt.parse().ok()),
+                    urgent: raw.contains(" UUA ")
+                        || adds::text(element, "report_type").as_deref() == Some("Urgent PIREP"),
+                    turbulence: condition(element, "turbulence_condition", "turbulence"),
+                    icing: condition(element, "icing_condition", "icing"),
+                    raw,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "client")]
+fn condition(element: &str, tag: &str, prefix: &str) -> Option<Condition> {
+    let attr = |name: &str| adds::attr(element, tag, &format!("{}_{}", prefix, name));
+    let feet = |name: &str| attr(name).and_then(|val| val.parse().ok());
+
+    Some(Condition {
+        intensity: attr("intensity")?,
+        kind: attr("type"),
+        base: feet("base_ft_msl"),
+        top: feet("top_ft_msl"),
+    })
+}
+
+/// Reports within a radius in nautical miles of a point
+pub fn near(reports: &[Pirep], lat: f64, lon: f64, radius_nm: f64) -> Vec<&Pirep> {
+    reports
+        .iter()
+        .filter(|report| report.distance_to(lat, lon) <= radius_nm)
+        .collect()
+}
+
+/// Reports no older than `max_age` at `now`
+pub fn recent(reports: &[Pirep], max_age: Duration, now: DateTime<Utc>) -> Vec<&Pirep> {
+    reports
+        .iter()
+        .filter(|report| report.age(now) <= max_age)
+        .collect()
+}
+
+/// Pilot reports within a radius (nautical miles) of a station in the embedded table
+/// and no older than `max_age`, newest first
+#[cfg(feature = "client")]
+pub fn get(icao: &str, radius_nm: f64, max_age: Duration) -> Result<Vec<Pirep>, error::Error> {
+    fetch(client::default_transport(), icao, radius_nm, max_age)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(
+    transport: &T,
+    icao: &str,
+    radius_nm: f64,
+    max_age: Duration,
+) -> Result<Vec<Pirep>, error::Error> {
+    let station =
+        stations::lookup(icao).ok_or_else(|| error::Error::UnknownStation(icao.to_uppercase()))?;
+    // The data server takes statute miles and whole hours, so ask for a little more
+    // and trim to the exact radius and age after
+    let xml = adds::query(
+        transport,
+        "aircraftreports",
+        &format!(
+            "radialDistance={};{},{}&hoursBeforeNow={}",
+            (radius_nm * 1.150_78).ceil(),
+            station.lon,
+            station.lat,
+            ((max_age.num_minutes() as f64) / 60.0).ceil().max(1.0)
+        ),
+    )?;
+
+    let now = Utc::now();
+    let mut reports: Vec<Pirep> = Pirep::from_xml(&xml)
+        .into_iter()
+        .filter(|report| {
+            report.distance_to(station.lat, station.lon) <= radius_nm && report.age(now) <= max_age
+        })
+        .collect();
+    reports.sort_by(|a, b| b.time.cmp(&a.time));
+    Ok(reports)
+}
@@ -0,0 +1,111 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use super::category::FlightCategory;
+use super::window::Window;
+use super::MOS;
+
+/// Weights for `MOS::best_window()`. Each criterion scores an entry from 0 (worst) to 1
+/// (best) and the weights set how much each one counts
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Criteria {
+    /// Light winds: 1 at or below `light_wind`, falling to 0 at twice it
+    pub wind_weight: f64,
+    /// VFR 1, MVFR 0.5, IFR 0.2, LIFR 0
+    pub category_weight: f64,
+    /// No precipitation: 1 minus the PoP of the period
+    pub precip_weight: f64,
+    /// Wind speed in knots considered light
+    pub light_wind: isize,
+}
+
+impl Default for Criteria {
+    fn default() -> Self {
+        Criteria {
+            wind_weight: 1.0,
+            category_weight: 1.0,
+            precip_weight: 1.0,
+            light_wind: 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScoredWindow {
+    pub window: Window,
+    /// Weighted mean score of the entries in the window, from 0 to 1
+    pub score: f64,
+}
+
+impl MOS {
+    /// Finds the window of the given length, starting at a valid time and ending within
+    /// the forecast, whose entries score best against the criteria. Ties go to the
+    /// earliest window
+    pub fn best_window(&self, duration: Duration, criteria: &Criteria) -> Option<ScoredWindow> {
+        let last = self.entries.last()?.timestamp;
+        let scores: Vec<f64> = (0..self.entries.len())
+            .map(|i| self.entry_score(i, criteria))
+            .collect();
+
+        self.entries
+            .iter()
+            .map(|entry| Window::new(entry.timestamp, entry.timestamp + duration))
+            .filter(|window| window.end <= last)
+            .filter_map(|window| {
+                let in_window: Vec<f64> = self
+                    .entries
+                    .iter()
+                    .zip(&scores)
+                    .filter(|(entry, _)| {
+                        entry.timestamp >= window.start && entry.timestamp <= window.end
+                    })
+                    .map(|(_, score)| *score)
+                    .collect();
+                if in_window.is_empty() {
+                    return None;
+                }
+                let score = in_window.iter().sum::<f64>() / in_window.len() as f64;
+                Some(ScoredWindow { window, score })
+            })
+            .fold(None, |best: Option<ScoredWindow>, candidate| match best {
+                Some(best) if best.score >= candidate.score => Some(best),
+                _ => Some(candidate),
+            })
+    }
+
+    fn entry_score(&self, i: usize, criteria: &Criteria) -> f64 {
+        let entry = &self.entries[i];
+        let mut total = 0.0;
+        let mut weights = 0.0;
+
+        if let Some(wsp) = entry.wsp {
+            let light = criteria.light_wind.max(1) as f64;
+            let excess = (wsp as f64 - light).max(0.0);
+            total += criteria.wind_weight * (1.0 - excess / light).max(0.0);
+            weights += criteria.wind_weight;
+        }
+
+        if let Some(category) = entry.flight_category() {
+            let score = match category {
+                FlightCategory::Vfr => 1.0,
+                FlightCategory::Mvfr => 0.5,
+                FlightCategory::Ifr => 0.2,
+                FlightCategory::Lifr => 0.0,
+            };
+            total += criteria.category_weight * score;
+            weights += criteria.category_weight;
+        }
+
+        if let Some(pop) = self.period_pop(i) {
+            total += criteria.precip_weight * (1.0 - pop as f64 / 100.0);
+            weights += criteria.precip_weight;
+        }
+
+        if weights > 0.0 {
+            total / weights
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::window::Window;
+use super::{MOSEntry, MOS};
+
+/// NWS likelihood wording for a probability of precipitation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Likelihood {
+    /// 20%
+    SlightChance,
+    /// 30% to 50%
+    Chance,
+    /// 60% to 70%
+    Likely,
+    /// 80% and up
+    Definite,
+}
+
+impl Likelihood {
+    /// `None` below 20%, which NWS forecasts don't mention
+    pub fn from_pop(pop: isize) -> Option<Likelihood> {
+        match pop {
+            p if p >= 80 => Some(Likelihood::Definite),
+            p if p >= 60 => Some(Likelihood::Likely),
+            p if p >= 30 => Some(Likelihood::Chance),
+            p if p >= 20 => Some(Likelihood::SlightChance),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecipType {
+    Rain,
+    Snow,
+    FreezingRain,
+}
+
+impl MOSEntry {
+    /// Expected precipitation type from TYP, falling back to the conditional POZ/POS
+    /// probabilities when TYP is missing
+    pub fn precip_type(&self) -> PrecipType {
+        match self.typ.as_deref() {
+            Some("S") => PrecipType::Snow,
+            Some("Z") => PrecipType::FreezingRain,
+            Some("R") => PrecipType::Rain,
+            _ => match (self.poz, self.pos) {
+                (Some(poz), _) if poz >= 50 => PrecipType::FreezingRain,
+                (_, Some(pos)) if pos >= 50 => PrecipType::Snow,
+                _ => PrecipType::Rain,
+            },
+        }
+    }
+}
+
+/// Precipitation amount in inches, from the Q06/Q12 categories. `max` is `None` for the
+/// open-ended top category
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct QpfRange {
+    pub min: f64,
+    pub max: Option<f64>,
+}
+
+impl QpfRange {
+    /// Category 0 is no measurable precipitation, 6 is 2 in or more
+    pub fn from_category(category: isize) -> Option<QpfRange> {
+        let (min, max) = match category {
+            0 => (0.0, Some(0.0)),
+            1 => (0.01, Some(0.09)),
+            2 => (0.10, Some(0.24)),
+            3 => (0.25, Some(0.49)),
+            4 => (0.50, Some(0.99)),
+            5 => (1.00, Some(1.99)),
+            6 => (2.00, None),
+            _ => return None,
+        };
+        Some(QpfRange { min, max })
+    }
+
+    /// Total of two consecutive periods
+    pub fn add(self, other: QpfRange) -> QpfRange {
+        QpfRange {
+            min: self.min + other.min,
+            max: match (self.max, other.max) {
+                (Some(a), Some(b)) => Some(a + b),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A run of consecutive periods with the same likelihood and precipitation type
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PrecipSegment {
+    pub window: Window,
+    pub likelihood: Likelihood,
+    pub kind: PrecipType,
+}
+
+impl MOS {
+    /// Splits the forecast into segments of mentionable precipitation. Each valid time
+    /// stands for the period since the previous one and takes its PoP from the P06 that
+    /// covers it (the six hours ending at or after it) and its type from
+    /// `MOSEntry::precip_type()`
+    pub fn precip_timeline(&self) -> Vec<PrecipSegment> {
+        let mut segments: Vec<PrecipSegment> = Vec::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let start = match i {
+                0 => entry.timestamp - Duration::hours(3),
+                _ => self.entries[i - 1].timestamp,
+            };
+            let window = Window::new(start, entry.timestamp);
+
+            let likelihood = match self.period_pop(i).and_then(Likelihood::from_pop) {
+                Some(likelihood) => likelihood,
+                None => continue,
+            };
+            let kind = entry.precip_type();
+
+            match segments.last_mut() {
+                Some(last)
+                    if last.window.end == window.start
+                        && last.likelihood == likelihood
+                        && last.kind == kind =>
+                {
+                    last.window.end = window.end
+                }
+                _ => segments.push(PrecipSegment {
+                    window,
+                    likelihood,
+                    kind,
+                }),
+            }
+        }
+
+        segments
+    }
+}
+
+impl MOS {
+    /// Probability in percent of measurable precipitation at any point from `start` to
+    /// `end`, combined from the P06 and P12 periods (the 6 or 12 hours ending at each
+    /// valid time) that overlap it. None if no period does.
+    ///
+    /// The window is tiled with non-overlapping periods from its start, taking a P12
+    /// where one fits inside the window, since it already accounts for how the chances
+    /// in its two halves go together, and a P06 otherwise. Separate periods are assumed
+    /// independent, so the result is `1 - Π(1 - p)`. A period only partly inside the
+    /// window counts as `1 - (1 - p)^f` for the fraction `f` inside, i.e. assuming the
+    /// chance is spread evenly over the period. Hours no period covers add nothing.
+    /// Independence overstates the chance when the same system brings precipitation to
+    /// consecutive periods, so the result is an upper estimate; the largest single PoP
+    /// is a lower bound
+    pub fn pop_over(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<isize> {
+        let window = Window::new(start, end);
+        let mut periods: Vec<(Window, isize, bool)> = Vec::new();
+        for entry in &self.entries {
+            if let Some(p12) = entry.p12 {
+                let period = Window::new(entry.timestamp - Duration::hours(12), entry.timestamp);
+                periods.push((period, p12, true));
+            }
+            if let Some(p06) = entry.p06 {
+                let period = Window::new(entry.timestamp - Duration::hours(6), entry.timestamp);
+                periods.push((period, p06, false));
+            }
+        }
+
+        let mut dry = 1.0;
+        let mut used = false;
+        let mut at = window.start;
+        while at < window.end {
+            let covering = periods.iter().filter(|(period, _, _)| period.contains(at));
+            let inside = |period: &Window| period.start >= window.start && period.end <= window.end;
+            let chosen = covering
+                .clone()
+                .find(|(period, _, twelve)| *twelve && inside(period))
+                .or_else(|| covering.clone().find(|(_, _, twelve)| !twelve))
+                .or_else(|| covering.clone().next());
+
+            let (period, pop) = match chosen {
+                Some((period, pop, _)) => (period, *pop),
+                None => {
+                    // Skip ahead to the next period, if any starts inside the window
+                    match periods
+                        .iter()
+                        .map(|(period, _, _)| period.start)
+                        .filter(|start| *start > at && *start < window.end)
+                        .min()
+                    {
+                        Some(next) => {
+                            at = next;
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
+            };
+
+            let overlap_end = period.end.min(window.end);
+            let overlap_start = period.start.max(at);
+            let fraction = (overlap_end - overlap_start).num_seconds() as f64
+                / (period.end - period.start).num_seconds() as f64;
+            dry *= (1.0 - pop as f64 / 100.0).powf(fraction);
+            used = true;
+            at = period.end;
+        }
+
+        if used {
+            Some(((1.0 - dry) * 100.0).round() as isize)
+        } else {
+            None
+        }
+    }
+
+    /// PoP for the period ending at entry `i`, from the P06 covering it: the one at or
+    /// within six hours after it
+    pub(crate) fn period_pop(&self, i: usize) -> Option<isize> {
+        let entry = self.entries.get(i)?;
+        self.entries[i..]
+            .iter()
+            .take_while(|later| later.timestamp < entry.timestamp + Duration::hours(6))
+            .find_map(|later| later.p06)
+    }
+}
+
+impl fmt::Display for PrecipSegment {
+    /// E.g. "rain likely 18–00Z"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            PrecipType::Rain => "rain",
+            PrecipType::Snow => "snow",
+            PrecipType::FreezingRain => "freezing rain",
+        };
+        let hours = format!(
+            "{}–{}Z",
+            self.window.start.format("%H"),
+            self.window.end.format("%H")
+        );
+        match self.likelihood {
+            Likelihood::SlightChance => write!(f, "slight chance of {} {}", kind, hours),
+            Likelihood::Chance => write!(f, "chance of {} {}", kind, hours),
+            Likelihood::Likely => write!(f, "{} likely {}", kind, hours),
+            Likelihood::Definite => write!(f, "{} {}", kind, hours),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use super::client::{self, Transport};
+use super::stations;
+use super::{error, Product, MOS};
+
+/// A text product from the api.weather.gov products API, as far as it matters here
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextProduct {
+    product_text: String,
+}
+
+/// The locations listing of a product type, e.g. `/products/types/MAV/locations`
+#[derive(Deserialize)]
+struct Locations {
+    locations: BTreeMap<String, Option<String>>,
+}
+
+/// Collective locations found by trying every location, for stations data/mos_stations.txt
+/// doesn't give one for, so that each is only searched for once
+static FOUND: Lazy<Mutex<HashMap<(Product, String), String>>> = Lazy::new(Default::default);
+
+impl Product {
+    /// Product code of the collectives in the text product API
+    pub fn code(self) -> &'static str {
+        match self {
+            Product::Mav => "MAV",
+            Product::Mex => "MEX",
+        }
+    }
+}
+
+/// MAV guidance from the text product API instead of the MOS CGI
+pub fn get(icao: &str) -> Result<MOS, error::Error> {
+    fetch(client::default_transport(), icao, Product::Mav)
+}
+
+/// Fetches the latest collective carrying the station's bulletin and takes the bulletin
+/// out of it. The collective's location comes from data/mos_stations.txt; for stations
+/// it doesn't give one for, every location of the product is tried in turn the first
+/// time, and the one carrying the station is remembered for the next
+pub fn fetch<T: Transport + ?Sized>(
+    transport: &T,
+    icao: &str,
+    product: Product,
+) -> Result<MOS, error::Error> {
+    let icao = icao.to_uppercase();
+    if let Some(location) = stations::collective(&icao, product) {
+        return fetch_location(transport, location, &icao, product);
+    }
+
+    let key = (product, icao.clone());
+    let found = FOUND
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(&key)
+        .cloned();
+    if let Some(location) = found {
+        match fetch_location(transport, &location, &icao, product) {
+            // The station moved to another collective
+            Err(error::Error::StationNotFound(_)) => {}
+            res => return res,
+        }
+    }
+
+    for location in locations(transport, product)? {
+        match fetch_location(transport, &location, &icao, product) {
+            Err(error::Error::StationNotFound(_)) => continue,
+            Ok(mos) => {
+                FOUND
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .insert(key, location);
+                return Ok(mos);
+            }
+            err => return err,
+        }
+    }
+    Err(error::Error::StationNotFound(icao))
+}
+
+/// Locations the product's collectives are filed under, from the products API
+pub fn locations<T: Transport + ?Sized>(
+    transport: &T,
+    product: Product,
+) -> Result<Vec<String>, error::Error> {
+    let res = transport.get(&format!(
+        "https://api.weather.gov/products/types/{}/locations",
+        product.code()
+    ))?;
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "api.weather.gov",
+            status: res.status,
+        });
+    }
+    let listing: Locations = serde_json::from_str(&res.body)?;
+    Ok(listing
+        .locations
+        .into_iter()
+        .map(|(location, _)| location)
+        .collect())
+}
+
+/// Like `fetch`, with the collective's location given as listed by `locations()`
+pub fn fetch_location<T: Transport + ?Sized>(
+    transport: &T,
+    location: &str,
+    icao: &str,
+    product: Product,
+) -> Result<MOS, error::Error> {
+    let icao = icao.to_uppercase();
+    let res = transport.get(&format!(
+        "https://api.weather.gov/products/types/{}/locations/{}/latest",
+        product.code(),
+        location.to_uppercase()
+    ))?;
+    if res.status == 404 {
+        return Err(error::Error::StationNotFound(icao));
+    }
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "api.weather.gov",
+            status: res.status,
+        });
+    }
+
+    match bulletin(&product_text(&res.body)?, &icao) {
+        Some(raw_mos) => MOS::from_string(raw_mos),
+        None => Err(error::Error::StationNotFound(icao)),
+    }
+}
+
+/// The collective in a text product response
+pub(crate) fn product_text(body: &str) -> Result<String, error::Error> {
+    let text_product: TextProduct = serde_json::from_str(body)?;
+    Ok(text_product.product_text)
+}
+
+/// The station's bulletin in a collective: from its header line to the next blank line.
+/// The first bulletin follows the WMO heading and AWIPS identifier without one
+pub(crate) fn bulletin(text: &str, icao: &str) -> Option<String> {
+    let mut lines = text.lines().map(|line| line.trim_end_matches('\r'));
+    let header = lines.find(|line| {
+        line.split_whitespace()
+            .next()
+            .map_or(false, |first| first.eq_ignore_ascii_case(icao))
+    })?;
+
+    let mut raw_mos = vec![header];
+    raw_mos.extend(lines.take_while(|line| !line.trim().is_empty()));
+    let mut raw_mos = raw_mos.join("\n");
+    raw_mos.push('\n');
+    Some(raw_mos)
+}
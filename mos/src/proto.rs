@@ -0,0 +1,150 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::units::Units;
+use super::{MOSEntry, MOSMeta, MOS};
+
+#[allow(clippy::all)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/mos.rs"));
+}
+
+pub use generated::{Cells, Entry, Forecast, Meta, Thunderstorm};
+
+impl From<&MOS> for Forecast {
+    fn from(mos: &MOS) -> Self {
+        Forecast {
+            meta: Some(Meta {
+                icao: mos.meta.icao.clone(),
+                timestamp: mos.meta.timestamp.timestamp(),
+            }),
+            entries: mos.entries.iter().map(Entry::from).collect(),
+            raw: mos.raw.clone(),
+            units: units_to_proto(mos.units),
+            extras: mos
+                .extras
+                .iter()
+                .map(|(label, cells)| {
+                    (
+                        label.clone(),
+                        Cells {
+                            cells: cells.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&MOSEntry> for Entry {
+    fn from(entry: &MOSEntry) -> Self {
+        let int = |val: Option<isize>| val.map(|v| v as i32);
+        let thunderstorm = |val: Option<(isize, isize)>| {
+            val.map(|(thunder, severe)| Thunderstorm {
+                thunder: thunder as i32,
+                severe: severe as i32,
+            })
+        };
+        Entry {
+            timestamp: entry.timestamp.timestamp(),
+            nx: int(entry.nx),
+            tmp: int(entry.tmp),
+            dpt: int(entry.dpt),
+            cld: entry.cld.clone(),
+            wdr: int(entry.wdr),
+            wsp: int(entry.wsp),
+            p06: int(entry.p06),
+            p12: int(entry.p12),
+            q06: int(entry.q06),
+            q12: int(entry.q12),
+            t06: thunderstorm(entry.t06),
+            t12: thunderstorm(entry.t12),
+            poz: int(entry.poz),
+            pos: int(entry.pos),
+            typ: entry.typ.clone(),
+            snw: int(entry.snw),
+            cig: int(entry.cig),
+            vis: int(entry.vis),
+            obv: entry.obv.clone(),
+            wind_chill: int(entry.wind_chill),
+            heat_index: int(entry.heat_index),
+            units: units_to_proto(entry.units),
+        }
+    }
+}
+
+/// Out of range times, which only a foreign encoder could produce, become the epoch
+impl From<Forecast> for MOS {
+    fn from(forecast: Forecast) -> Self {
+        let meta = forecast.meta.unwrap_or_default();
+        MOS {
+            meta: MOSMeta {
+                icao: meta.icao,
+                timestamp: from_unix(meta.timestamp),
+            },
+            entries: forecast.entries.into_iter().map(MOSEntry::from).collect(),
+            raw: forecast.raw,
+            units: units_from_proto(forecast.units),
+            extras: forecast
+                .extras
+                .into_iter()
+                .map(|(label, cells)| (label, cells.cells))
+                .collect(),
+            ..MOS::default()
+        }
+    }
+}
+
+impl From<Entry> for MOSEntry {
+    fn from(entry: Entry) -> Self {
+        let int = |val: Option<i32>| val.map(|v| v as isize);
+        let thunderstorm =
+            |val: Option<Thunderstorm>| val.map(|t| (t.thunder as isize, t.severe as isize));
+        MOSEntry {
+            timestamp: from_unix(entry.timestamp),
+            nx: int(entry.nx),
+            tmp: int(entry.tmp),
+            dpt: int(entry.dpt),
+            cld: entry.cld,
+            wdr: int(entry.wdr),
+            wsp: int(entry.wsp),
+            p06: int(entry.p06),
+            p12: int(entry.p12),
+            q06: int(entry.q06),
+            q12: int(entry.q12),
+            t06: thunderstorm(entry.t06),
+            t12: thunderstorm(entry.t12),
+            poz: int(entry.poz),
+            pos: int(entry.pos),
+            typ: entry.typ,
+            snw: int(entry.snw),
+            cig: int(entry.cig),
+            vis: int(entry.vis),
+            obv: entry.obv,
+            wind_chill: int(entry.wind_chill),
+            heat_index: int(entry.heat_index),
+            units: units_from_proto(entry.units),
+        }
+    }
+}
+
+fn units_to_proto(units: Units) -> i32 {
+    match units {
+        Units::Imperial => generated::Units::Imperial as i32,
+        Units::Metric => generated::Units::Metric as i32,
+    }
+}
+
+fn units_from_proto(units: i32) -> Units {
+    if units == generated::Units::Metric as i32 {
+        Units::Metric
+    } else {
+        Units::Imperial
+    }
+}
+
+fn from_unix(secs: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(secs, 0)
+        .single()
+        .unwrap_or_else(|| Utc.timestamp(0, 0))
+}
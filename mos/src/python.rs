@@ -0,0 +1,49 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use serde::Serialize;
+
+use super::{error, ParseOptions, MOS};
+
+/// Parses a bulletin into a dict shaped like the JSON the backend serves
+#[pyfunction(strict = "false")]
+fn parse(py: Python, text: &str, strict: bool) -> PyResult<PyObject> {
+    let opts = ParseOptions {
+        strict,
+        ..ParseOptions::default()
+    };
+    let mos = MOS::new_with(text, &opts).map_err(to_exception)?;
+    to_py(py, &mos)
+}
+
+/// Fetches and parses the latest MAV or MEX bulletin for a station. The GIL is released
+/// while the request is in flight
+#[cfg(feature = "client")]
+#[pyfunction(product = "\"mav\"")]
+fn fetch(py: Python, icao: &str, product: &str) -> PyResult<PyObject> {
+    let product: super::Product = product.parse().map_err(to_exception)?;
+    let mos = py
+        .allow_threads(|| super::get_product(icao, product))
+        .map_err(to_exception)?;
+    to_py(py, &mos)
+}
+
+#[pymodule]
+fn mos(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    #[cfg(feature = "client")]
+    m.add_function(wrap_pyfunction!(fetch, m)?)?;
+    Ok(())
+}
+
+/// Raised as ValueError with the error code leading the message, e.g. "E_PARSE: ..."
+fn to_exception(err: error::Error) -> PyErr {
+    PyValueError::new_err(format!("{}: {}", err.code(), err))
+}
+
+/// Goes through the json module so timestamps come out as the same RFC 3339 strings
+/// the JSON API has
+fn to_py<T: Serialize>(py: Python, val: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(val).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(py.import("json")?.call1("loads", (json,))?.to_object(py))
+}
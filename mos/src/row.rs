@@ -0,0 +1,83 @@
+use super::format::Format;
+use super::wind::turn;
+use super::{cell, layout, MOSEntry, MOS, ROW_LABEL_RE};
+
+impl MOSEntry {
+    /// Every numeric element by its row label, as compared across cycles by `diff()`
+    /// and `trend`
+    pub(crate) fn numeric_elements(&self) -> [(&'static str, Option<isize>); 16] {
+        [
+            ("N/X", self.nx),
+            ("TMP", self.tmp),
+            ("DPT", self.dpt),
+            ("WDR", self.wdr),
+            ("WSP", self.wsp),
+            ("P06", self.p06),
+            ("P12", self.p12),
+            ("Q06", self.q06),
+            ("Q12", self.q12),
+            ("T06", self.t06.map(|t| t.0)),
+            ("T12", self.t12.map(|t| t.0)),
+            ("POZ", self.poz),
+            ("POS", self.pos),
+            ("SNW", self.snw),
+            ("CIG", self.cig),
+            ("VIS", self.vis),
+        ]
+    }
+}
+
+/// How far an element moved from one value to the next. WDR goes the short way around,
+/// in tens of degrees like the row, so 35 to 1 is 2
+pub(crate) fn change(element: &str, from: isize, to: isize) -> isize {
+    match element {
+        "WDR" => turn(from * 10, to * 10) / 10,
+        _ => to - from,
+    }
+}
+
+impl MOS {
+    /// Cells of the row with the given bulletin label, e.g. "TMP" or "P24", as printed
+    /// in `raw`, one per entry with blanks as None. Any row the bulletin has can be read,
+    /// including ones `MOSEntry` has no field for, and legacy bulletins also answer to
+    /// the current labels. None if the bulletin has no such row
+    pub fn row_text(&self, label: &str) -> Option<Vec<Option<String>>> {
+        let lines: Vec<&str> = self
+            .raw
+            .split('\n')
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+        let format = Format::detect(&self.raw);
+        let (_, chunks, _) = layout(&lines, format, false).ok()?;
+
+        let wanted = label.trim().to_uppercase();
+        let (line, label) = lines.iter().skip(1).find_map(|line| {
+            let label = ROW_LABEL_RE.find(line)?;
+            let found = line[label.start()..label.end()].trim();
+            if found == wanted || format.row(found) == Some(wanted.as_str()) {
+                Some((line, label))
+            } else {
+                None
+            }
+        })?;
+
+        let cells = (0..chunks.len())
+            .map(|i| match cell(line, label.end(), &chunks, i) {
+                "" => None,
+                data => Some(data.to_string()),
+            })
+            .collect();
+        Some(cells)
+    }
+
+    /// `row_text()` with the cells read as numbers, None for blank and non-numeric ones
+    pub fn row(&self, label: &str) -> Option<Vec<Option<isize>>> {
+        let cells = self.row_text(label)?;
+        Some(
+            cells
+                .into_iter()
+                .map(|cell| cell.and_then(|cell| cell.parse::<isize>().ok()))
+                .collect(),
+        )
+    }
+}
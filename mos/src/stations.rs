@@ -0,0 +1,198 @@
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::Product;
+
+/// Location metadata for a MOS station
+#[derive(Debug, Serialize)]
+pub struct Station {
+    pub icao: &'static str,
+    pub name: &'static str,
+    pub lat: f64,
+    pub lon: f64,
+    /// Field elevation in feet MSL
+    pub elevation: f64,
+    /// IANA time zone name
+    pub tz: &'static str,
+    /// NWS forecast office covering the station
+    pub wfo: &'static str,
+}
+
+/// Station table, from data/stations.txt. Lines that don't parse are skipped
+static STATIONS: Lazy<Vec<Station>> = Lazy::new(|| {
+    include_str!("../data/stations.txt")
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(parse_station)
+        .collect()
+});
+
+fn parse_station(line: &'static str) -> Option<Station> {
+    let mut fields = line.split('\t').map(str::trim);
+    Some(Station {
+        icao: fields.next()?,
+        lat: fields.next()?.parse().ok()?,
+        lon: fields.next()?.parse().ok()?,
+        elevation: fields.next()?.parse().ok()?,
+        tz: fields.next()?,
+        wfo: fields.next()?,
+        name: fields.next()?,
+    })
+}
+
+impl Station {
+    pub fn timezone(&self) -> Option<Tz> {
+        self.tz.parse().ok()
+    }
+
+    /// Great-circle distance to a point, in nautical miles
+    pub fn distance_to(&self, lat: f64, lon: f64) -> f64 {
+        distance_nm(self.lat, self.lon, lat, lon)
+    }
+}
+
+const KM_PER_NM: f64 = 1.852;
+
+/// Great-circle distance between two points in degrees, in nautical miles
+pub fn distance_nm(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    const EARTH_RADIUS_NM: f64 = 3440.065;
+
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (lon2 - lon1).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_NM * a.sqrt().asin()
+}
+
+pub fn all() -> &'static [Station] {
+    &STATIONS
+}
+
+pub fn lookup(icao: &str) -> Option<&'static Station> {
+    STATIONS
+        .iter()
+        .find(|station| station.icao.eq_ignore_ascii_case(icao))
+}
+
+/// Stations with MOS guidance and the products they get, each with the location of the
+/// api.weather.gov collective carrying it where known, from data/mos_stations.txt
+static SUPPORTED: Lazy<HashMap<&'static str, Vec<(Product, Option<&'static str>)>>> =
+    Lazy::new(|| {
+        include_str!("../data/mos_stations.txt")
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut tokens = line.split_whitespace();
+                let icao = tokens.next()?;
+                Some((icao, tokens.filter_map(parse_product).collect()))
+            })
+            .collect()
+    });
+
+/// "mav", or "mav=XYZ" with the collective's location
+fn parse_product(token: &'static str) -> Option<(Product, Option<&'static str>)> {
+    let mut parts = token.splitn(2, '=');
+    let product = parts.next()?.parse().ok()?;
+    Some((
+        product,
+        parts.next().filter(|location| !location.is_empty()),
+    ))
+}
+
+/// Whether data/mos_stations.txt lists the product for the station. Only a hint: the
+/// client asks upstream for any station, and a station gets `Error::StationNotFound`
+/// when no bulletin comes back for it
+pub fn supports(icao: &str, product: Product) -> bool {
+    SUPPORTED
+        .get(icao.to_uppercase().as_str())
+        .map_or(false, |products| {
+            products.iter().any(|(listed, _)| *listed == product)
+        })
+}
+
+/// Location of the api.weather.gov collective the station's bulletin is issued in,
+/// e.g. for `products::fetch`. None where data/mos_stations.txt doesn't say
+pub fn collective(icao: &str, product: Product) -> Option<&'static str> {
+    SUPPORTED
+        .get(icao.to_uppercase().as_str())?
+        .iter()
+        .find(|(listed, _)| *listed == product)
+        .and_then(|(_, location)| *location)
+}
+
+/// The `n` stations closest to a point within `max_km` of it, with their distances in
+/// nautical miles, closest first
+pub fn nearest(lat: f64, lon: f64, n: usize, max_km: f64) -> Vec<(&'static Station, f64)> {
+    let mut stations: Vec<(&'static Station, f64)> = STATIONS
+        .iter()
+        .map(|station| (station, station.distance_to(lat, lon)))
+        .filter(|(_, distance_nm)| distance_nm * KM_PER_NM <= max_km)
+        .collect();
+    stations.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    stations.truncate(n);
+    stations
+}
+
+/// User-defined names for stations, e.g. `{"home": "KFIT", "route": ["KFIT", "KBED"]}`
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Aliases(BTreeMap<String, Alias>);
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Alias {
+    Station(String),
+    Stations(Vec<String>),
+}
+
+impl<'de> Deserialize<'de> for Alias {
+    /// A station or a list of them. An empty list is refused rather than taken as an
+    /// alias for nothing
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Alias, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Untagged {
+            Station(String),
+            Stations(Vec<String>),
+        }
+
+        match Untagged::deserialize(deserializer)? {
+            Untagged::Station(icao) => Ok(Alias::Station(icao)),
+            Untagged::Stations(icaos) if icaos.is_empty() => {
+                Err(D::Error::custom("an alias needs at least one station"))
+            }
+            Untagged::Stations(icaos) => Ok(Alias::Stations(icaos)),
+        }
+    }
+}
+
+impl Aliases {
+    pub fn from_json(json: &str) -> Result<Aliases, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn insert(&mut self, name: &str, alias: Alias) {
+        self.0.insert(name.to_string(), alias);
+    }
+
+    /// The stations a name stands for, matched case-insensitively. A name that isn't an
+    /// alias, or is one inserted for no stations, is taken to be an ICAO identifier and
+    /// so never resolves to nothing
+    pub fn resolve(&self, name: &str) -> Vec<String> {
+        let alias = self
+            .0
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, stations)| stations);
+        match alias {
+            Some(Alias::Station(icao)) => vec![icao.to_uppercase()],
+            Some(Alias::Stations(icaos)) if !icaos.is_empty() => {
+                icaos.iter().map(|icao| icao.to_uppercase()).collect()
+            }
+            _ => vec![name.to_string()],
+        }
+    }
+}
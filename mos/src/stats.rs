@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::units::Units;
+use super::MOS;
+
+/// PoP counted by `ForecastStats::pop_50_hours`
+const LIKELY_POP: isize = 50;
+
+/// Headline numbers over the whole forecast period
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ForecastStats {
+    /// Of TMP, None without one
+    pub temperature: Option<TemperatureStats>,
+    /// The strongest WSP, the first one on a tie
+    pub peak_wind: Option<PeakWind>,
+    /// Hours with a PoP of 50% or more. Each hour takes the P06 of its period, or the
+    /// P12 where no P06 covers it
+    pub pop_50_hours: i64,
+    pub units: Units,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TemperatureStats {
+    pub mean: f64,
+    pub min: isize,
+    pub max: isize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct PeakWind {
+    pub speed: isize,
+    /// Tens of degrees, as in the bulletin
+    pub direction: Option<isize>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl MOS {
+    /// Summaries of the temperature, wind and PoP over every entry, e.g. for a
+    /// dashboard's headline numbers
+    pub fn stats(&self) -> ForecastStats {
+        let temps: Vec<isize> = self.entries.iter().filter_map(|entry| entry.tmp).collect();
+        let temperature = match (temps.iter().min(), temps.iter().max()) {
+            (Some(&min), Some(&max)) => Some(TemperatureStats {
+                mean: temps.iter().sum::<isize>() as f64 / temps.len() as f64,
+                min,
+                max,
+            }),
+            _ => None,
+        };
+
+        let mut peak_wind: Option<PeakWind> = None;
+        for entry in &self.entries {
+            let speed = match entry.wsp {
+                Some(speed) => speed,
+                None => continue,
+            };
+            if peak_wind.map_or(true, |peak| speed > peak.speed) {
+                peak_wind = Some(PeakWind {
+                    speed,
+                    direction: entry.wdr,
+                    timestamp: entry.timestamp,
+                });
+            }
+        }
+
+        ForecastStats {
+            temperature,
+            peak_wind,
+            pop_50_hours: self.pop_hours(LIKELY_POP),
+            units: self.units,
+        }
+    }
+
+    /// Number of hours whose PoP is at least `threshold`
+    fn pop_hours(&self, threshold: isize) -> i64 {
+        let mut hourly: BTreeMap<DateTime<Utc>, isize> = BTreeMap::new();
+        let mut fill = |end: DateTime<Utc>, hours: i64, pop: isize| {
+            for hour in 1..=hours {
+                hourly.insert(end - Duration::hours(hour), pop);
+            }
+        };
+        // P06 last, so it overrides the coarser P12 where both are given
+        for entry in &self.entries {
+            if let Some(p12) = entry.p12 {
+                fill(entry.timestamp, 12, p12);
+            }
+        }
+        for entry in &self.entries {
+            if let Some(p06) = entry.p06 {
+                fill(entry.timestamp, 6, p06);
+            }
+        }
+
+        hourly.values().filter(|&&pop| pop >= threshold).count() as i64
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rusoto_dynamodb::{
+    AttributeValue, BatchWriteItemInput, DynamoDb, DynamoDbClient, PutRequest, QueryInput,
+    WriteRequest,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::super::{error, MOS};
+use super::{sort_key, timestamp, valid_key, StoredEntry};
+
+/// Name of the global secondary index on `station_valid`
+pub const VALID_INDEX: &str = "station_valid";
+
+/// Most items BatchWriteItem takes per request
+const BATCH_SIZE: usize = 25;
+
+/// Retries of items DynamoDB hands back unprocessed
+const MAX_RETRIES: usize = 5;
+
+/// Backoff before the first retry, doubled for each one after it
+const BASE_BACKOFF_MS: u64 = 50;
+
+/// Longest backoff between retries
+const MAX_BACKOFF_MS: u64 = 2_000;
+
+/// Archive of parsed cycles in a DynamoDB table keyed by `station` (partition) and `sk`
+/// (sort, see `sort_key`), with a global secondary index named `station_valid` on the
+/// attribute of the same name (see `valid_key`). Each item carries the entry as JSON
+pub struct Store {
+    client: DynamoDbClient,
+    table: String,
+}
+
+impl Store {
+    pub fn new(client: DynamoDbClient, table: &str) -> Store {
+        Store {
+            client,
+            table: table.to_string(),
+        }
+    }
+
+    /// A store on a client for the region and credentials in the environment
+    pub fn from_env(table: &str) -> Store {
+        Store::new(DynamoDbClient::new(rusoto_core::Region::default()), table)
+    }
+
+    /// Writes every entry of the cycle. Writing a cycle again overwrites it
+    pub async fn put_cycle(&self, mos: &MOS) -> Result<(), error::Error> {
+        let mut requests = Vec::with_capacity(mos.entries.len());
+        for stored in mos.to_stored() {
+            requests.push(WriteRequest {
+                put_request: Some(PutRequest {
+                    item: item(&stored)?,
+                }),
+                ..WriteRequest::default()
+            });
+        }
+
+        for chunk in requests.chunks(BATCH_SIZE) {
+            let mut pending = chunk.to_vec();
+            for attempt in 0..=MAX_RETRIES {
+                if attempt > 0 {
+                    tokio::time::delay_for(backoff(attempt)).await;
+                }
+                let mut request_items = HashMap::new();
+                request_items.insert(self.table.clone(), pending);
+                let output = self
+                    .client
+                    .batch_write_item(BatchWriteItemInput {
+                        request_items,
+                        ..BatchWriteItemInput::default()
+                    })
+                    .await
+                    .map_err(storage_error)?;
+
+                pending = output
+                    .unprocessed_items
+                    .and_then(|mut unprocessed| unprocessed.remove(&self.table))
+                    .unwrap_or_default();
+                if pending.is_empty() {
+                    break;
+                }
+            }
+            if !pending.is_empty() {
+                return Err(error::Error::Storage(format!(
+                    "{} items still unprocessed after {} retries",
+                    pending.len(),
+                    MAX_RETRIES
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every archived cycle's forecast for the station at one valid time, oldest cycle
+    /// first
+    pub async fn valid_at(
+        &self,
+        icao: &str,
+        valid: DateTime<Utc>,
+    ) -> Result<Vec<StoredEntry>, error::Error> {
+        let mut values = HashMap::new();
+        values.insert(String::from(":key"), string(valid_key(icao, valid)));
+        let mut stored = self
+            .query(Some(VALID_INDEX), "station_valid = :key", values)
+            .await?;
+        stored.sort_by_key(|item| item.cycle);
+        Ok(stored)
+    }
+
+    /// Every cycle issued at or after `since`, oldest first
+    pub async fn cycles_since(
+        &self,
+        icao: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<MOS>, error::Error> {
+        let mut values = HashMap::new();
+        values.insert(String::from(":station"), string(icao.to_uppercase()));
+        values.insert(String::from(":since"), string(timestamp(since)));
+        let stored = self
+            .query(None, "station = :station AND sk >= :since", values)
+            .await?;
+        Ok(super::into_cycles(stored))
+    }
+
+    async fn query(
+        &self,
+        index: Option<&str>,
+        condition: &str,
+        values: HashMap<String, AttributeValue>,
+    ) -> Result<Vec<StoredEntry>, error::Error> {
+        let mut stored = Vec::new();
+        let mut start_key = None;
+        loop {
+            let output = self
+                .client
+                .query(QueryInput {
+                    table_name: self.table.clone(),
+                    index_name: index.map(|index| index.to_string()),
+                    key_condition_expression: Some(condition.to_string()),
+                    expression_attribute_values: Some(values.clone()),
+                    exclusive_start_key: start_key,
+                    ..QueryInput::default()
+                })
+                .await
+                .map_err(storage_error)?;
+
+            for item in output.items.unwrap_or_default() {
+                stored.push(from_item(&item)?);
+            }
+            start_key = output.last_evaluated_key;
+            if start_key.is_none() {
+                break;
+            }
+        }
+        Ok(stored)
+    }
+}
+
+fn item(stored: &StoredEntry) -> Result<HashMap<String, AttributeValue>, error::Error> {
+    let mut item = HashMap::new();
+    item.insert(String::from("station"), string(stored.station.clone()));
+    item.insert(
+        String::from("sk"),
+        string(sort_key(stored.cycle, stored.entry.timestamp)),
+    );
+    item.insert(
+        String::from("station_valid"),
+        string(valid_key(&stored.station, stored.entry.timestamp)),
+    );
+    item.insert(String::from("cycle"), string(timestamp(stored.cycle)));
+    item.insert(
+        String::from("lead_hours"),
+        AttributeValue {
+            n: Some(stored.lead_hours().to_string()),
+            ..AttributeValue::default()
+        },
+    );
+    item.insert(
+        String::from("entry"),
+        string(serde_json::to_string(&stored.entry)?),
+    );
+    Ok(item)
+}
+
+fn from_item(item: &HashMap<String, AttributeValue>) -> Result<StoredEntry, error::Error> {
+    let attr = |name: &str| {
+        item.get(name)
+            .and_then(|val| val.s.as_deref())
+            .ok_or_else(|| error::Error::Storage(format!("item without a {} attribute", name)))
+    };
+
+    Ok(StoredEntry {
+        station: attr("station")?.to_string(),
+        cycle: attr("cycle")?.parse()?,
+        entry: serde_json::from_str(attr("entry")?)?,
+    })
+}
+
+fn string(val: String) -> AttributeValue {
+    AttributeValue {
+        s: Some(val),
+        ..AttributeValue::default()
+    }
+}
+
+/// Full jitter: anywhere up to the exponential backoff of the retry, so writers throttled
+/// together don't all retry together
+fn backoff(attempt: usize) -> Duration {
+    let ceiling = (BASE_BACKOFF_MS << (attempt - 1)).min(MAX_BACKOFF_MS);
+    Duration::from_millis(rand::thread_rng().gen_range(0, ceiling + 1))
+}
+
+fn storage_error<E: std::error::Error + 'static>(err: rusoto_core::RusotoError<E>) -> error::Error {
+    error::Error::Storage(err.to_string())
+}
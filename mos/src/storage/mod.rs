@@ -0,0 +1,76 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{MOSEntry, MOSMeta, MOS};
+
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb;
+#[cfg(feature = "s3")]
+pub mod s3;
+
+/// One entry of an archived cycle
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoredEntry {
+    pub station: String,
+    pub cycle: DateTime<Utc>,
+    pub entry: MOSEntry,
+}
+
+impl StoredEntry {
+    /// Hours from the model cycle to the valid time
+    pub fn lead_hours(&self) -> i64 {
+        (self.entry.timestamp - self.cycle).num_hours()
+    }
+}
+
+/// "{cycle}#{valid_time}" in RFC 3339, which sorts by cycle then valid time
+pub fn sort_key(cycle: DateTime<Utc>, valid: DateTime<Utc>) -> String {
+    format!("{}#{}", timestamp(cycle), timestamp(valid))
+}
+
+/// "{station}#{valid_time}", the key for looking up every cycle's forecast for a time
+pub fn valid_key(station: &str, valid: DateTime<Utc>) -> String {
+    format!("{}#{}", station.to_uppercase(), timestamp(valid))
+}
+
+pub(crate) fn timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Groups stored entries back into one MOS per cycle, oldest first. Only the metadata
+/// and entries survive storage, so `raw` is empty
+pub fn into_cycles(mut stored: Vec<StoredEntry>) -> Vec<MOS> {
+    stored.sort_by(|a, b| (a.cycle, a.entry.timestamp).cmp(&(b.cycle, b.entry.timestamp)));
+
+    let mut cycles: Vec<MOS> = Vec::new();
+    for item in stored {
+        match cycles.last_mut() {
+            Some(mos) if mos.meta.timestamp == item.cycle && mos.meta.icao == item.station => {
+                mos.entries.push(item.entry)
+            }
+            _ => cycles.push(MOS {
+                meta: MOSMeta {
+                    icao: item.station,
+                    timestamp: item.cycle,
+                },
+                entries: vec![item.entry],
+                ..MOS::default()
+            }),
+        }
+    }
+    cycles
+}
+
+impl MOS {
+    /// The cycle's entries as they're archived
+    pub fn to_stored(&self) -> Vec<StoredEntry> {
+        self.entries
+            .iter()
+            .map(|entry| StoredEntry {
+                station: self.meta.icao.clone(),
+                cycle: self.meta.timestamp,
+                entry: entry.clone(),
+            })
+            .collect()
+    }
+}
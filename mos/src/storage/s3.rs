@@ -0,0 +1,124 @@
+use rusoto_s3::{GetObjectRequest, ListObjectsV2Request, S3Client, S3};
+use std::future::Future;
+use std::sync::{mpsc, Mutex};
+use tokio::io::AsyncReadExt;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use super::super::error;
+
+/// Archived bulletins stored as objects in an S3 bucket, one bulletin or collective per
+/// object
+pub struct Bulletins {
+    client: S3Client,
+    bucket: String,
+    /// Runs the requests, so the archive can be read from synchronous code
+    handle: Handle,
+    _runtime: Mutex<Runtime>,
+}
+
+impl Bulletins {
+    pub fn new(client: S3Client, bucket: &str) -> Result<Bulletins, error::Error> {
+        let runtime = Builder::new()
+            .threaded_scheduler()
+            .core_threads(1)
+            .enable_all()
+            .build()?;
+        Ok(Bulletins {
+            client,
+            bucket: bucket.to_string(),
+            handle: runtime.handle().clone(),
+            _runtime: Mutex::new(runtime),
+        })
+    }
+
+    /// Bulletins on a client for the region and credentials in the environment
+    pub fn from_env(bucket: &str) -> Result<Bulletins, error::Error> {
+        Bulletins::new(S3Client::new(rusoto_core::Region::default()), bucket)
+    }
+
+    fn run<T: Send + 'static>(
+        &self,
+        fut: impl Future<Output = Result<T, error::Error>> + Send + 'static,
+    ) -> Result<T, error::Error> {
+        let (tx, rx) = mpsc::channel();
+        self.handle.spawn(async move {
+            let _ = tx.send(fut.await);
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(error::Error::Storage(String::from("request was dropped"))))
+    }
+
+    /// Keys of every object under the prefix, in the bucket's order
+    pub fn keys(&self, prefix: &str) -> Result<Vec<String>, error::Error> {
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+        let prefix = prefix.to_string();
+        self.run(async move {
+            let mut keys = Vec::new();
+            let mut continuation_token = None;
+            loop {
+                let output = client
+                    .list_objects_v2(ListObjectsV2Request {
+                        bucket: bucket.clone(),
+                        prefix: Some(prefix.clone()),
+                        continuation_token,
+                        ..ListObjectsV2Request::default()
+                    })
+                    .await
+                    .map_err(storage_error)?;
+
+                keys.extend(
+                    output
+                        .contents
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|object| object.key)
+                        .filter(|key| !key.ends_with('/')),
+                );
+                continuation_token = output.next_continuation_token;
+                if continuation_token.is_none() {
+                    return Ok(keys);
+                }
+            }
+        })
+    }
+
+    /// Contents of the object
+    pub fn get(&self, key: &str) -> Result<Vec<u8>, error::Error> {
+        let client = self.client.clone();
+        let input = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_string(),
+            ..GetObjectRequest::default()
+        };
+        self.run(async move {
+            let output = client.get_object(input).await.map_err(storage_error)?;
+            let mut body = Vec::new();
+            if let Some(stream) = output.body {
+                stream.into_async_read().read_to_end(&mut body).await?;
+            }
+            Ok(body)
+        })
+    }
+}
+
+/// Bucket and prefix of "s3://bucket/prefix", None for anything else
+pub fn parse_url(url: &str) -> Option<(&str, &str)> {
+    if !url.starts_with("s3://") {
+        return None;
+    }
+    let rest = &url["s3://".len()..];
+    let (bucket, prefix) = match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash + 1..]),
+        None => (rest, ""),
+    };
+    if bucket.is_empty() {
+        None
+    } else {
+        Some((bucket, prefix))
+    }
+}
+
+fn storage_error<E: std::error::Error + 'static>(err: rusoto_core::RusotoError<E>) -> error::Error {
+    error::Error::Storage(err.to_string())
+}
@@ -0,0 +1,85 @@
+use std::io::BufRead;
+
+use super::{error, ParseOptions, MOS};
+
+/// Bulletins of a collective as they're read, see `parse_stream`
+pub struct Stream<R> {
+    reader: R,
+    opts: ParseOptions,
+    line: String,
+    done: bool,
+}
+
+/// Parses the bulletins of a collective one at a time as their blocks are read, so a
+/// multi-megabyte file never has to be held in memory. Lines before a bulletin header,
+/// like the WMO heading and AWIPS identifier, are skipped, and a blank line ends each
+/// bulletin. A bulletin that doesn't parse is yielded as its error and the stream goes
+/// on; a read error ends it
+pub fn parse_stream<R: BufRead>(reader: R) -> Stream<R> {
+    parse_stream_with(reader, ParseOptions::default())
+}
+
+/// Like `parse_stream`, parsing each bulletin with the given options
+pub fn parse_stream_with<R: BufRead>(reader: R, opts: ParseOptions) -> Stream<R> {
+    Stream {
+        reader,
+        opts,
+        line: String::new(),
+        done: false,
+    }
+}
+
+impl<R: BufRead> Stream<R> {
+    /// The next line without its line ending, or None at the end of input
+    fn next_line(&mut self) -> Result<Option<&str>, error::Error> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.line.trim_end_matches(|c| c == '\n' || c == '\r')))
+    }
+
+    /// Text of the next bulletin, from its header line to the blank line or end of
+    /// input after it
+    fn next_bulletin(&mut self) -> Result<Option<String>, error::Error> {
+        let mut raw_mos = loop {
+            match self.next_line()? {
+                Some(line) if MOS::parse_meta(line).is_ok() => break format!("{}\n", line),
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        };
+        while let Some(line) = self.next_line()? {
+            if line.trim().is_empty() {
+                break;
+            }
+            raw_mos.push_str(line);
+            raw_mos.push('\n');
+        }
+        Ok(Some(raw_mos))
+    }
+}
+
+impl<R: BufRead> Iterator for Stream<R> {
+    type Item = Result<MOS, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_bulletin() {
+            Ok(Some(raw_mos)) => Some(MOS::parse(&raw_mos, &self.opts).map(|(mut mos, _)| {
+                mos.raw = raw_mos;
+                mos
+            })),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
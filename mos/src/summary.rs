@@ -0,0 +1,160 @@
+use chrono::{Duration, Timelike};
+
+use super::units::Units;
+use super::{MOSEntry, MOS};
+
+/// PoP below which precipitation isn't mentioned, as in NWS worded forecasts
+const MENTION_POP: isize = 20;
+
+impl MOS {
+    /// Worded forecast, one line per day or night period, e.g.
+    ///
+    /// "Tonight: low around 28, becoming overcast, 40% chance of snow after 06Z; winds NW
+    /// 10–15 kt."
+    ///
+    /// Periods are the 12-hour windows ending at each N/X value. Their names assume a US
+    /// station, where daytime maxima verify at 00Z and nighttime minima at 12Z
+    pub fn summarize(&self) -> String {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if entry.nx.is_none() {
+                continue;
+            }
+            let period = &self.entries[start..=i];
+            start = i + 1;
+            lines.push(format!(
+                "{}: {}.",
+                period_name(entry, lines.is_empty()),
+                period_summary(period, entry)
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn is_daytime(end: &MOSEntry) -> bool {
+    end.timestamp.hour() < 6 || end.timestamp.hour() >= 18
+}
+
+fn period_name(end: &MOSEntry, first: bool) -> String {
+    match (is_daytime(end), first) {
+        (true, true) => String::from("Today"),
+        (false, true) => String::from("Tonight"),
+        (true, false) => (end.timestamp - Duration::hours(12))
+            .format("%A")
+            .to_string(),
+        (false, false) => (end.timestamp - Duration::hours(24))
+            .format("%A night")
+            .to_string(),
+    }
+}
+
+fn period_summary(period: &[MOSEntry], end: &MOSEntry) -> String {
+    let mut parts = Vec::new();
+
+    if let Some(nx) = end.nx {
+        if is_daytime(end) {
+            parts.push(format!("high near {}", nx));
+        } else {
+            parts.push(format!("low around {}", nx));
+        }
+    }
+
+    let skies: Vec<&str> = period
+        .iter()
+        .filter_map(|entry| entry.cld.as_deref().and_then(sky_words))
+        .collect();
+    match (skies.first(), skies.last()) {
+        (Some(first), Some(last)) if first != last => parts.push(format!("becoming {}", last)),
+        (Some(first), _) => parts.push(first.to_string()),
+        _ => (),
+    }
+
+    if let Some(precip) = precip_phrase(period) {
+        parts.push(precip);
+    }
+
+    let mut out = parts.join(", ");
+    if let Some(wind) = wind_phrase(period) {
+        out.push_str("; ");
+        out.push_str(&wind);
+    }
+
+    out
+}
+
+fn precip_phrase(period: &[MOSEntry]) -> Option<String> {
+    let wettest = period
+        .iter()
+        .filter(|entry| entry.p06.is_some())
+        .max_by_key(|entry| entry.p06)?;
+    let pop = wettest.p06?;
+    if pop < MENTION_POP {
+        return None;
+    }
+
+    let kind = match wettest.typ.as_deref() {
+        Some("S") => "snow",
+        Some("Z") => "freezing rain",
+        Some("R") => "rain",
+        _ => "precipitation",
+    };
+    let mut phrase = format!("{}% chance of {}", pop, kind);
+
+    // P06 covers the six hours ending at its valid time
+    let onset = period
+        .iter()
+        .find(|entry| entry.p06.map_or(false, |p| p >= MENTION_POP))
+        .map(|entry| entry.timestamp - Duration::hours(6));
+    if let (Some(onset), Some(first)) = (onset, period.first()) {
+        if onset > first.timestamp {
+            phrase.push_str(&format!(" after {}", onset.format("%HZ")));
+        }
+    }
+
+    Some(phrase)
+}
+
+fn wind_phrase(period: &[MOSEntry]) -> Option<String> {
+    let speeds: Vec<isize> = period.iter().filter_map(|entry| entry.wsp).collect();
+    let (low, high) = (*speeds.iter().min()?, *speeds.iter().max()?);
+    // 5 kt
+    let light = match period.first()?.units {
+        Units::Imperial => 5,
+        Units::Metric => 9,
+    };
+    if high < light {
+        return Some(String::from("winds light"));
+    }
+
+    let strongest = period.iter().max_by_key(|entry| entry.wsp)?;
+    let dir = strongest
+        .wdr
+        .map(|wdr| compass_abbrev(wdr * 10))
+        .unwrap_or("variable");
+    let unit = strongest.units.speed_symbol();
+    if low == high {
+        Some(format!("winds {} {} {}", dir, high, unit))
+    } else {
+        Some(format!("winds {} {}–{} {}", dir, low, high, unit))
+    }
+}
+
+fn sky_words(cld: &str) -> Option<&'static str> {
+    match cld {
+        "CL" => Some("clear"),
+        "FW" => Some("mostly clear"),
+        "SC" => Some("partly cloudy"),
+        "BK" => Some("mostly cloudy"),
+        "OV" => Some("overcast"),
+        _ => None,
+    }
+}
+
+fn compass_abbrev(degrees: isize) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    let idx = ((degrees.rem_euclid(360) as f64 / 45.0).round() as usize) % POINTS.len();
+    POINTS[idx]
+}
@@ -0,0 +1,330 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::iter::Peekable;
+
+#[cfg(feature = "client")]
+use super::adds;
+use super::category::FlightCategory;
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+use super::error;
+use super::metar::{self, SkyLayer, Wind};
+use super::MOS;
+
+static PERIOD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{2})(\d{2})/(\d{2})(\d{2})$").unwrap());
+static FROM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^FM(\d{2})(\d{2})(\d{2})$").unwrap());
+static PROB_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^PROB(\d{2})$").unwrap());
+
+/// A terminal aerodrome forecast
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Taf {
+    pub icao: String,
+    pub issued: DateTime<Utc>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: DateTime<Utc>,
+    /// The initial conditions followed by each change group in the order given
+    pub groups: Vec<ChangeGroup>,
+    pub raw: String,
+}
+
+/// How a change group modifies the forecast before it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Conditions from the start of the valid period
+    Base,
+    /// FM: replaces everything from its start time
+    From,
+    /// BECMG: a gradual change over the period, in effect after it
+    Becoming,
+    /// TEMPO: temporary fluctuations during the period
+    Tempo,
+    /// PROBnn, alone or with TEMPO
+    Probability { percent: u8, tempo: bool },
+}
+
+/// Conditions forecast for one valid period. Elements the group doesn't mention are
+/// None or empty
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ChangeGroup {
+    pub kind: ChangeKind,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub wind: Option<Wind>,
+    /// Statute miles
+    pub visibility: Option<f64>,
+    pub weather: Vec<String>,
+    pub sky: Vec<SkyLayer>,
+}
+
+/// A valid time where the TAF and MOS guidance put the airport in different categories
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Disagreement {
+    pub timestamp: DateTime<Utc>,
+    pub taf: FlightCategory,
+    pub mos: FlightCategory,
+}
+
+impl ChangeGroup {
+    fn new(kind: ChangeKind, from: DateTime<Utc>, to: DateTime<Utc>) -> ChangeGroup {
+        ChangeGroup {
+            kind,
+            from,
+            to,
+            wind: None,
+            visibility: None,
+            weather: Vec::new(),
+            sky: Vec::new(),
+        }
+    }
+
+    pub fn ceiling(&self) -> Option<u32> {
+        metar::ceiling(&self.sky)
+    }
+
+    /// Overlays the elements a BECMG group changes
+    fn apply(&mut self, change: &ChangeGroup) {
+        if change.wind.is_some() {
+            self.wind = change.wind;
+        }
+        if change.visibility.is_some() {
+            self.visibility = change.visibility;
+        }
+        if !change.weather.is_empty() {
+            self.weather = change.weather.clone();
+        }
+        if !change.sky.is_empty() {
+            self.sky = change.sky.clone();
+        }
+    }
+}
+
+impl Taf {
+    /// Parses a raw TAF, taking the month and year of its timestamps from the current
+    /// time
+    pub fn parse(raw: &str) -> Result<Taf, error::Error> {
+        Taf::parse_at(raw, Utc::now())
+    }
+
+    /// Parses a raw TAF issued at or shortly before `now`
+    pub fn parse_at(raw: &str, now: DateTime<Utc>) -> Result<Taf, error::Error> {
+        let raw = raw.trim();
+        let mut tokens = raw
+            .split_whitespace()
+            .skip_while(|token| *token == "TAF" || *token == "AMD" || *token == "COR")
+            .peekable();
+
+        let icao = match tokens.next() {
+            Some(icao) => icao.to_string(),
+            None => return Err(error::Error::parse("TAF", "forecast is empty")),
+        };
+        let issued = match tokens
+            .next()
+            .and_then(|token| metar::observation_time(token, now))
+        {
+            Some(issued) => issued,
+            None => return Err(error::Error::parse("TAF", "no issue time")),
+        };
+        let (valid_from, valid_to) = match tokens.next().and_then(|token| period(token, issued)) {
+            Some(valid) => valid,
+            None => return Err(error::Error::parse("TAF", "no valid period")),
+        };
+
+        let mut groups = vec![ChangeGroup::new(ChangeKind::Base, valid_from, valid_to)];
+        while let Some(token) = tokens.next() {
+            if token == "RMK" {
+                break;
+            }
+
+            let group = if let Some(caps) = FROM_RE.captures(token) {
+                let from = caps[1]
+                    .parse()
+                    .ok()
+                    .and_then(|day| day_hour(day, caps[2].parse().ok()?, issued))
+                    .map(|from| from + Duration::minutes(caps[3].parse().unwrap_or(0)));
+                from.map(|from| ChangeGroup::new(ChangeKind::From, from, valid_to))
+            } else if token == "BECMG" || token == "TEMPO" {
+                let kind = if token == "BECMG" {
+                    ChangeKind::Becoming
+                } else {
+                    ChangeKind::Tempo
+                };
+                next_period(&mut tokens, issued).map(|(from, to)| ChangeGroup::new(kind, from, to))
+            } else if let Some(caps) = PROB_RE.captures(token) {
+                let tempo = tokens.peek() == Some(&"TEMPO");
+                if tempo {
+                    tokens.next();
+                }
+                let kind = ChangeKind::Probability {
+                    percent: caps[1].parse().unwrap_or(0),
+                    tempo,
+                };
+                next_period(&mut tokens, issued).map(|(from, to)| ChangeGroup::new(kind, from, to))
+            } else {
+                None
+            };
+            if let Some(group) = group {
+                // An FM group ends the one before it
+                if group.kind == ChangeKind::From {
+                    if let Some(prev) = groups
+                        .iter_mut()
+                        .rev()
+                        .find(|prev| prev.kind == ChangeKind::From || prev.kind == ChangeKind::Base)
+                    {
+                        prev.to = group.from;
+                    }
+                }
+                groups.push(group);
+                continue;
+            }
+
+            let group = groups.last_mut().unwrap();
+            if token == "CAVOK" {
+                group.visibility = Some(6.0);
+            } else if let Some(wind) = metar::wind(token) {
+                group.wind = Some(wind);
+            } else if let Some(vis) = metar::visibility_group(token, &mut tokens) {
+                group.visibility = Some(vis);
+            } else if let Some(layer) = metar::sky_layer(token) {
+                group.sky.push(layer);
+            } else if token == "NSW" || metar::is_weather(token) {
+                group.weather.push(token.to_string());
+            }
+        }
+
+        Ok(Taf {
+            icao,
+            issued,
+            valid_from,
+            valid_to,
+            groups,
+            raw: raw.to_string(),
+        })
+    }
+
+    /// The prevailing conditions at a time: the base or FM group covering it, with any
+    /// BECMG group that has finished applied on top. TEMPO and PROB groups are left out
+    pub fn prevailing(&self, at: DateTime<Utc>) -> Option<ChangeGroup> {
+        if at < self.valid_from || at >= self.valid_to {
+            return None;
+        }
+
+        let mut conditions: Option<ChangeGroup> = None;
+        for group in &self.groups {
+            match group.kind {
+                ChangeKind::Base | ChangeKind::From if group.from <= at && at < group.to => {
+                    conditions = Some(group.clone());
+                }
+                ChangeKind::Becoming if group.to <= at => {
+                    if let Some(conditions) = conditions.as_mut() {
+                        conditions.apply(group);
+                    }
+                }
+                _ => {}
+            }
+        }
+        conditions
+    }
+
+    /// TEMPO and PROB groups in effect at a time
+    pub fn temporary(&self, at: DateTime<Utc>) -> Vec<&ChangeGroup> {
+        self.groups
+            .iter()
+            .filter(|group| match group.kind {
+                ChangeKind::Tempo | ChangeKind::Probability { .. } => {
+                    group.from <= at && at < group.to
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Flight category of the prevailing conditions at a time
+    pub fn flight_category(&self, at: DateTime<Utc>) -> Option<FlightCategory> {
+        self.prevailing(at)
+            .map(|group| FlightCategory::from_conditions(group.ceiling(), group.visibility))
+    }
+
+    /// MOS valid times inside the TAF period where the guidance and the TAF's prevailing
+    /// conditions fall in different flight categories
+    pub fn disagreements(&self, mos: &MOS) -> Vec<Disagreement> {
+        mos.category_timeline()
+            .into_iter()
+            .filter_map(|(timestamp, category)| {
+                let taf = self.flight_category(timestamp)?;
+                if taf == category {
+                    None
+                } else {
+                    Some(Disagreement {
+                        timestamp,
+                        taf,
+                        mos: category,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+fn next_period<'a, I>(
+    tokens: &mut Peekable<I>,
+    issued: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let valid = period(tokens.peek()?, issued)?;
+    tokens.next();
+    Some(valid)
+}
+
+/// A DDHH/DDHH period, where the end hour may be 24
+fn period(token: &str, issued: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let caps = PERIOD_RE.captures(token)?;
+    let from = day_hour(caps[1].parse().ok()?, caps[2].parse().ok()?, issued)?;
+    let to = day_hour(caps[3].parse().ok()?, caps[4].parse().ok()?, issued)?;
+    Some((from, to))
+}
+
+/// Day of month and hour on or after the day of issue, rolling into the next month
+/// when the day is earlier than the issue day
+fn day_hour(day: u32, hour: u32, issued: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (mut year, mut month) = (issued.year(), issued.month());
+    if day + 1 < issued.day() {
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+    let date = Utc.ymd_opt(year, month, day).single()?;
+    match hour {
+        24 => Some(date.and_hms(0, 0, 0) + Duration::days(1)),
+        _ => date.and_hms_opt(hour, 0, 0),
+    }
+}
+
+/// Latest TAF for the station from aviationweather.gov
+#[cfg(feature = "client")]
+pub fn get(icao: &str) -> Result<Taf, error::Error> {
+    fetch(client::default_transport(), icao)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(transport: &T, icao: &str) -> Result<Taf, error::Error> {
+    let icao = icao.to_uppercase();
+    let xml = adds::query(
+        transport,
+        "tafs",
+        &format!("hoursBeforeNow=12&mostRecent=true&stationString={}", icao),
+    )?;
+
+    match adds::text(&xml, "raw_text") {
+        Some(raw) => Taf::parse(&raw),
+        None => Err(error::Error::StationNotFound(icao)),
+    }
+}
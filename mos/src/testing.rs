@@ -0,0 +1,127 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "client")]
+use super::client::{Response, Transport};
+use super::{error, MOSEntry, MOSMeta, MOS};
+
+/// Directory holding the bulletin corpus. It's resolved from this crate's manifest, so
+/// downstream crates can test against the same bulletins
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+}
+
+/// File names of every fixture, sorted
+pub fn fixtures() -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(fixtures_dir()) {
+        Ok(dir) => dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.ends_with(".txt"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    names
+}
+
+/// Text of a fixture, e.g. `load("kfit_mav.txt")`. Panics if it doesn't exist
+pub fn load(name: &str) -> String {
+    let path = fixtures_dir().join(name);
+    match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => panic!("could not read fixture {}: {}", path.display(), err),
+    }
+}
+
+/// Parses a fixture holding a single bulletin
+pub fn parse(name: &str) -> Result<MOS, error::Error> {
+    MOS::from_file(fixtures_dir().join(name))
+}
+
+/// The bulletins of a collective fixture, which separates stations with blank lines
+pub fn bulletins(name: &str) -> Vec<String> {
+    load(name)
+        .split("\n\n")
+        .filter(|bulletin| !bulletin.trim().is_empty())
+        .map(|bulletin| bulletin.to_string())
+        .collect()
+}
+
+/// Transport answering every request with the closure's result, for testing the client
+/// against canned pages, error statuses and timeouts
+#[cfg(feature = "client")]
+pub struct FnTransport<F>(pub F);
+
+#[cfg(feature = "client")]
+impl<F> Transport for FnTransport<F>
+where
+    F: Fn(&str) -> Result<Response, error::Error>,
+{
+    fn get(&self, url: &str) -> Result<Response, error::Error> {
+        (self.0)(url)
+    }
+}
+
+/// A 200 response wrapping the bulletin in a page like the NWS CGI's
+#[cfg(feature = "client")]
+pub fn page(bulletin: &str) -> Response {
+    Response {
+        status: 200,
+        body: format!(
+            "<html><head><title>MOS</title></head><body><pre>\n{}</pre></body></html>",
+            bulletin
+        ),
+    }
+}
+
+/// Directory holding the canonical JSON of every fixture, written by
+/// `cargo run -p cia-backed -- gen-snapshots`
+pub fn snapshots_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+}
+
+/// Fixtures holding a single bulletin, which are the ones with snapshots
+pub fn snapshot_fixtures() -> Vec<String> {
+    fixtures()
+        .into_iter()
+        .filter(|name| !name.contains("collective"))
+        .collect()
+}
+
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    meta: &'a MOSMeta,
+    entries: &'a [MOSEntry],
+}
+
+#[derive(Serialize)]
+struct ErrorSnapshot {
+    error: &'static str,
+}
+
+/// Canonical JSON of a parsed fixture: the meta and entries, or the error code for
+/// fixtures that don't parse
+pub fn snapshot(name: &str) -> String {
+    let json = match parse(name) {
+        Ok(mos) => serde_json::to_string_pretty(&Snapshot {
+            meta: &mos.meta,
+            entries: &mos.entries,
+        }),
+        Err(err) => serde_json::to_string_pretty(&ErrorSnapshot { error: err.code() }),
+    };
+
+    let mut json = json.unwrap_or_else(|err| panic!("could not serialize {}: {}", name, err));
+    json.push('\n');
+    json
+}
+
+/// Snapshot file name for a fixture, e.g. kfit_mav.json for kfit_mav.txt
+pub fn snapshot_name(fixture: &str) -> String {
+    format!("{}.json", fixture.trim_end_matches(".txt"))
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+
+use super::{stations, MOSEntry, MOS};
+
+fn f_to_c(f: f64) -> f64 {
+    (f - 32.0) * 5.0 / 9.0
+}
+
+/// Saturation vapor pressure in hPa (Magnus)
+fn vapor_pressure(c: f64) -> f64 {
+    6.1078 * 10f64.powf(7.5 * c / (237.3 + c))
+}
+
+impl MOSEntry {
+    /// Estimated density altitude in feet at a field of the given elevation (feet MSL).
+    ///
+    /// MOS has no pressure forecast, so station pressure is taken from the standard
+    /// atmosphere at the field elevation (altimeter 29.92). The dew point corrects for
+    /// humidity through the virtual temperature
+    pub fn density_altitude(&self, elevation: f64) -> Option<f64> {
+        let t = f_to_c(self.tmp? as f64);
+        let td = f_to_c(self.dpt? as f64);
+
+        let pressure = 1013.25 * (1.0 - 6.8756e-6 * elevation).powf(5.2559);
+        let vapor = vapor_pressure(td);
+        let virtual_k = (t + 273.15) / (1.0 - (vapor / pressure) * (1.0 - 0.622));
+
+        let pressure_inhg = pressure * 0.029_53;
+        let virtual_r = virtual_k * 1.8;
+        Some(145_442.16 * (1.0 - (17.326 * pressure_inhg / virtual_r).powf(0.235)))
+    }
+}
+
+impl MOSEntry {
+    /// NWS wind chill in °F, defined for temperatures at or below 50°F with winds above
+    /// 3 mph
+    pub fn wind_chill(&self) -> Option<f64> {
+        let t = self.tmp? as f64;
+        let mph = self.wsp? as f64 * 1.150_78;
+        if t > 50.0 || mph <= 3.0 {
+            return None;
+        }
+
+        let v = mph.powf(0.16);
+        Some(35.74 + 0.6215 * t - 35.75 * v + 0.4275 * t * v)
+    }
+
+    /// NWS heat index in °F (Rothfusz regression with the NWS adjustments), defined once
+    /// the simple estimate reaches 80°F. Relative humidity comes from the dew point
+    pub fn heat_index(&self) -> Option<f64> {
+        let t = self.tmp? as f64;
+        let rh = 100.0 * vapor_pressure(f_to_c(self.dpt? as f64)) / vapor_pressure(f_to_c(t));
+
+        let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+        if (simple + t) / 2.0 < 80.0 {
+            return None;
+        }
+
+        let mut hi = -42.379 + 2.049_015_23 * t + 10.143_331_27 * rh
+            - 0.224_755_41 * t * rh
+            - 0.006_837_83 * t * t
+            - 0.054_817_17 * rh * rh
+            + 0.001_228_74 * t * t * rh
+            + 0.000_852_82 * t * rh * rh
+            - 0.000_001_99 * t * t * rh * rh;
+        if rh < 13.0 && t >= 80.0 && t <= 112.0 {
+            hi -= ((13.0 - rh) / 4.0) * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt();
+        } else if rh > 85.0 && t >= 80.0 && t <= 87.0 {
+            hi += ((rh - 85.0) / 10.0) * ((87.0 - t) / 5.0);
+        }
+
+        Some(hi)
+    }
+}
+
+impl MOS {
+    /// Density altitude at every valid time, using the station's elevation from the
+    /// embedded station table. `None` when the station isn't in it
+    pub fn density_altitudes(&self) -> Option<Vec<(DateTime<Utc>, f64)>> {
+        let station = stations::lookup(&self.meta.icao)?;
+        Some(
+            self.entries
+                .iter()
+                .filter_map(|entry| {
+                    entry
+                        .density_altitude(station.elevation)
+                        .map(|da| (entry.timestamp, da))
+                })
+                .collect(),
+        )
+    }
+}
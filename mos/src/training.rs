@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[cfg(feature = "parquet")]
+use parquet::file::writer::{FileWriter, ParquetWriter};
+
+#[cfg(feature = "parquet")]
+use super::error;
+#[cfg(feature = "parquet")]
+use super::export::parquet::{file_writer, write_columns, Column};
+use super::metar::Metar;
+use super::storage::StoredEntry;
+use super::verify::{fahrenheit, knots, nearest, pop_outcome};
+
+const COLUMNS: &[&str] = &[
+    "station",
+    "cycle",
+    "valid",
+    "lead_hours",
+    "tmp",
+    "dpt",
+    "wdr",
+    "wsp",
+    "p06",
+    "p12",
+    "cig",
+    "vis",
+    "cld",
+    "typ",
+    "observed_tmp",
+    "observed_dpt",
+    "observed_wdr",
+    "observed_wsp",
+    "observed_ceiling",
+    "observed_visibility",
+    "observed_precip",
+];
+
+#[cfg(feature = "parquet")]
+const SCHEMA: &str = "
+message training_row {
+    REQUIRED BYTE_ARRAY station (UTF8);
+    REQUIRED INT64 cycle (TIMESTAMP_MILLIS);
+    REQUIRED INT64 valid (TIMESTAMP_MILLIS);
+    OPTIONAL INT32 lead_hours;
+    OPTIONAL DOUBLE tmp;
+    OPTIONAL DOUBLE dpt;
+    OPTIONAL INT32 wdr;
+    OPTIONAL DOUBLE wsp;
+    OPTIONAL INT32 p06;
+    OPTIONAL INT32 p12;
+    OPTIONAL INT32 cig;
+    OPTIONAL INT32 vis;
+    OPTIONAL BYTE_ARRAY cld (UTF8);
+    OPTIONAL BYTE_ARRAY typ (UTF8);
+    OPTIONAL DOUBLE observed_tmp;
+    OPTIONAL DOUBLE observed_dpt;
+    OPTIONAL INT32 observed_wdr;
+    OPTIONAL DOUBLE observed_wsp;
+    OPTIONAL INT32 observed_ceiling;
+    OPTIONAL DOUBLE observed_visibility;
+    OPTIONAL BOOLEAN observed_precip;
+}
+";
+
+/// An archived entry's forecast next to what was observed, as the features and labels
+/// of a post-processing model. Temperatures are °F and speeds knots whatever the
+/// archive's units
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TrainingRow {
+    pub station: String,
+    pub cycle: DateTime<Utc>,
+    pub valid: DateTime<Utc>,
+    pub lead_hours: i64,
+    pub tmp: Option<f64>,
+    pub dpt: Option<f64>,
+    /// Tens of degrees, as in the bulletin
+    pub wdr: Option<isize>,
+    pub wsp: Option<f64>,
+    pub p06: Option<isize>,
+    pub p12: Option<isize>,
+    /// Ceiling category
+    pub cig: Option<isize>,
+    /// Visibility category
+    pub vis: Option<isize>,
+    pub cld: Option<String>,
+    pub typ: Option<String>,
+    pub observed_tmp: Option<f64>,
+    pub observed_dpt: Option<f64>,
+    /// Degrees true, None when calm or variable
+    pub observed_wdr: Option<isize>,
+    pub observed_wsp: Option<f64>,
+    /// Feet AGL
+    pub observed_ceiling: Option<u32>,
+    /// Statute miles
+    pub observed_visibility: Option<f64>,
+    /// Whether precipitation was reported in the P06 or P12 period, as `verify` scores it
+    pub observed_precip: Option<bool>,
+}
+
+/// Joins each archived entry with the observation nearest its valid time, matched like
+/// `verify::verify`. Entries with nothing observed to learn from are left out
+pub fn rows(archive: &[StoredEntry], observations: &[Metar]) -> Vec<TrainingRow> {
+    let mut rows = Vec::new();
+    for stored in archive {
+        let entry = &stored.entry;
+        let station_obs: Vec<&Metar> = observations
+            .iter()
+            .filter(|obs| obs.icao.eq_ignore_ascii_case(&stored.station))
+            .collect();
+        let obs = nearest(&station_obs, entry);
+        let precip = pop_outcome(&station_obs, entry).map(|(_, observed)| observed);
+        if obs.is_none() && precip.is_none() {
+            continue;
+        }
+
+        rows.push(TrainingRow {
+            station: stored.station.to_uppercase(),
+            cycle: stored.cycle,
+            valid: entry.timestamp,
+            lead_hours: stored.lead_hours(),
+            tmp: entry.tmp.map(|tmp| fahrenheit(tmp, entry.units)),
+            dpt: entry.dpt.map(|dpt| fahrenheit(dpt, entry.units)),
+            wdr: entry.wdr,
+            wsp: entry.wsp.map(|wsp| knots(wsp, entry.units)),
+            p06: entry.p06,
+            p12: entry.p12,
+            cig: entry.cig,
+            vis: entry.vis,
+            cld: entry.cld.clone(),
+            typ: entry.typ.clone(),
+            observed_tmp: obs.and_then(Metar::temperature_f),
+            observed_dpt: obs.and_then(Metar::dew_point_f),
+            observed_wdr: obs.and_then(|obs| obs.wind).and_then(|wind| wind.direction),
+            observed_wsp: obs.and_then(|obs| obs.wind).map(|wind| wind.speed as f64),
+            observed_ceiling: obs.and_then(Metar::ceiling),
+            observed_visibility: obs.and_then(|obs| obs.visibility),
+            observed_precip: precip,
+        });
+    }
+    rows
+}
+
+/// The rows as CSV with a header, missing values as empty cells
+pub fn to_csv(rows: &[TrainingRow]) -> String {
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for row in rows {
+        let cells = vec![
+            row.station.clone(),
+            row.cycle.to_rfc3339(),
+            row.valid.to_rfc3339(),
+            row.lead_hours.to_string(),
+            num(row.tmp),
+            num(row.dpt),
+            num(row.wdr),
+            num(row.wsp),
+            num(row.p06),
+            num(row.p12),
+            num(row.cig),
+            num(row.vis),
+            text(&row.cld),
+            text(&row.typ),
+            num(row.observed_tmp),
+            num(row.observed_dpt),
+            num(row.observed_wdr),
+            num(row.observed_wsp),
+            num(row.observed_ceiling),
+            num(row.observed_visibility),
+            num(row.observed_precip.map(|precip| precip as u8)),
+        ];
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Writes the rows as a single Parquet row group with the columns of `to_csv`
+#[cfg(feature = "parquet")]
+pub fn to_parquet<W: ParquetWriter + 'static>(
+    rows: &[TrainingRow],
+    writer: W,
+) -> Result<(), error::Error> {
+    let int = |f: &dyn Fn(&TrainingRow) -> Option<isize>| {
+        Column::Int(rows.iter().map(|row| f(row).map(|v| v as i32)).collect())
+    };
+    let float =
+        |f: &dyn Fn(&TrainingRow) -> Option<f64>| Column::Float(rows.iter().map(f).collect());
+    let string =
+        |f: &dyn Fn(&TrainingRow) -> Option<String>| Column::Str(rows.iter().map(f).collect());
+    let columns = vec![
+        Column::Label(rows.iter().map(|row| row.station.clone()).collect()),
+        Column::Time(
+            rows.iter()
+                .map(|row| row.cycle.timestamp_millis())
+                .collect(),
+        ),
+        Column::Time(
+            rows.iter()
+                .map(|row| row.valid.timestamp_millis())
+                .collect(),
+        ),
+        Column::Int(rows.iter().map(|row| Some(row.lead_hours as i32)).collect()),
+        float(&|row| row.tmp),
+        float(&|row| row.dpt),
+        int(&|row| row.wdr),
+        float(&|row| row.wsp),
+        int(&|row| row.p06),
+        int(&|row| row.p12),
+        int(&|row| row.cig),
+        int(&|row| row.vis),
+        string(&|row| row.cld.clone()),
+        string(&|row| row.typ.clone()),
+        float(&|row| row.observed_tmp),
+        float(&|row| row.observed_dpt),
+        int(&|row| row.observed_wdr),
+        float(&|row| row.observed_wsp),
+        int(&|row| row.observed_ceiling.map(|ceiling| ceiling as isize)),
+        float(&|row| row.observed_visibility),
+        Column::Bool(rows.iter().map(|row| row.observed_precip).collect()),
+    ];
+
+    let mut file = file_writer(writer, SCHEMA)?;
+    let mut row_group = file.next_row_group()?;
+    write_columns(row_group.as_mut(), columns)?;
+    file.close_row_group(row_group)?;
+    file.close()?;
+    Ok(())
+}
+
+fn num<T: ToString>(val: Option<T>) -> String {
+    val.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn text(val: &Option<String>) -> String {
+    match val {
+        Some(val) if val.contains(|c| c == ',' || c == '"' || c == '\n') => {
+            format!("\"{}\"", val.replace('"', "\"\""))
+        }
+        Some(val) => val.clone(),
+        None => String::new(),
+    }
+}
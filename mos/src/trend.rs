@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+use super::row::change;
+use super::{MOSEntry, MOS};
+
+/// How one element at a fixed valid time moved across successive cycles
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Trend {
+    pub element: &'static str,
+    pub valid: DateTime<Utc>,
+    /// Oldest cycle first. Cycles that forecast the valid time but left the element
+    /// blank keep their place as `None`
+    pub values: Vec<(DateTime<Utc>, Option<isize>)>,
+}
+
+impl Trend {
+    /// Newest value minus oldest value, skipping blanks at either end. WDR takes the
+    /// short way around, so 35 to 1 is 2
+    pub fn change(&self) -> Option<isize> {
+        let mut values = self.values.iter().filter_map(|(_, v)| *v);
+        let first = values.next()?;
+        Some(change(self.element, first, values.last().unwrap_or(first)))
+    }
+
+    /// Whether the last two runs disagree less than the first two did, i.e. the
+    /// guidance is settling on a value. `None` with fewer than three values
+    pub fn converging(&self) -> Option<bool> {
+        let values: Vec<isize> = self.values.iter().filter_map(|(_, v)| *v).collect();
+        if values.len() < 3 {
+            return None;
+        }
+        let step = |i: usize| change(self.element, values[i - 1], values[i]).abs();
+        let first = step(1);
+        let last = step(values.len() - 1);
+        Some(last < first)
+    }
+}
+
+/// Trends of every numeric element at `valid` across `cycles`, which can be in any
+/// order. Elements no cycle forecast are left out
+pub fn trends(cycles: &[MOS], valid: DateTime<Utc>) -> Vec<Trend> {
+    let mut cycles: Vec<(&MOS, &MOSEntry)> = cycles
+        .iter()
+        .filter_map(|mos| {
+            mos.entries
+                .iter()
+                .find(|entry| entry.timestamp == valid)
+                .map(|entry| (mos, entry))
+        })
+        .collect();
+    cycles.sort_by_key(|(mos, _)| mos.meta.timestamp);
+
+    let mut trends: Vec<Trend> = Vec::new();
+    for (mos, entry) in cycles {
+        for (i, (element, value)) in entry.numeric_elements().iter().enumerate() {
+            if trends.len() <= i {
+                trends.push(Trend {
+                    element: *element,
+                    valid,
+                    values: Vec::new(),
+                });
+            }
+            trends[i].values.push((mos.meta.timestamp, *value));
+        }
+    }
+
+    trends
+        .into_iter()
+        .filter(|trend| trend.values.iter().any(|(_, v)| v.is_some()))
+        .collect()
+}
+
+/// A single element's trend, by its MOS row name (e.g. "P06")
+pub fn trend(cycles: &[MOS], valid: DateTime<Utc>, element: &str) -> Option<Trend> {
+    trends(cycles, valid)
+        .into_iter()
+        .find(|trend| trend.element.eq_ignore_ascii_case(element))
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values
+            .iter()
+            .map(|(_, v)| v.map_or_else(|| String::from("-"), |v| v.to_string()))
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "{} {}: {} over the last {} runs",
+            self.valid.format("%a %HZ"),
+            self.element,
+            values.join(" → "),
+            values.len()
+        )
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::{error, MOSEntry, MOS};
+
+#[cfg(feature = "uom")]
+mod typed;
+
+/// Unit system of the temperature and wind speed fields. Bulletins are imperial: °F and
+/// knots. Metric is °C and km/h
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    Imperial,
+    Metric,
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Units::Imperial
+    }
+}
+
+impl Units {
+    pub fn is_imperial(&self) -> bool {
+        *self == Units::Imperial
+    }
+
+    /// "°F" or "°C"
+    pub fn temperature_symbol(&self) -> &'static str {
+        match self {
+            Units::Imperial => "°F",
+            Units::Metric => "°C",
+        }
+    }
+
+    /// "kt" or "km/h"
+    pub fn speed_symbol(&self) -> &'static str {
+        match self {
+            Units::Imperial => "kt",
+            Units::Metric => "km/h",
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "imperial" => Ok(Units::Imperial),
+            "metric" => Ok(Units::Metric),
+            _ => Err(error::Error::unknown("units", s)),
+        }
+    }
+}
+
+fn f_to_c(val: isize) -> isize {
+    ((val as f64 - 32.0) * 5.0 / 9.0).round() as isize
+}
+
+fn c_to_f(val: isize) -> isize {
+    (val as f64 * 9.0 / 5.0 + 32.0).round() as isize
+}
+
+fn kt_to_kmh(val: isize) -> isize {
+    (val as f64 * 1.852).round() as isize
+}
+
+fn kmh_to_kt(val: isize) -> isize {
+    (val as f64 / 1.852).round() as isize
+}
+
+impl MOS {
+    /// Copy with temperatures in °C and wind speeds in km/h, each rounded to the nearest
+    /// whole unit. The categorical elements (CIG, VIS, Q06/Q12, SNW) keep their category
+    /// codes, whose bounds are defined in imperial units. The derived helpers on
+    /// `MOSEntry` expect imperial values, so call them on the original
+    pub fn to_metric(&self) -> MOS {
+        self.in_units(Units::Metric)
+    }
+
+    /// Copy with temperatures in °F and wind speeds in knots. Converting a metric copy back
+    /// can be off by one from the bulletin because of the rounding
+    pub fn to_imperial(&self) -> MOS {
+        self.in_units(Units::Imperial)
+    }
+
+    /// `to_metric()` or `to_imperial()` by the units asked for
+    pub fn in_units(&self, units: Units) -> MOS {
+        let mut mos = self.clone();
+        if mos.units != units {
+            mos.units = units;
+            mos.entries = self
+                .entries
+                .iter()
+                .map(|entry| entry.converted(units))
+                .collect();
+        }
+
+        mos
+    }
+}
+
+impl MOSEntry {
+    fn converted(&self, units: Units) -> MOSEntry {
+        let mut entry = self.clone();
+        if entry.units == units {
+            return entry;
+        }
+        let (temp, speed): (fn(isize) -> isize, fn(isize) -> isize) = match units {
+            Units::Metric => (f_to_c, kt_to_kmh),
+            Units::Imperial => (c_to_f, kmh_to_kt),
+        };
+
+        entry.units = units;
+        entry.nx = entry.nx.map(temp);
+        entry.tmp = entry.tmp.map(temp);
+        entry.dpt = entry.dpt.map(temp);
+        entry.wind_chill = entry.wind_chill.map(temp);
+        entry.heat_index = entry.heat_index.map(temp);
+        entry.wsp = entry.wsp.map(speed);
+        entry
+    }
+}
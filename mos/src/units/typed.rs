@@ -0,0 +1,46 @@
+use uom::si::angle::degree;
+use uom::si::f64::{Angle, ThermodynamicTemperature, Velocity};
+use uom::si::thermodynamic_temperature::{degree_celsius, degree_fahrenheit};
+use uom::si::velocity::{kilometer_per_hour, knot};
+
+use super::super::MOSEntry;
+use super::Units;
+
+/// Unit-carrying views of the raw fields, so callers can't mix up °F and °C or knots and
+/// km/h. They follow the entry's `units`
+impl MOSEntry {
+    fn thermodynamic(&self, val: isize) -> ThermodynamicTemperature {
+        match self.units {
+            Units::Imperial => ThermodynamicTemperature::new::<degree_fahrenheit>(val as f64),
+            Units::Metric => ThermodynamicTemperature::new::<degree_celsius>(val as f64),
+        }
+    }
+
+    /// TMP
+    pub fn temperature(&self) -> Option<ThermodynamicTemperature> {
+        self.tmp.map(|tmp| self.thermodynamic(tmp))
+    }
+
+    /// DPT
+    pub fn dew_point(&self) -> Option<ThermodynamicTemperature> {
+        self.dpt.map(|dpt| self.thermodynamic(dpt))
+    }
+
+    /// N/X
+    pub fn extreme_temperature(&self) -> Option<ThermodynamicTemperature> {
+        self.nx.map(|nx| self.thermodynamic(nx))
+    }
+
+    /// WDR, bulletin tens of degrees true
+    pub fn wind_direction(&self) -> Option<Angle> {
+        self.wdr.map(|wdr| Angle::new::<degree>((wdr * 10) as f64))
+    }
+
+    /// WSP
+    pub fn wind_speed(&self) -> Option<Velocity> {
+        self.wsp.map(|wsp| match self.units {
+            Units::Imperial => Velocity::new::<knot>(wsp as f64),
+            Units::Metric => Velocity::new::<kilometer_per_hour>(wsp as f64),
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::Duration;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::metar::Metar;
+use super::storage::StoredEntry;
+use super::units::Units;
+use super::MOSEntry;
+
+/// How far an observation can be from a valid time and still verify it
+const MATCH_MINUTES: i64 = 30;
+
+/// Present weather that counts as measurable precipitation for PoP
+const PRECIP_CODES: &[&str] = &["DZ", "RA", "SN", "SG", "IC", "PL", "GR", "GS", "UP"];
+
+/// Error statistics for one station at one lead time
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Verification {
+    pub station: String,
+    /// Hours from the model cycle to the valid time
+    pub lead_hours: i64,
+    /// °F
+    pub temperature: Option<ErrorStats>,
+    /// Knots
+    pub wind_speed: Option<ErrorStats>,
+    /// P06 and P12 against whether precipitation was reported in the period
+    pub pop: Option<BrierScore>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ErrorStats {
+    pub count: usize,
+    /// Mean absolute error
+    pub mae: f64,
+    /// Mean forecast minus observed, positive when the guidance runs high
+    pub bias: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct BrierScore {
+    pub count: usize,
+    /// Mean squared difference between the probability and the 0/1 outcome. 0 is a
+    /// perfect forecast
+    pub score: f64,
+}
+
+#[derive(Default)]
+struct Errors {
+    errors: Vec<f64>,
+}
+
+impl Errors {
+    fn stats(&self) -> Option<ErrorStats> {
+        if self.errors.is_empty() {
+            return None;
+        }
+        let n = self.errors.len() as f64;
+        Some(ErrorStats {
+            count: self.errors.len(),
+            mae: self.errors.iter().map(|e| e.abs()).sum::<f64>() / n,
+            bias: self.errors.iter().sum::<f64>() / n,
+        })
+    }
+}
+
+#[derive(Default)]
+struct Group {
+    temperature: Errors,
+    wind_speed: Errors,
+    pop: Vec<f64>,
+}
+
+/// Pairs each archived entry with the observation nearest its valid time, within 30
+/// minutes, and groups the errors by station and lead time. PoP is scored against
+/// whether any observation in the entry's 6 or 12 hour period reported precipitation,
+/// which misses precipitation between reports and so only approximates the NWS
+/// definition of 0.01 in or more
+pub fn verify(archive: &[StoredEntry], observations: &[Metar]) -> Vec<Verification> {
+    let mut groups: BTreeMap<(String, i64), Group> = BTreeMap::new();
+
+    for stored in archive {
+        let entry = &stored.entry;
+        let station_obs: Vec<&Metar> = observations
+            .iter()
+            .filter(|obs| obs.icao.eq_ignore_ascii_case(&stored.station))
+            .collect();
+        let group = groups
+            .entry((stored.station.to_uppercase(), stored.lead_hours()))
+            .or_insert_with(Group::default);
+
+        if let Some(obs) = nearest(&station_obs, entry) {
+            if let (Some(tmp), Some(observed)) = (entry.tmp, obs.temperature_f()) {
+                group
+                    .temperature
+                    .errors
+                    .push(fahrenheit(tmp, entry.units) - observed);
+            }
+            if let (Some(wsp), Some(wind)) = (entry.wsp, obs.wind) {
+                group
+                    .wind_speed
+                    .errors
+                    .push(knots(wsp, entry.units) - wind.speed as f64);
+            }
+        }
+
+        if let Some((pop, observed)) = pop_outcome(&station_obs, entry) {
+            let outcome = if observed { 1.0 } else { 0.0 };
+            group.pop.push((pop as f64 / 100.0 - outcome).powi(2));
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((station, lead_hours), group)| Verification {
+            station,
+            lead_hours,
+            temperature: group.temperature.stats(),
+            wind_speed: group.wind_speed.stats(),
+            pop: if group.pop.is_empty() {
+                None
+            } else {
+                Some(BrierScore {
+                    count: group.pop.len(),
+                    score: group.pop.iter().sum::<f64>() / group.pop.len() as f64,
+                })
+            },
+        })
+        .collect()
+}
+
+/// The observation nearest the entry's valid time, within 30 minutes
+pub(crate) fn nearest<'a>(station_obs: &[&'a Metar], entry: &MOSEntry) -> Option<&'a Metar> {
+    station_obs
+        .iter()
+        .copied()
+        .filter(|obs| (obs.time - entry.timestamp).num_minutes().abs() <= MATCH_MINUTES)
+        .min_by_key(|obs| (obs.time - entry.timestamp).num_seconds().abs())
+}
+
+/// The entry's P06, or P12 without one, and whether any observation in its period
+/// reported precipitation. None without a PoP or without observations through the
+/// period, which leave nothing to score against
+pub(crate) fn pop_outcome(station_obs: &[&Metar], entry: &MOSEntry) -> Option<(isize, bool)> {
+    let (pop, hours) = match (entry.p06, entry.p12) {
+        (Some(p06), _) => (p06, 6),
+        (None, Some(p12)) => (p12, 12),
+        _ => return None,
+    };
+    let start = entry.timestamp - Duration::hours(hours);
+    let period: Vec<&&Metar> = station_obs
+        .iter()
+        .filter(|obs| obs.time > start && obs.time <= entry.timestamp)
+        .collect();
+    if period.is_empty() {
+        return None;
+    }
+    Some((pop, period.iter().any(|obs| precipitating(obs))))
+}
+
+fn precipitating(obs: &Metar) -> bool {
+    obs.weather
+        .iter()
+        .any(|wx| PRECIP_CODES.iter().any(|code| wx.contains(code)))
+}
+
+pub(crate) fn fahrenheit(tmp: isize, units: Units) -> f64 {
+    match units {
+        Units::Imperial => tmp as f64,
+        Units::Metric => tmp as f64 * 9.0 / 5.0 + 32.0,
+    }
+}
+
+pub(crate) fn knots(wsp: isize, units: Units) -> f64 {
+    match units {
+        Units::Imperial => wsp as f64,
+        Units::Metric => wsp as f64 / 1.852,
+    }
+}
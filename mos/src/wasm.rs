@@ -0,0 +1,38 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{ParseOptions, MOS};
+
+#[derive(Serialize)]
+struct ParseError {
+    code: &'static str,
+    message: String,
+}
+
+/// Parses a bulletin into the same JSON shape the backend serves. Errors are thrown as
+/// `{code, message}` objects
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<JsValue, JsValue> {
+    parse_with(text, false)
+}
+
+/// Like `parse`, but with `strict` failing on cells that can't be parsed instead of
+/// skipping them
+#[wasm_bindgen(js_name = parseWith)]
+pub fn parse_with(text: &str, strict: bool) -> Result<JsValue, JsValue> {
+    let opts = ParseOptions {
+        strict,
+        ..ParseOptions::default()
+    };
+    let mos = MOS::new_with(text, &opts).map_err(|err| {
+        to_js(&ParseError {
+            code: err.code(),
+            message: err.to_string(),
+        })
+    })?;
+    Ok(to_js(&mos))
+}
+
+fn to_js<T: Serialize>(val: &T) -> JsValue {
+    JsValue::from_serde(val).unwrap_or_else(|err| JsValue::from_str(&err.to_string()))
+}
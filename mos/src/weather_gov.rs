@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+use super::error;
+#[cfg(feature = "client")]
+use super::stations;
+use super::{MOSEntry, MOSMeta, MOS};
+
+#[derive(Deserialize)]
+struct Point {
+    properties: PointProperties,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointProperties {
+    grid_id: String,
+    grid_x: u32,
+    grid_y: u32,
+    forecast_hourly: String,
+}
+
+#[derive(Deserialize)]
+struct Hourly {
+    properties: HourlyProperties,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HourlyProperties {
+    update_time: DateTime<Utc>,
+    periods: Vec<Period>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Period {
+    start_time: DateTime<Utc>,
+    temperature: Option<f64>,
+    temperature_unit: Option<String>,
+    dewpoint: Option<Quantity>,
+    wind_speed: Option<String>,
+    wind_direction: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Quantity {
+    unit_code: String,
+    value: Option<f64>,
+}
+
+impl MOS {
+    /// Converts an api.weather.gov hourly forecast into MOS entries, one per hour, so it
+    /// can go anywhere a bulletin does. Temperatures come out in °F, wind in knots and
+    /// tens of degrees like WDR; the elements MOS has no equivalent for are left empty.
+    /// `name` goes in `meta.icao`
+    pub fn from_weather_gov(name: &str, json: &str) -> Result<MOS, error::Error> {
+        let hourly: Hourly = serde_json::from_str(json)?;
+        let entries = hourly
+            .properties
+            .periods
+            .iter()
+            .map(|period| {
+                let (wdr, wsp) = wind(period);
+                MOSEntry {
+                    timestamp: period.start_time,
+                    tmp: period.temperature.map(|t| {
+                        match period.temperature_unit.as_deref() {
+                            Some("C") => t * 9.0 / 5.0 + 32.0,
+                            _ => t,
+                        }
+                        .round() as isize
+                    }),
+                    dpt: period.dewpoint.as_ref().and_then(fahrenheit),
+                    wdr,
+                    wsp,
+                    ..MOSEntry::default()
+                }
+            })
+            .collect();
+
+        Ok(MOS {
+            meta: MOSMeta {
+                icao: name.to_uppercase(),
+                timestamp: hourly.properties.update_time,
+            },
+            entries,
+            raw: json.to_string(),
+            ..MOS::default()
+        })
+    }
+}
+
+fn fahrenheit(quantity: &Quantity) -> Option<isize> {
+    let value = quantity.value?;
+    let f = if quantity.unit_code.ends_with("degC") {
+        value * 9.0 / 5.0 + 32.0
+    } else {
+        value
+    };
+    Some(f.round() as isize)
+}
+
+/// "NW" and "10 mph" or "5 to 10 mph" to WDR and WSP, taking the top of a range
+fn wind(period: &Period) -> (Option<isize>, Option<isize>) {
+    let mph = period.wind_speed.as_deref().and_then(|speed| {
+        speed
+            .split_whitespace()
+            .filter_map(|word| word.parse::<f64>().ok())
+            .last()
+    });
+    let wsp = mph.map(|mph| (mph * 0.868_976).round() as isize);
+
+    let wdr = match wsp {
+        Some(0) => Some(0),
+        _ => period
+            .wind_direction
+            .as_deref()
+            .and_then(compass_degrees)
+            .map(|degrees| match (degrees / 10.0).round() as isize {
+                0 => 36,
+                tens => tens,
+            }),
+    };
+    (wdr, wsp)
+}
+
+fn compass_degrees(point: &str) -> Option<f64> {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    POINTS
+        .iter()
+        .position(|p| *p == point)
+        .map(|idx| idx as f64 * 22.5)
+}
+
+/// Hourly forecast for a point from api.weather.gov
+#[cfg(feature = "client")]
+pub fn hourly(lat: f64, lon: f64) -> Result<MOS, error::Error> {
+    fetch_hourly(client::default_transport(), lat, lon)
+}
+
+/// Hourly forecast at a station in the embedded table, named after the station
+#[cfg(feature = "client")]
+pub fn hourly_for(icao: &str) -> Result<MOS, error::Error> {
+    fetch_hourly_for(client::default_transport(), icao)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch_hourly_for<T: Transport + ?Sized>(
+    transport: &T,
+    icao: &str,
+) -> Result<MOS, error::Error> {
+    let station =
+        stations::lookup(icao).ok_or_else(|| error::Error::UnknownStation(icao.to_uppercase()))?;
+    let mut mos = fetch_hourly(transport, station.lat, station.lon)?;
+    mos.meta.icao = station.icao.to_string();
+    Ok(mos)
+}
+
+/// Looks the point up to find its forecast grid, then fetches the grid's hourly
+/// forecast. `meta.icao` is the grid, e.g. "BOX/71,90"
+#[cfg(feature = "client")]
+pub fn fetch_hourly<T: Transport + ?Sized>(
+    transport: &T,
+    lat: f64,
+    lon: f64,
+) -> Result<MOS, error::Error> {
+    let point: Point = serde_json::from_str(&get_json(
+        transport,
+        &format!("https://api.weather.gov/points/{:.4},{:.4}", lat, lon),
+    )?)?;
+    let grid = point.properties;
+
+    let json = get_json(transport, &grid.forecast_hourly)?;
+    MOS::from_weather_gov(
+        &format!("{}/{},{}", grid.grid_id, grid.grid_x, grid.grid_y),
+        &json,
+    )
+}
+
+#[cfg(feature = "client")]
+fn get_json<T: Transport + ?Sized>(transport: &T, url: &str) -> Result<String, error::Error> {
+    let res = transport.get(url)?;
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "api.weather.gov",
+            status: res.status,
+        });
+    }
+    Ok(res.body)
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::window::Window;
+use super::{MOSEntry, MOS};
+
+/// Wind relative to a runway, in knots
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct WindComponents {
+    /// Positive for a headwind, negative for a tailwind
+    pub headwind: f64,
+    /// Positive for wind from the right of the runway, negative from the left
+    pub crosswind: f64,
+}
+
+impl MOSEntry {
+    /// Resolves the forecast wind against a runway heading in degrees. MOS wind
+    /// directions are true, so pass the runway's true heading rather than its number
+    pub fn wind_components(&self, runway_heading: f64) -> Option<WindComponents> {
+        let wsp = self.wsp? as f64;
+        let wdr = (self.wdr? * 10) as f64;
+        let angle = (wdr - runway_heading).to_radians();
+
+        Some(WindComponents {
+            headwind: wsp * angle.cos(),
+            crosswind: wsp * angle.sin(),
+        })
+    }
+}
+
+impl MOS {
+    /// Strongest crosswind (either side) on the runway forecast within the window, with
+    /// its valid time
+    pub fn max_crosswind(
+        &self,
+        runway_heading: f64,
+        window: &Window,
+    ) -> Option<(DateTime<Utc>, WindComponents)> {
+        self.entries
+            .iter()
+            .filter(|entry| window.contains(entry.timestamp))
+            .filter_map(|entry| {
+                entry
+                    .wind_components(runway_heading)
+                    .map(|components| (entry.timestamp, components))
+            })
+            .fold(None, |max, (ts, components)| match max {
+                Some((_, best)) if best.crosswind.abs() >= components.crosswind.abs() => max,
+                _ => Some((ts, components)),
+            })
+    }
+}
+
+/// Signed shortest turn from one direction to another in degrees, -180 to 180, so
+/// 350° to 10° is 20
+pub(crate) fn turn(from: isize, to: isize) -> isize {
+    let delta = (to - from).rem_euclid(360);
+    if delta > 180 {
+        delta - 360
+    } else {
+        delta
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A span of valid times, start inclusive and end exclusive
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Window {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Window {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Window {
+        Window { start, end }
+    }
+
+    pub fn contains(&self, ts: DateTime<Utc>) -> bool {
+        ts >= self.start && ts < self.end
+    }
+}
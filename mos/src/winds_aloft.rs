@@ -0,0 +1,344 @@
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use super::client::{self, Transport};
+use super::error;
+use super::metar;
+use super::stations;
+
+static BASED_ON_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"DATA BASED ON (\d{6}Z)").unwrap());
+static VALID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"VALID (\d{6}Z)\s+FOR USE (\d{2})(\d{2})-(\d{2})(\d{2})Z").unwrap());
+static CELL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{2})(\d{2})(?:([+-]?)(\d{2}))?$").unwrap());
+
+/// An FB winds and temperatures aloft bulletin
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct WindsAloft {
+    pub based_on: DateTime<Utc>,
+    pub valid: DateTime<Utc>,
+    pub use_from: DateTime<Utc>,
+    pub use_to: DateTime<Utc>,
+    /// Forecast levels in feet MSL, in the order of the FT line
+    pub levels: Vec<u32>,
+    pub stations: Vec<StationWinds>,
+}
+
+/// One FD station's row of the bulletin
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StationWinds {
+    pub id: String,
+    /// Levels with a forecast. Levels within 1,500 ft of the station or below it are
+    /// left blank in the bulletin and missing here
+    pub winds: Vec<LevelWind>,
+}
+
+/// Wind and temperature at one level
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LevelWind {
+    /// Feet MSL
+    pub altitude: u32,
+    /// Degrees true, None for light and variable
+    pub direction: Option<isize>,
+    /// Knots
+    pub speed: isize,
+    /// °C, None at 3,000 ft where the bulletin has no temperature
+    pub temperature: Option<isize>,
+}
+
+/// FD station locations, used to pick the site nearest an airport
+static FD_STATIONS: &[(&str, f64, f64)] = &[
+    ("ABI", 32.48, -99.86),
+    ("ABQ", 35.04, -106.62),
+    ("ACK", 41.28, -70.03),
+    ("ACY", 39.45, -74.58),
+    ("AGC", 40.35, -79.93),
+    ("ALB", 42.75, -73.80),
+    ("ALS", 37.35, -105.82),
+    ("AMA", 35.22, -101.71),
+    ("ATL", 33.63, -84.44),
+    ("AVP", 41.34, -75.72),
+    ("BDL", 41.94, -72.69),
+    ("BGR", 44.81, -68.83),
+    ("BHM", 33.56, -86.75),
+    ("BIL", 45.81, -108.54),
+    ("BNA", 36.12, -86.68),
+    ("BOI", 43.57, -116.24),
+    ("BOS", 42.36, -71.01),
+    ("BUF", 42.94, -78.73),
+    ("CAR", 46.87, -68.02),
+    ("CHS", 32.90, -80.04),
+    ("CLE", 41.41, -81.85),
+    ("CMH", 39.99, -82.88),
+    ("CRP", 27.77, -97.50),
+    ("CRW", 38.37, -81.59),
+    ("CVG", 39.05, -84.67),
+    ("DAL", 32.85, -96.85),
+    ("DEN", 39.86, -104.67),
+    ("DLH", 46.84, -92.19),
+    ("DSM", 41.53, -93.66),
+    ("EKN", 38.89, -79.86),
+    ("ELP", 31.81, -106.38),
+    ("EMI", 39.50, -76.98),
+    ("EYW", 24.56, -81.76),
+    ("FAT", 36.78, -119.72),
+    ("FLO", 34.19, -79.72),
+    ("GEG", 47.62, -117.53),
+    ("GJT", 39.12, -108.53),
+    ("GLD", 39.37, -101.70),
+    ("GRB", 44.48, -88.13),
+    ("GSP", 34.90, -82.22),
+    ("HAT", 35.23, -75.62),
+    ("HOU", 29.65, -95.28),
+    ("ICT", 37.65, -97.43),
+    ("IND", 39.72, -86.29),
+    ("JAX", 30.49, -81.69),
+    ("JFK", 40.64, -73.78),
+    ("JOT", 41.52, -88.18),
+    ("LAS", 36.08, -115.15),
+    ("LBB", 33.66, -101.82),
+    ("MEM", 35.04, -89.98),
+    ("MIA", 25.80, -80.29),
+    ("MKC", 39.12, -94.59),
+    ("MLB", 28.10, -80.65),
+    ("MSP", 44.88, -93.22),
+    ("MSY", 29.99, -90.26),
+    ("OKC", 35.39, -97.60),
+    ("OMA", 41.30, -95.89),
+    ("ORF", 36.89, -76.20),
+    ("PDX", 45.59, -122.60),
+    ("PHX", 33.43, -112.01),
+    ("PIE", 27.91, -82.69),
+    ("PLB", 44.69, -73.52),
+    ("PUB", 38.29, -104.50),
+    ("PWM", 43.65, -70.31),
+    ("RDU", 35.88, -78.79),
+    ("RIC", 37.51, -77.32),
+    ("RKS", 41.59, -109.07),
+    ("RNO", 39.50, -119.77),
+    ("SAC", 38.51, -121.49),
+    ("SAN", 32.73, -117.19),
+    ("SAT", 29.53, -98.47),
+    ("SAV", 32.13, -81.20),
+    ("SBA", 34.43, -119.84),
+    ("SEA", 47.45, -122.31),
+    ("SFO", 37.62, -122.38),
+    ("SLC", 40.79, -111.98),
+    ("STL", 38.75, -90.37),
+    ("SYR", 43.11, -76.11),
+    ("TLH", 30.40, -84.35),
+    ("TRI", 36.48, -82.40),
+    ("TUL", 36.20, -95.89),
+    ("TUS", 32.12, -110.94),
+    ("TYS", 35.81, -83.99),
+];
+
+impl WindsAloft {
+    /// Parses an FB bulletin, taking the month and year of its timestamps from the
+    /// current time
+    pub fn parse(raw: &str) -> Result<WindsAloft, error::Error> {
+        WindsAloft::parse_at(raw, Utc::now())
+    }
+
+    /// Parses an FB bulletin issued at or shortly before `now`
+    pub fn parse_at(raw: &str, now: DateTime<Utc>) -> Result<WindsAloft, error::Error> {
+        let based_on = BASED_ON_RE
+            .captures(raw)
+            .and_then(|caps| metar::observation_time(&caps[1], now))
+            .ok_or_else(|| error::Error::parse("header", "no DATA BASED ON time"))?;
+        let caps = VALID_RE
+            .captures(raw)
+            .ok_or_else(|| error::Error::parse("header", "no VALID time"))?;
+        let valid = metar::observation_time(&caps[1], now + Duration::days(2))
+            .filter(|valid| *valid >= based_on)
+            .ok_or_else(|| error::Error::parse("header", "bad VALID time"))?;
+        let hhmm = |hh: &str, mm: &str| {
+            let (hh, mm) = (hh.parse::<u32>().ok()?, mm.parse::<u32>().ok()?);
+            match hh {
+                24 => Some(valid.date().and_hms(0, 0, 0) + Duration::days(1)),
+                _ => valid.date().and_hms_opt(hh, mm, 0),
+            }
+        };
+        let (use_from, use_to) = match (hhmm(&caps[2], &caps[3]), hhmm(&caps[4], &caps[5])) {
+            (Some(from), Some(to)) => (
+                if from > valid {
+                    from - Duration::days(1)
+                } else {
+                    from
+                },
+                if to < valid {
+                    to + Duration::days(1)
+                } else {
+                    to
+                },
+            ),
+            _ => return Err(error::Error::parse("header", "bad FOR USE period")),
+        };
+
+        let mut lines = raw.lines().skip_while(|line| !line.starts_with("FT"));
+        let header = lines
+            .next()
+            .ok_or_else(|| error::Error::parse("FT", "no FT line in the bulletin"))?;
+        let columns: Vec<(usize, u32)> = tokens(header)
+            .into_iter()
+            .skip(1)
+            .filter_map(|(end, level)| level.parse().ok().map(|level| (end, level)))
+            .collect();
+        if columns.is_empty() {
+            return Err(error::Error::parse("FT", "no levels on the FT line"));
+        }
+
+        let mut stations = Vec::new();
+        for line in lines {
+            let mut cells = tokens(line).into_iter();
+            let id = match cells.next() {
+                Some((_, id)) if id.len() == 3 && id.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    id
+                }
+                _ => continue,
+            };
+
+            let winds = cells
+                .filter_map(|(end, cell)| {
+                    // Cells are right-aligned under their level
+                    let &(_, altitude) = columns
+                        .iter()
+                        .min_by_key(|(col, _)| (*col as isize - end as isize).abs())?;
+                    decode(cell, altitude)
+                })
+                .collect();
+            stations.push(StationWinds {
+                id: id.to_string(),
+                winds,
+            });
+        }
+
+        Ok(WindsAloft {
+            based_on,
+            valid,
+            use_from,
+            use_to,
+            levels: columns.iter().map(|&(_, level)| level).collect(),
+            stations,
+        })
+    }
+
+    pub fn station(&self, id: &str) -> Option<&StationWinds> {
+        self.stations
+            .iter()
+            .find(|station| station.id.eq_ignore_ascii_case(id))
+    }
+
+    /// The bulletin's nearest FD station to a point
+    pub fn nearest(&self, lat: f64, lon: f64) -> Option<&StationWinds> {
+        FD_STATIONS
+            .iter()
+            .filter_map(|&(id, fd_lat, fd_lon)| {
+                self.station(id)
+                    .map(|station| (stations::distance_nm(lat, lon, fd_lat, fd_lon), station))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, station)| station)
+    }
+
+    /// The nearest FD station to an airport in the embedded station table
+    pub fn for_airport(&self, icao: &str) -> Option<&StationWinds> {
+        let station = stations::lookup(icao)?;
+        self.nearest(station.lat, station.lon)
+    }
+}
+
+impl StationWinds {
+    /// The forecast at a level, if the bulletin has one for this station
+    pub fn at(&self, altitude: u32) -> Option<&LevelWind> {
+        self.winds.iter().find(|wind| wind.altitude == altitude)
+    }
+}
+
+/// Whitespace-separated tokens with the byte offset of their last character
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(from)) => {
+                out.push((i - 1, &line[from..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Decodes DDSS, DDSS±TT or DDSSTT. Directions of 51–86 carry 100 kt on the speed, 9900
+/// is light and variable, and a temperature without a sign is below zero
+fn decode(cell: &str, altitude: u32) -> Option<LevelWind> {
+    let caps = CELL_RE.captures(cell)?;
+    let mut dd = caps[1].parse::<isize>().ok()?;
+    let mut speed = caps[2].parse::<isize>().ok()?;
+    let temperature = caps.get(4).and_then(|tt| {
+        let tt = tt.as_str().parse::<isize>().ok()?;
+        match caps.get(3).map(|sign| sign.as_str()) {
+            Some("+") => Some(tt),
+            _ => Some(-tt),
+        }
+    });
+
+    let direction = if dd == 99 {
+        speed = 0;
+        None
+    } else {
+        if dd >= 51 {
+            dd -= 50;
+            speed += 100;
+        }
+        Some(dd * 10)
+    };
+
+    Some(LevelWind {
+        altitude,
+        direction,
+        speed,
+        temperature,
+    })
+}
+
+/// Latest low-level FB bulletin for the contiguous US, for the 6, 12 or 24 hour
+/// forecast
+#[cfg(feature = "client")]
+pub fn get(forecast_hours: u32) -> Result<WindsAloft, error::Error> {
+    fetch(client::default_transport(), forecast_hours)
+}
+
+#[cfg(feature = "client")]
+pub fn fetch<T: Transport + ?Sized>(
+    transport: &T,
+    forecast_hours: u32,
+) -> Result<WindsAloft, error::Error> {
+    if ![6, 12, 24].contains(&forecast_hours) {
+        return Err(error::Error::unknown(
+            "winds aloft forecast",
+            &forecast_hours.to_string(),
+        ));
+    }
+    let res = transport.get(&format!(
+        "https://aviationweather.gov/windtemp/data?level=low&fcst={:02}&region=all&layout=off",
+        forecast_hours
+    ))?;
+    if !(200..300).contains(&res.status) {
+        return Err(error::Error::Status {
+            service: "aviationweather.gov",
+            status: res.status,
+        });
+    }
+
+    WindsAloft::parse(&client::extract_pre(&res.body)?)
+}
@@ -0,0 +1,76 @@
+use mos::afd::{self, Discussion};
+use mos::testing::{self, FnTransport};
+
+const AFD: &str = "\
+000
+FXUS61 KBOX 121925
+AFDBOX
+
+Area Forecast Discussion
+National Weather Service Boston/Norton MA
+325 PM EDT Sun Apr 12 2020
+
+.SYNOPSIS...
+A strong storm brings heavy rain and damaging winds late tonight
+into Monday.
+
+&&
+
+.NEAR TERM /THROUGH TONIGHT/...
+Rain overspreads the region this evening.
+
+Winds ramp up after midnight.
+
+&&
+
+.AVIATION /20Z TUESDAY THROUGH SATURDAY/...Moderate confidence.
+IFR in rain and fog overnight with LLWS.
+
+&&
+
+.BOX WATCHES/WARNINGS/ADVISORIES...
+MA...Wind Advisory until 8 PM EDT Monday for MAZ005>007.
+
+$$
+";
+
+#[test]
+fn splits_sections() {
+    let afd = Discussion::parse("box", AFD);
+    assert_eq!(afd.office, "BOX");
+    assert_eq!(afd.sections.len(), 4);
+
+    let synopsis = afd.synopsis().unwrap();
+    assert_eq!(synopsis.period, None);
+    assert!(synopsis.body.starts_with("A strong storm"));
+    assert!(synopsis.body.ends_with("into Monday."));
+
+    let near_term = afd.near_term().unwrap();
+    assert_eq!(near_term.period.as_deref(), Some("THROUGH TONIGHT"));
+    assert_eq!(
+        near_term.body,
+        "Rain overspreads the region this evening.\n\nWinds ramp up after midnight."
+    );
+
+    let aviation = afd.aviation().unwrap();
+    assert_eq!(
+        aviation.period.as_deref(),
+        Some("20Z TUESDAY THROUGH SATURDAY")
+    );
+    assert!(aviation.body.starts_with("Moderate confidence.\nIFR"));
+
+    let headlines = afd.section("BOX WATCHES").unwrap();
+    assert_eq!(headlines.title, "BOX WATCHES/WARNINGS/ADVISORIES");
+    assert_eq!(headlines.period, None);
+}
+
+#[test]
+fn fetches_the_covering_office() {
+    let transport = FnTransport(|url: &str| {
+        assert!(url.contains("issuedby=BOX&product=AFD"));
+        Ok(testing::page(AFD))
+    });
+    let afd = afd::fetch(&transport, "kfit").unwrap();
+    assert_eq!(afd.office, "BOX");
+    assert!(afd.aviation().is_some());
+}
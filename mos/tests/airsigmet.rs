@@ -0,0 +1,85 @@
+use chrono::{TimeZone, Utc};
+
+use mos::airsigmet::{self, Advisory, AdvisoryKind, BoundingBox, Hazard};
+
+const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<response version="1.2">
+<data num_results="2">
+<AIRSIGMET>
+<raw_text>WAUS41 KKCI 121445 BOSZ WA 121445 AIRMET ZULU UPDT 2 FOR ICE AND FRZLVL VALID UNTIL 122100</raw_text>
+<valid_time_from>2020-04-12T14:45:00Z</valid_time_from>
+<valid_time_to>2020-04-12T21:00:00Z</valid_time_to>
+<altitude min_ft_msl="4000" max_ft_msl="16000"/>
+<hazard type="ICE" severity="LT-MOD"/>
+<airsigmet_type>AIRMET</airsigmet_type>
+<area num_points="5">
+<point><longitude>-73.5</longitude><latitude>41.0</latitude></point>
+<point><longitude>-73.5</longitude><latitude>44.0</latitude></point>
+<point><longitude>-69.5</longitude><latitude>44.0</latitude></point>
+<point><longitude>-69.5</longitude><latitude>41.0</latitude></point>
+<point><longitude>-73.5</longitude><latitude>41.0</latitude></point>
+</area>
+</AIRSIGMET>
+<AIRSIGMET>
+<raw_text>WSUS32 KKCI 121655 SIGC CONVECTIVE SIGMET 12C VALID UNTIL 1855Z</raw_text>
+<valid_time_from>2020-04-12T16:55:00Z</valid_time_from>
+<valid_time_to>2020-04-12T18:55:00Z</valid_time_to>
+<altitude max_ft_msl="45000"/>
+<hazard type="CONVECTIVE" severity=""/>
+<airsigmet_type>SIGMET</airsigmet_type>
+<area num_points="4">
+<point><longitude>-97.0</longitude><latitude>33.5</latitude></point>
+<point><longitude>-95.5</longitude><latitude>32.0</latitude></point>
+<point><longitude>-98.0</longitude><latitude>32.0</latitude></point>
+<point><longitude>-97.0</longitude><latitude>33.5</latitude></point>
+</area>
+</AIRSIGMET>
+</data>
+</response>
+"#;
+
+#[test]
+fn parses_advisories() {
+    let advisories = Advisory::from_xml(XML).unwrap();
+    assert_eq!(advisories.len(), 2);
+
+    let icing = &advisories[0];
+    assert_eq!(icing.kind, AdvisoryKind::Airmet);
+    assert_eq!(icing.hazard, Hazard::Icing);
+    assert_eq!(icing.severity.as_deref(), Some("LT-MOD"));
+    assert_eq!(icing.valid_to, Utc.ymd(2020, 4, 12).and_hms(21, 0, 0));
+    assert_eq!(
+        (icing.min_altitude, icing.max_altitude),
+        (Some(4000), Some(16000))
+    );
+    assert_eq!(icing.area.len(), 5);
+    assert!(icing.is_active(Utc.ymd(2020, 4, 12).and_hms(15, 0, 0)));
+
+    let convective = &advisories[1];
+    assert_eq!(convective.kind, AdvisoryKind::Sigmet);
+    assert_eq!(convective.hazard, Hazard::Convective);
+    assert_eq!(convective.severity, None);
+    assert_eq!(convective.min_altitude, None);
+}
+
+#[test]
+fn filters_by_location() {
+    let advisories = Advisory::from_xml(XML).unwrap();
+
+    let kbos: Vec<Hazard> = airsigmet::for_station(&advisories, "KBOS")
+        .into_iter()
+        .map(|advisory| advisory.hazard.clone())
+        .collect();
+    assert_eq!(kbos, vec![Hazard::Icing]);
+    assert!(airsigmet::for_station(&advisories, "KMIA").is_empty());
+
+    let texas = BoundingBox {
+        min_lat: 31.0,
+        min_lon: -99.0,
+        max_lat: 34.0,
+        max_lon: -96.0,
+    };
+    let found = airsigmet::in_box(&advisories, &texas);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].hazard, Hazard::Convective);
+}
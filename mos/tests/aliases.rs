@@ -0,0 +1,23 @@
+use mos::stations::{Alias, Aliases};
+
+#[test]
+fn resolves_aliases() {
+    let mut aliases =
+        Aliases::from_json(r#"{"home": "kfit", "Work-Route": ["KFIT", "KBED", "KBOS"]}"#).unwrap();
+    aliases.insert("Office", Alias::Station(String::from("KBOS")));
+
+    assert_eq!(aliases.resolve("HOME"), vec!["KFIT"]);
+    assert_eq!(aliases.resolve("work-route"), vec!["KFIT", "KBED", "KBOS"]);
+    assert_eq!(aliases.resolve("office"), vec!["KBOS"]);
+    assert_eq!(aliases.resolve("KORH"), vec!["KORH"]);
+}
+
+#[test]
+fn aliases_for_no_stations() {
+    let err = Aliases::from_json(r#"{"home": "KFIT", "nowhere": []}"#).unwrap_err();
+    assert!(err.to_string().contains("at least one station"), "{}", err);
+
+    let mut aliases = Aliases::default();
+    aliases.insert("nowhere", Alias::Stations(Vec::new()));
+    assert_eq!(aliases.resolve("nowhere"), vec!["nowhere"]);
+}
@@ -0,0 +1,134 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mos::batch::{Batch, Limits, Throttle};
+use mos::client::{Response, Transport};
+use mos::testing::{self, FnTransport};
+use mos::Product;
+
+/// Serves the collective fixture's bulletins, keeping track of the most requests it had
+/// in flight at once
+fn collective(in_flight: Arc<AtomicUsize>, most: Arc<Mutex<usize>>) -> impl Transport {
+    let bulletins = testing::bulletins("mav_collective.txt");
+    FnTransport(move |url: &str| {
+        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        {
+            let mut most = most.lock().unwrap();
+            *most = (*most).max(now);
+        }
+        thread::sleep(Duration::from_millis(20));
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        let icao = url.rsplit('=').next().unwrap_or("");
+        match bulletins
+            .iter()
+            .find(|bulletin| bulletin.trim_start().starts_with(icao))
+        {
+            Some(bulletin) => Ok(testing::page(bulletin)),
+            None => Ok(Response {
+                status: 404,
+                body: String::new(),
+            }),
+        }
+    })
+}
+
+#[test]
+fn fetches_in_order_within_the_limits() {
+    let (in_flight, most) = (Arc::new(AtomicUsize::new(0)), Arc::new(Mutex::new(0)));
+    let limits = Limits {
+        max_in_flight: 4,
+        per_host: 2,
+        ..Limits::default()
+    };
+    let batch = Batch::new(collective(in_flight, Arc::clone(&most)), limits);
+
+    let stations: Vec<String> = ["KFIT", "KBED", "EGLL", "KBOS", "KORH", "KPVD"]
+        .iter()
+        .map(|icao| icao.to_string())
+        .collect();
+    let results = batch.fetch_all(&stations, Product::Mav);
+
+    assert_eq!(results.len(), stations.len());
+    for (icao, res) in stations.iter().zip(&results) {
+        match res {
+            Ok(mos) => assert_eq!(&mos.meta.icao, icao),
+            Err(err) => {
+                assert_eq!(icao, "EGLL");
+                assert_eq!(err.code(), "E_STATION_NOT_FOUND");
+            }
+        }
+    }
+    assert!(*most.lock().unwrap() <= 2);
+}
+
+#[test]
+fn spaces_requests_to_a_host() {
+    let starts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&starts);
+    let transport = FnTransport(move |_: &str| {
+        recorded.lock().unwrap().push(Instant::now());
+        Ok(Response {
+            status: 404,
+            body: String::new(),
+        })
+    });
+    let throttle = Arc::new(Throttle::new(
+        transport,
+        Limits {
+            delay: Duration::from_millis(30),
+            ..Limits::default()
+        },
+    ));
+
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let throttle = Arc::clone(&throttle);
+            thread::spawn(move || throttle.get("https://www.nws.noaa.gov/cgi-bin/mos/getmav.pl"))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap().unwrap();
+    }
+
+    let mut starts = starts.lock().unwrap().clone();
+    starts.sort();
+    for pair in starts.windows(2) {
+        assert!(pair[1] - pair[0] >= Duration::from_millis(30));
+    }
+}
+
+#[test]
+fn a_panicking_request_frees_its_slot() {
+    let calls = AtomicUsize::new(0);
+    let transport = FnTransport(move |_: &str| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("transport bug");
+        }
+        Ok(Response {
+            status: 404,
+            body: String::new(),
+        })
+    });
+    let throttle = Arc::new(Throttle::new(
+        transport,
+        Limits {
+            max_in_flight: 1,
+            per_host: 1,
+            ..Limits::default()
+        },
+    ));
+    let url = "https://www.nws.noaa.gov/cgi-bin/mos/getmav.pl";
+    let panicked = panic::catch_unwind(AssertUnwindSafe(|| throttle.get(url)));
+    assert!(panicked.is_err());
+
+    // With the slot still taken this would block forever
+    let (done, finished) = mpsc::channel();
+    let shared = Arc::clone(&throttle);
+    thread::spawn(move || done.send(shared.get(url).map(|res| res.status)));
+    let status = finished.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(status.unwrap(), 404);
+}
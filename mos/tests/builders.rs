@@ -0,0 +1,29 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use mos::{MOSEntry, MOS};
+
+#[test]
+fn builds_synthetic_forecasts() {
+    let cycle = Utc.ymd(2020, 4, 12).and_hms(12, 0, 0);
+    let mos = MOS::from_entries(
+        "kfit",
+        cycle,
+        vec![
+            MOSEntry::at(cycle + Duration::hours(9)).with_tmp(33),
+            MOSEntry::at(cycle + Duration::hours(6))
+                .with_tmp(35)
+                .with_wind(25, 1)
+                .with_cld("FW"),
+        ],
+    );
+
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.entries[0].timestamp, cycle + Duration::hours(6));
+    assert_eq!(mos.entries[0].wsp, Some(1));
+    assert_eq!(mos.entries[1].wsp, None);
+    assert_eq!(mos.clone(), mos);
+    assert_ne!(
+        mos.entries[0],
+        MOSEntry::at(cycle + Duration::hours(6)).with_tmp(35)
+    );
+}
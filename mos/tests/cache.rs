@@ -0,0 +1,74 @@
+use chrono::{Duration, TimeZone, Utc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mos::cache::{self, Cache, MemoryCache};
+use mos::client;
+use mos::testing::FnTransport;
+use mos::{testing, Product};
+
+#[test]
+fn expects_the_cycle_published_by_now() {
+    let now = Utc.ymd(2020, 4, 12).and_hms(17, 30, 0);
+    assert_eq!(
+        cache::expected_cycle(Product::Mav, now),
+        Utc.ymd(2020, 4, 12).and_hms(12, 0, 0)
+    );
+    assert_eq!(
+        cache::expected_cycle(Product::Mav, Utc.ymd(2020, 4, 13).and_hms(2, 0, 0)),
+        Utc.ymd(2020, 4, 12).and_hms(18, 0, 0)
+    );
+    assert_eq!(
+        cache::expected_cycle(Product::Mex, now),
+        Utc.ymd(2020, 4, 12).and_hms(12, 0, 0)
+    );
+}
+
+#[test]
+fn keys_name_station_product_and_cycle() {
+    let cycle = Utc.ymd(2020, 4, 12).and_hms(12, 0, 0);
+    assert_eq!(
+        cache::key("kfit", Product::Mav, cycle),
+        "mos:KFIT:mav:2020-04-12T12:00:00Z"
+    );
+}
+
+#[test]
+fn expires_when_the_next_cycle_is_due() {
+    let cycle = Utc.ymd(2020, 4, 12).and_hms(12, 0, 0);
+    let now = Utc.ymd(2020, 4, 12).and_hms(17, 0, 0);
+    assert_eq!(cache::ttl(Product::Mav, cycle, now), Duration::hours(5));
+    assert_eq!(cache::ttl(Product::Mex, cycle, now), Duration::hours(11));
+
+    let late = Utc.ymd(2020, 4, 13).and_hms(0, 0, 0);
+    assert_eq!(cache::ttl(Product::Mav, cycle, late), Duration::minutes(1));
+}
+
+#[test]
+fn memory_cache_expires_and_invalidates() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let cache = MemoryCache::new();
+    cache.put("kfit", &mos, Duration::minutes(5)).unwrap();
+    assert_eq!(cache.get("kfit").unwrap(), Some(mos.clone()));
+
+    cache.invalidate("kfit").unwrap();
+    assert_eq!(cache.get("kfit").unwrap(), None);
+
+    cache.put("kfit", &mos, Duration::seconds(-1)).unwrap();
+    assert_eq!(cache.get("kfit").unwrap(), None);
+}
+
+#[test]
+fn fetches_once_while_cached() {
+    let bulletin = testing::load("kfit_mav.txt");
+    let fetches = AtomicUsize::new(0);
+    let transport = FnTransport(|_: &str| {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        Ok(testing::page(&bulletin))
+    });
+    let cache = MemoryCache::new();
+
+    let first = client::fetch_cached(&transport, &cache, "KFIT", Product::Mav).unwrap();
+    let second = client::fetch_cached(&transport, &cache, "KFIT", Product::Mav).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+}
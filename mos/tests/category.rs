@@ -0,0 +1,28 @@
+use chrono::{TimeZone, Utc};
+
+use mos::category::FlightCategory;
+use mos::testing;
+
+#[test]
+fn worst_conditions_in_a_window() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let worst = mos
+        .worst_conditions(
+            Utc.ymd(2020, 4, 12).and_hms(18, 0, 0),
+            Utc.ymd(2020, 4, 13).and_hms(12, 0, 0),
+        )
+        .unwrap();
+
+    let ceiling = worst.ceiling.unwrap();
+    assert_eq!(ceiling.value, 3);
+    assert_eq!(ceiling.at, Utc.ymd(2020, 4, 13).and_hms(9, 0, 0));
+    let visibility = worst.visibility.unwrap();
+    assert_eq!(visibility.value, 4);
+    assert_eq!(visibility.at, Utc.ymd(2020, 4, 13).and_hms(3, 0, 0));
+    let category = worst.flight_category.unwrap();
+    assert_eq!(category.value, FlightCategory::Ifr);
+    assert_eq!(category.at, Utc.ymd(2020, 4, 13).and_hms(3, 0, 0));
+
+    let before = Utc.ymd(2020, 4, 1).and_hms(0, 0, 0);
+    assert_eq!(mos.worst_conditions(before, before), None);
+}
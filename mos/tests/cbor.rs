@@ -0,0 +1,11 @@
+#![cfg(feature = "serde_cbor")]
+
+use mos::{testing, MOS};
+
+#[test]
+fn roundtrips_through_cbor() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let bytes = mos.to_cbor().unwrap();
+    assert_eq!(MOS::from_cbor(&bytes).unwrap(), mos);
+    assert!(MOS::from_cbor(&bytes[..bytes.len() / 2]).is_err());
+}
@@ -0,0 +1,196 @@
+use chrono::{TimeZone, Utc};
+use std::cell::RefCell;
+use std::time::Duration;
+
+use mos::client::{self, Response, Transport, Validators};
+use mos::error::Error;
+use mos::testing::{self, FnTransport};
+use mos::{stations, Product};
+
+#[test]
+fn fetches_through_the_transport() {
+    let bulletin = testing::load("kfit_mav.txt");
+    let transport = FnTransport(|url: &str| {
+        assert_eq!(
+            url,
+            "https://www.nws.noaa.gov/cgi-bin/mos/getmav.pl?sta=KFIT"
+        );
+        Ok(testing::page(&bulletin))
+    });
+
+    let mos = client::fetch(&transport, "kfit", Product::Mav).unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.raw, bulletin);
+}
+
+#[test]
+fn unknown_station() {
+    let transport = FnTransport(|_: &str| {
+        Ok(Response {
+            status: 200,
+            body: String::from("<html><body>No MOS data for this station</body></html>"),
+        })
+    });
+    let err = client::fetch(&transport, "KXXX", Product::Mav).unwrap_err();
+    assert!(matches!(err, Error::StationNotFound(ref icao) if icao == "KXXX"));
+
+    let transport = FnTransport(|_: &str| {
+        Ok(Response {
+            status: 404,
+            body: String::new(),
+        })
+    });
+    let err = client::fetch(&transport, "KXXX", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_STATION_NOT_FOUND");
+}
+
+#[test]
+fn upstream_failures() {
+    let transport = FnTransport(|_: &str| {
+        Ok(Response {
+            status: 503,
+            body: String::from("Service Unavailable"),
+        })
+    });
+    let err = client::fetch(&transport, "KFIT", Product::Mex).unwrap_err();
+    assert!(matches!(err, Error::Status { status: 503, .. }));
+
+    let transport = FnTransport(|_: &str| Err(Error::Timeout));
+    let err = client::fetch(&transport, "KFIT", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_UPSTREAM_TIMEOUT");
+}
+
+#[test]
+fn maintenance_pages() {
+    let transport = FnTransport(|_: &str| {
+        Ok(Response {
+            status: 200,
+            body: String::from(
+                "<html><head><title>NWS MOS</title></head><body><h1>Notice</h1><pre>\n\
+                 The MOS products are   temporarily unavailable due to\n\
+                 scheduled maintenance.</pre></body></html>",
+            ),
+        })
+    });
+    let err = client::fetch(&transport, "KFIT", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_UPSTREAM_UNAVAILABLE");
+    assert!(matches!(
+        err,
+        Error::UpstreamUnavailable(ref notice)
+            if notice == "The MOS products are temporarily unavailable due to"
+    ));
+}
+
+#[test]
+fn fetches_the_nearest_station_with_a_bulletin() {
+    let bulletin = testing::load("kbed_mav_missing_rows.txt");
+    let transport = FnTransport(|url: &str| {
+        if url.ends_with("sta=KBED") {
+            Ok(testing::page(&bulletin))
+        } else {
+            Ok(Response {
+                status: 404,
+                body: String::new(),
+            })
+        }
+    });
+
+    let near = client::fetch_near(&transport, 42.55, -71.76, 100.0, Product::Mav).unwrap();
+    assert_eq!(near.station.icao, "KBED");
+    assert_eq!(near.mos.meta.icao, "KBED");
+    assert!((near.distance_nm - 21.4).abs() < 0.1);
+
+    let err = client::fetch_near(&transport, 91.0, 0.0, 100.0, Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_INVALID_ARGUMENT");
+
+    // Mid-Atlantic, and short of KBED from Fitchburg
+    let err = client::fetch_near(&transport, 40.0, -40.0, 100.0, Product::Mav).unwrap_err();
+    assert!(matches!(err, Error::UnknownStation(ref point) if point == "40,-40"));
+    let err = client::fetch_near(&transport, 42.55, -71.76, 10.0, Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_STATION_NOT_FOUND");
+}
+
+#[test]
+fn asks_upstream_about_unlisted_stations() {
+    assert!(stations::supports("kfit", Product::Mav));
+    assert!(stations::supports("KFIT", Product::Mex));
+    assert!(!stations::supports("EGLL", Product::Mav));
+    assert!(!stations::supports("KXYZ", Product::Mav));
+
+    let bulletin = testing::load("kfit_mav.txt").replacen("KFIT", "KXYZ", 1);
+    let transport = FnTransport(|url: &str| {
+        if url.ends_with("sta=KXYZ") {
+            Ok(testing::page(&bulletin))
+        } else {
+            Ok(Response {
+                status: 404,
+                body: String::new(),
+            })
+        }
+    });
+    let mos = client::fetch(&transport, "kxyz", Product::Mav).unwrap();
+    assert_eq!(mos.meta.icao, "KXYZ");
+
+    let err = client::fetch(&transport, "egll", Product::Mav).unwrap_err();
+    assert!(matches!(err, Error::StationNotFound(ref icao) if icao == "EGLL"));
+}
+
+/// Answers every conditional GET with a 304, recording the validators it was sent
+struct NotModified(RefCell<Vec<Validators>>);
+
+impl Transport for NotModified {
+    fn get(&self, _: &str) -> Result<Response, Error> {
+        panic!("polling should use conditional GETs")
+    }
+
+    fn get_if_changed(
+        &self,
+        _: &str,
+        validators: &Validators,
+    ) -> Result<(Response, Validators), Error> {
+        self.0.borrow_mut().push(validators.clone());
+        let res = Response {
+            status: 304,
+            body: String::new(),
+        };
+        Ok((res, validators.clone()))
+    }
+}
+
+#[test]
+fn waits_for_a_newer_cycle() {
+    let bulletin = testing::load("kfit_mav.txt");
+    let transport = FnTransport(|_: &str| Ok(testing::page(&bulletin)));
+    let six_z = Utc.ymd(2020, 4, 12).and_hms(6, 0, 0);
+    let mos = client::wait_for_cycle(
+        &transport,
+        "KFIT",
+        Product::Mav,
+        six_z,
+        Duration::from_secs(0),
+    )
+    .unwrap();
+    assert_eq!(mos.meta.timestamp, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+
+    let err = client::wait_for_cycle(
+        &transport,
+        "KFIT",
+        Product::Mav,
+        mos.meta.timestamp,
+        Duration::from_secs(0),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), "E_NO_NEW_CYCLE");
+
+    let transport = NotModified(RefCell::new(Vec::new()));
+    let err = client::wait_for_cycle(
+        &transport,
+        "KFIT",
+        Product::Mav,
+        six_z,
+        Duration::from_secs(0),
+    )
+    .unwrap_err();
+    assert!(matches!(err, Error::NoNewCycle(cycle) if cycle == six_z));
+    assert_eq!(transport.0.into_inner(), vec![Validators::default()]);
+}
@@ -0,0 +1,40 @@
+use chrono::{TimeZone, Utc};
+
+use mos::category::FlightCategory;
+use mos::compare;
+use mos::metar::Metar;
+use mos::testing;
+
+#[test]
+fn compares_the_nearest_entry() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let metar = Metar::parse_at(
+        "KFIT 121752Z 28004KT 10SM BKN008 00/M03 A3002",
+        Utc.ymd(2020, 4, 12).and_hms(18, 0, 0),
+    )
+    .unwrap();
+
+    let cmp = compare::mos_vs_obs(&mos, &metar).unwrap();
+    assert_eq!(cmp.valid, Utc.ymd(2020, 4, 12).and_hms(18, 0, 0));
+    assert_eq!(cmp.temperature_error, Some(3.0));
+    assert_eq!(cmp.wind_speed_error, Some(-3.0));
+    assert_eq!(cmp.wind_direction_error, Some(-30));
+    assert_eq!(cmp.forecast_category, Some(FlightCategory::Vfr));
+    assert_eq!(cmp.observed_category, FlightCategory::Ifr);
+    assert!(cmp.category_mismatch());
+    assert_eq!(
+        cmp.temperature_bias().as_deref(),
+        Some("MOS is running 3° warm")
+    );
+}
+
+#[test]
+fn skips_observations_outside_the_forecast() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let metar = Metar::parse_at(
+        "KFIT 110052Z 00000KT 10SM CLR 05/M03 A3002",
+        Utc.ymd(2020, 4, 11).and_hms(1, 0, 0),
+    )
+    .unwrap();
+    assert_eq!(compare::mos_vs_obs(&mos, &metar), None);
+}
@@ -0,0 +1,37 @@
+use mos::{testing, ParseOptions, MOS};
+
+#[derive(Debug, PartialEq)]
+struct Knots(u32);
+
+#[test]
+fn parses_registered_rows() {
+    let opts = ParseOptions::default()
+        .with_row("p24", |cell| cell.parse::<isize>().ok())
+        .with_row("WND", |cell| cell.parse::<u32>().ok().map(Knots));
+    let mos = MOS::new_with(&testing::load("kfit_mex.txt"), &opts).unwrap();
+
+    let p24 = mos.custom::<isize>("P24").unwrap();
+    assert_eq!(p24.len(), mos.entries.len());
+    assert_eq!(&p24[..4], &[None, Some(&65), None, Some(&40)]);
+    assert_eq!(mos.custom::<Knots>("WND").unwrap()[0], Some(&Knots(14)));
+    assert_eq!(mos.custom.labels(), vec!["P24", "WND"]);
+
+    assert!(mos.custom::<String>("P24").is_none());
+    assert!(mos.custom::<isize>("Q24").is_none());
+}
+
+#[test]
+fn custom_rows_leave_the_rest_alone() {
+    let opts = ParseOptions::default().with_row("TMP", |cell| cell.parse::<i32>().ok());
+    let mos = MOS::new_with(&testing::load("kfit_mav.txt"), &opts).unwrap();
+    let plain = testing::parse("kfit_mav.txt").unwrap();
+
+    assert_eq!(mos.custom::<i32>("TMP").unwrap()[0], Some(&35));
+    assert_eq!(mos.entries, plain.entries);
+    assert_eq!(mos, plain);
+    assert_eq!(
+        serde_json::to_string(&mos).unwrap(),
+        serde_json::to_string(&plain).unwrap()
+    );
+    assert!(plain.custom.is_empty());
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+
+use mos::precip::{PrecipType, QpfRange};
+use mos::testing;
+
+#[test]
+fn summarizes_each_local_day() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let days = mos.daily_summaries();
+    assert_eq!(days.len(), mos.by_day().len());
+
+    // 18Z through 03Z: the max of 36 at 00Z, but the 12Z minimum is tomorrow morning's
+    let first = &days[0];
+    assert_eq!(first.date, NaiveDate::from_ymd(2020, 4, 12));
+    assert_eq!(first.high, Some(36));
+    assert_eq!(first.low, Some(25));
+    assert_eq!(first.max_pop, Some(40));
+    assert_eq!(
+        first.qpf,
+        Some(QpfRange {
+            min: 0.10,
+            max: Some(0.24)
+        })
+    );
+    assert_eq!(first.sky.as_deref(), Some("FW"));
+    assert_eq!(first.precip_type, Some(PrecipType::Snow));
+
+    let second = &days[1];
+    assert_eq!(second.low, Some(20));
+    assert_eq!(second.high, Some(33));
+}
+
+#[test]
+fn adds_qpf_ranges() {
+    let total = QpfRange::from_category(2)
+        .unwrap()
+        .add(QpfRange::from_category(6).unwrap());
+    assert_eq!(total.max, None);
+    assert!((total.min - 2.1).abs() < 1e-9);
+    assert_eq!(QpfRange::from_category(7), None);
+}
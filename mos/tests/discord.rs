@@ -0,0 +1,31 @@
+use mos::notify::discord;
+use mos::testing;
+
+#[test]
+fn embed_covers_the_next_24_hours() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let message = discord::message(&mos);
+    let embed = &message["embeds"][0];
+    assert_eq!(embed["title"], "KFIT MOS guidance");
+
+    let field = |name: &str| {
+        embed["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|field| field["name"] == name)
+            .map(|field| field["value"].clone())
+            .unwrap()
+    };
+    assert_eq!(field("Temperature"), "20–36°F");
+    assert_eq!(field("Wind"), "300° 22kt");
+    assert_eq!(field("PoP"), "70%");
+
+    let worst = mos.entries[..8]
+        .iter()
+        .filter_map(|entry| entry.flight_category())
+        .min()
+        .unwrap();
+    assert_eq!(field("Flight category"), worst.to_string().as_str());
+    assert_eq!(embed["color"], discord::color(worst));
+}
@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use mos::client::{self, Endpoint, Response};
+use mos::failover::Failover;
+use mos::testing::{self, FnTransport};
+use mos::Product;
+
+const LISTING: &str = "https://api.weather.gov/products/types/MAV/locations";
+
+fn endpoints() -> Vec<Endpoint> {
+    vec![Endpoint::nws(), Endpoint::products_api(), Endpoint::iem()]
+}
+
+/// api.weather.gov answering with the collective fixture under a placeholder location
+fn products_api(url: &str) -> Response {
+    let body = if url == LISTING {
+        serde_json::json!({ "locations": { "LOC1": null } })
+    } else if url == format!("{}/LOC1/latest", LISTING) {
+        serde_json::json!({ "productText": testing::load("mav_collective.txt") })
+    } else {
+        return Response {
+            status: 404,
+            body: String::new(),
+        };
+    };
+    Response {
+        status: 200,
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn prefers_the_endpoint_that_has_been_working() {
+    let calls = RefCell::new(Vec::new());
+    let transport = FnTransport(|url: &str| {
+        calls.borrow_mut().push(url.to_string());
+        if url.starts_with("https://www.nws.noaa.gov/") {
+            Ok(Response {
+                status: 503,
+                body: String::new(),
+            })
+        } else {
+            Ok(products_api(url))
+        }
+    });
+    let failover = Failover::new(transport, endpoints());
+
+    let mos = failover.fetch("KFIT", Product::Mav).unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(
+        *calls.borrow(),
+        vec![
+            "https://www.nws.noaa.gov/cgi-bin/mos/getmav.pl?sta=KFIT".to_string(),
+            LISTING.to_string(),
+            format!("{}/LOC1/latest", LISTING),
+        ]
+    );
+
+    // Straight to the collective found the first time
+    failover.fetch("KFIT", Product::Mav).unwrap();
+    assert_eq!(calls.borrow().len(), 4);
+    assert_eq!(calls.borrow()[3], format!("{}/LOC1/latest", LISTING));
+
+    let health = failover.health();
+    assert_eq!(health[0].0.name, "nws");
+    assert_eq!(health[0].1.consecutive_failures, 1);
+    assert_eq!(health[1].0.name, "api.weather.gov");
+    assert_eq!(health[1].1.successes, 2);
+    assert_eq!(health[2].1, Default::default());
+}
+
+#[test]
+fn retries_the_preferred_endpoint_after_the_cooldown() {
+    let calls = RefCell::new(Vec::new());
+    let transport = FnTransport(|url: &str| {
+        calls.borrow_mut().push(url.to_string());
+        Ok(Response {
+            status: 500,
+            body: String::new(),
+        })
+    });
+    let mut failover = Failover::new(transport, endpoints());
+    failover.cooldown = Duration::from_secs(0);
+
+    let err = failover.fetch("KFIT", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_UPSTREAM_STATUS");
+    failover.fetch("KFIT", Product::Mav).unwrap_err();
+    // The NWS, then the products API, each time. IEM has no collective location for
+    // the station to ask for
+    assert_eq!(calls.borrow().len(), 4);
+    assert!(calls.borrow()[2].starts_with("https://www.nws.noaa.gov/"));
+}
+
+#[test]
+fn presets_by_name() {
+    for name in client::SOURCES {
+        assert_eq!(Endpoint::named(name).unwrap().name, *name);
+    }
+    assert_eq!(
+        Endpoint::named(" API.weather.gov ").unwrap(),
+        Endpoint::products_api()
+    );
+    let err = Endpoint::named("mirror").unwrap_err();
+    assert_eq!(err.code(), "E_INVALID_ARGUMENT");
+}
+
+#[test]
+fn presets_serve_every_listed_station() {
+    let listed = include_str!("../data/mos_stations.txt")
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect::<Vec<&str>>();
+    assert!(listed.len() > 100);
+
+    for name in client::SOURCES {
+        let endpoint = Endpoint::named(name).unwrap();
+        for icao in &listed {
+            for &product in &[Product::Mav, Product::Mex] {
+                if mos::stations::supports(icao, product) {
+                    assert!(endpoint.serves(icao, product), "{} {}", name, icao);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use mos::ffi::{mos_free, mos_parse};
+use mos::{testing, MOS};
+
+fn parse(text: &str) -> serde_json::Value {
+    let text = CString::new(text).unwrap();
+    unsafe {
+        let json = mos_parse(text.as_ptr());
+        assert!(!json.is_null());
+        let val = serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+        mos_free(json);
+        val
+    }
+}
+
+#[test]
+fn parses_to_json() {
+    let val = parse(&testing::load("kfit_mav.txt"));
+    let mos: MOS = serde_json::from_value(val).unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+}
+
+#[test]
+fn reports_errors_as_json() {
+    let val = parse("not a bulletin");
+    assert!(val["error"].as_str().unwrap().starts_with("E_"));
+}
+
+#[test]
+fn handles_null() {
+    unsafe {
+        assert!(mos_parse(ptr::null()).is_null());
+        mos_free(ptr::null_mut());
+    }
+}
@@ -0,0 +1,319 @@
+use chrono::{TimeZone, Utc};
+
+use mos::format::Format;
+use mos::{testing, ColumnSource, ParseOptions, MOS};
+
+#[test]
+fn every_bulletin_fixture_parses() {
+    for name in testing::fixtures() {
+        if name.contains("collective") {
+            continue;
+        }
+        let mos = testing::parse(&name).unwrap_or_else(|err| panic!("{}: {}", name, err));
+        assert!(!mos.entries.is_empty(), "{} has no entries", name);
+        for pair in mos.entries.windows(2) {
+            assert!(
+                pair[0].timestamp < pair[1].timestamp,
+                "{} has unordered valid times",
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn mav_values() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.meta.timestamp, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(mos.entries.len(), 21);
+
+    let first = &mos.entries[0];
+    assert_eq!(first.timestamp, Utc.ymd(2020, 4, 12).and_hms(18, 0, 0));
+    assert_eq!(first.tmp, Some(35));
+    assert_eq!(first.dpt, Some(28));
+    assert_eq!(first.cld.as_deref(), Some("FW"));
+    assert_eq!(first.obv.as_deref(), Some("BR"));
+
+    let third = &mos.entries[2];
+    assert_eq!(third.nx, Some(36));
+    assert_eq!(third.p06, Some(35));
+    assert_eq!(third.p12, Some(40));
+    assert_eq!(third.t06, Some((13, 2)));
+    assert_eq!(third.t12, Some((17, 2)));
+
+    // The last two columns are 6-hourly
+    let last = mos.entries.last().unwrap();
+    assert_eq!(last.timestamp, Utc.ymd(2020, 4, 15).and_hms(12, 0, 0));
+    assert_eq!(
+        mos.entries[19].timestamp,
+        Utc.ymd(2020, 4, 15).and_hms(6, 0, 0)
+    );
+}
+
+#[test]
+fn mav_cycles() {
+    let cycles = &[
+        ("kbos_mav_00z.txt", Utc.ymd(2020, 4, 13).and_hms(6, 0, 0)),
+        ("kden_mav_06z.txt", Utc.ymd(2020, 4, 13).and_hms(12, 0, 0)),
+        ("kfit_mav.txt", Utc.ymd(2020, 4, 12).and_hms(18, 0, 0)),
+        ("kmia_mav_18z.txt", Utc.ymd(2020, 4, 13).and_hms(0, 0, 0)),
+    ];
+    for (name, first_valid) in cycles {
+        let mos = testing::parse(name).unwrap();
+        assert_eq!(mos.entries[0].timestamp, *first_valid, "{}", name);
+        assert_eq!(
+            mos.entries.last().unwrap().timestamp - mos.meta.timestamp,
+            chrono::Duration::hours(72),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn truncated_bulletin() {
+    let mos = testing::parse("kfit_mav_truncated.txt").unwrap();
+    assert_eq!(mos.entries.len(), 21);
+    assert_eq!(mos.entries[2].p06, Some(35));
+    assert!(mos.entries.iter().all(|entry| entry.cig.is_none()));
+    assert!(mos.entries.iter().all(|entry| entry.typ.is_none()));
+}
+
+#[test]
+fn missing_rows() {
+    let mos = testing::parse("kbed_mav_missing_rows.txt").unwrap();
+    assert_eq!(mos.entries[0].tmp, Some(71));
+    assert!(mos.entries.iter().all(|entry| entry.t06.is_none()));
+    assert!(mos.entries.iter().all(|entry| entry.cig.is_none()));
+    assert!(mos.entries.iter().all(|entry| entry.obv.is_none()));
+}
+
+#[test]
+fn small_station_without_precip_type_rows() {
+    let mos = testing::parse("kmvl_mav_small_station.txt").unwrap();
+    assert!(mos.entries.iter().all(|entry| entry.poz.is_none()
+        && entry.pos.is_none()
+        && entry.snw.is_none()
+        && entry.t06.is_none()
+        && entry.obv.is_none()));
+    assert_eq!(mos.entries[0].typ.as_deref(), Some("R"));
+    assert_eq!(mos.entries[0].cig, Some(8));
+    assert_eq!(mos.entries[20].vis, Some(7));
+}
+
+#[test]
+fn crlf_and_short_lines() {
+    let text = testing::load("kmvl_mav_small_station.txt")
+        .replace('\n', "\r\n")
+        .replace(
+            " VIS   7  7  7  7  6  6  7  7  7  7  7  7  6  6  7  7  7  7  7  6  7",
+            " VIS   7  7  7  7  6  6  7  7  7  7  7  7  6  6  7  7  7  7",
+        );
+    let mos = MOS::new(&text).unwrap();
+    let expected = testing::parse("kmvl_mav_small_station.txt").unwrap();
+    assert_eq!(mos.entries[20].cld, expected.entries[20].cld);
+    assert_eq!(mos.entries[17].vis, Some(7));
+    assert_eq!(mos.entries[18].vis, None);
+    assert_eq!(mos.entries[20].vis, None);
+}
+
+#[test]
+fn mex_projections() {
+    let mos = testing::parse("kfit_mex.txt").unwrap();
+    assert_eq!(mos.entries.len(), 15);
+    assert_eq!(
+        mos.entries[0].timestamp,
+        Utc.ymd(2020, 4, 13).and_hms(12, 0, 0)
+    );
+    assert_eq!(
+        mos.entries.last().unwrap().timestamp,
+        Utc.ymd(2020, 4, 20).and_hms(12, 0, 0)
+    );
+    assert_eq!(mos.entries[0].nx, Some(42));
+    assert_eq!(mos.entries[0].tmp, Some(44));
+    assert_eq!(mos.entries[0].p12, Some(60));
+    assert_eq!(mos.entries[7].p12, Some(5));
+
+    // Rows without a field of their own, every 12 hours
+    let row = |label: &str| -> Vec<isize> {
+        mos.row(label)
+            .unwrap()
+            .into_iter()
+            .map(|cell| cell.unwrap())
+            .collect()
+    };
+    assert_eq!(
+        row("WND"),
+        [14, 7, 9, 5, 12, 15, 5, 13, 4, 13, 7, 11, 14, 12, 10]
+    );
+    assert_eq!(row("PZP"), [3, 0, 0, 2, 3, 2, 3, 2, 0, 3, 2, 1, 0, 3, 0]);
+    assert_eq!(row("PSN"), [3, 4, 2, 3, 6, 6, 7, 1, 2, 7, 6, 8, 4, 2, 6]);
+    assert_eq!(row("PRS"), [8, 4, 6, 5, 6, 3, 2, 1, 2, 2, 3, 3, 0, 7, 2]);
+}
+
+#[test]
+fn lamp_values() {
+    let lamp = testing::load("kfit_lamp.txt");
+    assert_eq!(Format::detect(&lamp), Format::Lamp);
+
+    let mos = testing::parse("kfit_lamp.txt").unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.meta.timestamp, Utc.ymd(2020, 4, 12).and_hms(12, 30, 0));
+    assert_eq!(mos.entries.len(), 25);
+
+    let first = &mos.entries[0];
+    assert_eq!(first.timestamp, Utc.ymd(2020, 4, 12).and_hms(13, 0, 0));
+    assert_eq!(
+        (first.tmp, first.dpt, first.wdr, first.wsp),
+        (Some(53), Some(43), Some(12), Some(13))
+    );
+    assert_eq!(first.cld.as_deref(), Some("BK"));
+    assert_eq!((first.cig, first.vis), (Some(5), Some(7)));
+    assert_eq!(first.typ.as_deref(), Some("R"));
+    assert_eq!(first.obv.as_deref(), Some("N"));
+
+    // The hours roll over midnight and end a day after the first
+    assert_eq!(
+        mos.entries[11].timestamp,
+        Utc.ymd(2020, 4, 13).and_hms(0, 0, 0)
+    );
+    let last = mos.entries.last().unwrap();
+    assert_eq!(last.timestamp, Utc.ymd(2020, 4, 13).and_hms(13, 0, 0));
+    assert_eq!(
+        (last.tmp, last.wsp, last.cig),
+        (Some(56), Some(15), Some(8))
+    );
+
+    // Rows the current bulletins don't have are kept as text
+    assert_eq!(mos.extras["PPO"][..3], ["0", "6", "5"]);
+    assert_eq!(mos.extras["WGS"][0], "NG");
+}
+
+#[test]
+fn collective_file() {
+    let bulletins = testing::bulletins("mav_collective.txt");
+    assert_eq!(bulletins.len(), 22);
+    for bulletin in &bulletins {
+        let mos = mos::MOS::new(bulletin).unwrap();
+        assert!(mos::stations::lookup(&mos.meta.icao).is_some());
+        assert_eq!(mos.entries.len(), 21);
+    }
+}
+
+#[test]
+fn header_variants() {
+    let mex = testing::parse("kfit_mex.txt").unwrap();
+    assert_eq!(mex.meta.timestamp, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+
+    // The headers of the other model generations, over the body of the MAV fixture
+    let mav = testing::load("kfit_mav.txt");
+    let body = &mav[mav.find('\n').unwrap()..];
+    let cases = [
+        (" KFIT   NAM MOS GUIDANCE    4/12/2020  1200 UTC", 2020),
+        (" KFIT   AVN MOS GUIDANCE    4/12/2001  1200 UTC", 2001),
+        (" KFIT    ETA MOS GUIDANCE  4/12/2004 1200 UTC", 2004),
+        ("KFIT GFS MOS GUIDANCE 04/12/2020 1200UTC", 2020),
+        (" KFIT   GFSX MOS GUIDANCE   4/12/01  1200Z", 2001),
+    ];
+    for &(header, year) in cases.iter() {
+        let mos = MOS::new(&format!("{}{}", header, body))
+            .unwrap_or_else(|err| panic!("{}: {}", header, err));
+        assert_eq!(mos.meta.icao, "KFIT", "{}", header);
+        assert_eq!(
+            mos.meta.timestamp,
+            Utc.ymd(year, 4, 12).and_hms(12, 0, 0),
+            "{}",
+            header
+        );
+        assert_eq!(mos.entries.len(), 21, "{}", header);
+    }
+
+    let err = MOS::new(" KFIT   GFS MOS GUIDANCE\n").unwrap_err();
+    assert_eq!(err.code(), "E_PARSE_HEADER");
+}
+
+#[test]
+fn legacy_format() {
+    let legacy = testing::load("kbos_ngm_legacy.txt");
+    assert_eq!(Format::detect(&legacy), Format::Legacy);
+    assert_eq!(
+        Format::detect(&testing::load("kfit_mav.txt")),
+        Format::Current
+    );
+
+    let mos = testing::parse("kbos_ngm_legacy.txt").unwrap();
+    assert_eq!(mos.meta.icao, "KBOS");
+    assert_eq!(mos.meta.timestamp, Utc.ymd(2000, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(mos.entries.len(), 21);
+
+    // TEMP, DEWPT, CLDS, WDIR, WSPD, POZP, POSN, PTYPE, CIG, VIS and OBVIS
+    let first = &mos.entries[0];
+    assert_eq!(first.timestamp, Utc.ymd(2000, 4, 12).and_hms(18, 0, 0));
+    assert_eq!(
+        (first.tmp, first.dpt, first.wdr, first.wsp),
+        (Some(35), Some(28), Some(25), Some(1))
+    );
+    assert_eq!((first.poz, first.pos), (Some(3), Some(69)));
+    assert_eq!((first.cig, first.vis), (Some(7), Some(7)));
+    assert_eq!(first.cld.as_deref(), Some("FW"));
+    assert_eq!(first.typ.as_deref(), Some("S"));
+    assert_eq!(first.obv.as_deref(), Some("BR"));
+
+    // MX/MN, POP06, POP12, QPF06, QPF12, TSV06 and TSV12 open at 00Z
+    let third = &mos.entries[2];
+    assert_eq!(third.nx, Some(36));
+    assert_eq!((third.p06, third.p12), (Some(35), Some(40)));
+    assert_eq!((third.q06, third.q12), (Some(2), Some(4)));
+    assert_eq!((third.t06, third.t12), (Some((13, 2)), Some((17, 2))));
+
+    // SNOW only at 12Z
+    let snow: Vec<Option<isize>> = mos.entries.iter().map(|entry| entry.snw).collect();
+    assert_eq!(snow[6], Some(0));
+    assert_eq!(snow[14], Some(2));
+    assert_eq!(snow[20], Some(2));
+    assert_eq!(snow.iter().filter(|snw| snw.is_some()).count(), 3);
+
+    let last = mos.entries.last().unwrap();
+    assert_eq!(last.timestamp, Utc.ymd(2000, 4, 15).and_hms(12, 0, 0));
+    assert_eq!((last.nx, last.tmp, last.dpt), (Some(15), Some(16), Some(9)));
+    assert_eq!((last.p06, last.p12), (Some(10), Some(15)));
+    assert_eq!(last.obv.as_deref(), Some("N"));
+}
+
+#[test]
+fn column_fallbacks() {
+    let bulletin = testing::load("kfit_mav.txt");
+    let expected = testing::parse("kfit_mav.txt").unwrap();
+    let hour_line = " HR   18 21 00 03 06 09 12 15 18 21 00 03 06 09 12 15 18 21 00 06 12";
+    let date_line = " DT       /APR 13                 /APR 14                 /APR 15";
+    let opts = ParseOptions::default();
+
+    let (_, diagnostics) = MOS::new_with_diagnostics(&bulletin, &opts).unwrap();
+    assert_eq!(diagnostics.format, Format::Current);
+    assert_eq!(diagnostics.columns, ColumnSource::HourLine);
+
+    let garbled = bulletin.replace(hour_line, " HR   18 21 00 03 0? 09 12 15 18 21 00 03 06 09");
+    let (mos, diagnostics) = MOS::new_with_diagnostics(&garbled, &opts).unwrap();
+    assert_eq!(diagnostics.columns, ColumnSource::DateLine);
+    assert_eq!(mos.entries, expected.entries);
+
+    let missing = bulletin.replace(&format!("{}\n", hour_line), "");
+    let (mos, diagnostics) = MOS::new_with_diagnostics(&missing, &opts).unwrap();
+    assert_eq!(diagnostics.columns, ColumnSource::DateLine);
+    assert_eq!(mos.entries, expected.entries);
+
+    let undated = garbled.replace(date_line, " DT");
+    let (mos, diagnostics) = MOS::new_with_diagnostics(&undated, &opts).unwrap();
+    assert_eq!(diagnostics.columns, ColumnSource::FixedWidths);
+    assert_eq!(mos.entries, expected.entries);
+
+    let strict = ParseOptions {
+        strict: true,
+        ..ParseOptions::default()
+    };
+    let err = MOS::new_with(&garbled, &strict).unwrap_err();
+    assert_eq!(err.code(), "E_PARSE_HOURLINE");
+    let err = MOS::new(&missing.replace(&format!("{}\n", date_line), "")).unwrap_err();
+    assert_eq!(err.code(), "E_PARSE_HOURLINE");
+}
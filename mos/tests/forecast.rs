@@ -0,0 +1,62 @@
+use chrono::{TimeZone, Utc};
+
+use mos::category::FlightCategory;
+use mos::forecast::{Forecast, ForecastEntry};
+use mos::{testing, MOSEntry};
+
+/// Stands in for downstream code written against the trait
+fn warmest<F: Forecast>(forecast: &F) -> Option<isize> {
+    forecast
+        .entries()
+        .iter()
+        .filter_map(|entry| entry.temp())
+        .max()
+}
+
+#[test]
+fn mos_is_a_forecast() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    assert_eq!(mos.location(), "KFIT");
+    assert_eq!(mos.issued(), Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(
+        warmest(&mos),
+        mos.entries.iter().filter_map(|entry| entry.tmp).max()
+    );
+
+    let entry = mos.nearest(Utc.ymd(2020, 4, 12).and_hms(19, 0, 0)).unwrap();
+    assert_eq!(entry.valid_time(), Utc.ymd(2020, 4, 12).and_hms(18, 0, 0));
+    let wind = entry.wind().unwrap();
+    assert_eq!((wind.direction, wind.speed), (Some(250), 1));
+}
+
+#[test]
+fn ceiling_and_visibility() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let first = &mos.entries[0];
+    assert_eq!((first.cig, first.vis), (Some(7), Some(7)));
+    assert_eq!(first.ceiling(), Some(6600));
+    assert_eq!(first.visibility(), Some(6.0));
+    assert_eq!(MOSEntry::default().ceiling(), None);
+    assert_eq!(MOSEntry::default().visibility(), None);
+
+    // The bottoms of the categories fall in the same flight category as the codes
+    for cig in 1..=8 {
+        for vis in 1..=7 {
+            let entry = MOSEntry {
+                cig: Some(cig),
+                vis: Some(vis),
+                ..MOSEntry::default()
+            };
+            assert_eq!(
+                Some(FlightCategory::from_conditions(
+                    entry.ceiling(),
+                    entry.visibility()
+                )),
+                entry.flight_category(),
+                "CIG {} VIS {}",
+                cig,
+                vis
+            );
+        }
+    }
+}
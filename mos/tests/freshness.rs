@@ -0,0 +1,41 @@
+use chrono::{Duration, Utc};
+
+use mos::export::json::SerializeOptions;
+use mos::{testing, Product};
+
+#[test]
+fn infers_product_from_entry_spacing() {
+    assert_eq!(
+        testing::parse("kfit_mav.txt").unwrap().product(),
+        Product::Mav
+    );
+    assert_eq!(
+        testing::parse("kfit_mex.txt").unwrap().product(),
+        Product::Mex
+    );
+}
+
+#[test]
+fn staleness_follows_the_cycle_interval() {
+    let mut mav = testing::parse("kfit_mav.txt").unwrap();
+    assert!(mav.is_stale());
+
+    mav.meta.timestamp = Utc::now() - Duration::hours(6);
+    assert!(!mav.is_stale());
+    assert!(mav.age() >= Duration::hours(6));
+    mav.meta.timestamp = Utc::now() - Duration::hours(13);
+    assert!(mav.is_stale());
+
+    let mut mex = testing::parse("kfit_mex.txt").unwrap();
+    mex.meta.timestamp = Utc::now() - Duration::hours(13);
+    assert!(!mex.is_stale());
+}
+
+#[test]
+fn json_output_carries_age_minutes() {
+    let mut mos = testing::parse("kfit_mav.txt").unwrap();
+    mos.meta.timestamp = Utc::now() - Duration::minutes(90);
+    let val = mos.to_json_with(&SerializeOptions::default()).unwrap();
+    let age = val["meta"]["age_minutes"].as_i64().unwrap();
+    assert!(age == 90 || age == 91, "{}", age);
+}
@@ -0,0 +1,29 @@
+use chrono::{TimeZone, Utc};
+
+use mos::{testing, MOSEntry, MOS};
+
+#[test]
+fn renders_a_self_contained_page() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let html = mos.to_html();
+
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<title>KFIT MOS 2020-04-12 12Z</title>"));
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<link"));
+
+    let rows = html.matches("<tr>").count();
+    assert_eq!(rows, 2 + mos.daily_summaries().len() + mos.entries.len());
+    assert!(html.contains("<td>Mon 13 00Z</td><td>32°F</td>"));
+}
+
+#[test]
+fn escapes_text() {
+    let valid = Utc.ymd(2020, 4, 12).and_hms(18, 0, 0);
+    let entry = MOSEntry {
+        obv: Some(String::from("<BR>")),
+        ..MOSEntry::at(valid)
+    };
+    let html = MOS::from_entries("KFIT", valid, vec![entry]).to_html();
+    assert!(html.contains("<td>&lt;BR&gt;</td>"));
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDate;
+
+use mos::testing;
+
+#[test]
+fn groups_entries_by_local_day() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let days = mos.by_day();
+
+    // 18Z through 03Z are still the 12th in Eastern Daylight Time
+    assert_eq!(days[0].date, NaiveDate::from_ymd(2020, 4, 12));
+    assert_eq!(days[0].entries.len(), 4);
+    assert_eq!(days[1].date, NaiveDate::from_ymd(2020, 4, 13));
+    assert_eq!(
+        days[1].entries[0].timestamp.to_rfc3339(),
+        "2020-04-13T02:00:00-04:00"
+    );
+    assert_eq!(
+        days.iter().map(|day| day.entries.len()).sum::<usize>(),
+        mos.entries.len()
+    );
+}
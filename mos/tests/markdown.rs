@@ -0,0 +1,17 @@
+use mos::testing;
+
+#[test]
+fn renders_summary_bullets_and_a_table() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let md = mos.to_markdown();
+    let lines: Vec<&str> = md.lines().collect();
+
+    assert_eq!(lines[0], "### KFIT MOS 2020-04-12 12Z");
+    let bullets = lines.iter().filter(|line| line.starts_with("- **")).count();
+    assert_eq!(bullets, mos.summarize().lines().count());
+
+    let rows: Vec<&&str> = lines.iter().filter(|line| line.starts_with('|')).collect();
+    assert_eq!(rows.len(), 2 + mos.entries.len());
+    assert!(rows[1].starts_with("| --- | --: |"));
+    assert!(rows[2].starts_with("| Sun 18Z | 35 | 28 | FW | 250/01 |"));
+}
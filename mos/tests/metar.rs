@@ -0,0 +1,52 @@
+use chrono::{TimeZone, Utc};
+
+use mos::category::FlightCategory;
+use mos::metar::{Metar, Wind};
+
+#[test]
+fn parses_a_routine_report() {
+    let now = Utc.ymd(2020, 4, 12).and_hms(13, 0, 0);
+    let metar = Metar::parse_at(
+        "KFIT 121252Z AUTO 28012G21KT 250V310 1 1/2SM -RA BR FEW008 BKN015 OVC030 M02/M04 A2992 RMK AO2 SLP134",
+        now,
+    )
+    .unwrap();
+
+    assert_eq!(metar.icao, "KFIT");
+    assert_eq!(metar.time, Utc.ymd(2020, 4, 12).and_hms(12, 52, 0));
+    assert_eq!(
+        metar.wind,
+        Some(Wind {
+            direction: Some(280),
+            speed: 12,
+            gust: Some(21),
+        })
+    );
+    assert_eq!(metar.visibility, Some(1.5));
+    assert_eq!(metar.weather, vec!["-RA", "BR"]);
+    assert_eq!(metar.sky.len(), 3);
+    assert_eq!(metar.ceiling(), Some(1500));
+    assert_eq!(metar.temperature, Some(-2));
+    assert_eq!(metar.dew_point, Some(-4));
+    assert_eq!(metar.altimeter, Some(29.92));
+    assert_eq!(metar.flight_category(), FlightCategory::Ifr);
+}
+
+#[test]
+fn parses_clear_and_variable() {
+    let now = Utc.ymd(2020, 5, 1).and_hms(1, 0, 0);
+    let metar = Metar::parse_at("METAR KDEN 302353Z VRB03KT P6SM CLR 20/M01 A3001", now).unwrap();
+
+    assert_eq!(metar.time, Utc.ymd(2020, 4, 30).and_hms(23, 53, 0));
+    assert_eq!(metar.wind.unwrap().direction, None);
+    assert_eq!(metar.visibility, Some(6.0));
+    assert_eq!(metar.ceiling(), None);
+    assert_eq!(metar.temperature_f(), Some(68.0));
+    assert_eq!(metar.flight_category(), FlightCategory::Vfr);
+}
+
+#[test]
+fn rejects_reports_without_a_time() {
+    let err = Metar::parse_at("KFIT AUTO", Utc::now()).unwrap_err();
+    assert_eq!(err.code(), "E_PARSE");
+}
@@ -0,0 +1,37 @@
+use chrono::{TimeZone, Utc};
+
+use mos::notify::mqtt::{self, Topics};
+use mos::testing;
+
+#[test]
+fn builds_messages_for_the_next_valid_time() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let topics = Topics {
+        temperature: String::from("home/weather/{icao}/temp"),
+        ..Topics::default()
+    };
+    let messages = mqtt::messages(&mos, &topics, Utc.ymd(2020, 4, 12).and_hms(19, 0, 0));
+    let topic = |name: &str| {
+        messages
+            .iter()
+            .find(|(topic, _)| topic == name)
+            .map(|(_, payload)| payload.as_str())
+    };
+
+    let entries: serde_json::Value =
+        serde_json::from_str(topic("mos/KFIT/entries").unwrap()).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), mos.entries.len());
+
+    let next = &mos.entries[1];
+    assert_eq!(
+        topic("home/weather/KFIT/temp"),
+        next.tmp.map(|tmp| tmp.to_string()).as_deref()
+    );
+    assert_eq!(topic("mos/KFIT/next/pop"), Some("35"));
+    assert_eq!(
+        topic("mos/KFIT/next/flight_category"),
+        next.flight_category()
+            .map(|category| category.to_string())
+            .as_deref()
+    );
+}
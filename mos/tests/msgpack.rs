@@ -0,0 +1,19 @@
+#![cfg(feature = "rmp-serde")]
+
+use mos::export::json::{SerializeOptions, TimestampFormat};
+use mos::{testing, MOS};
+
+#[test]
+fn roundtrips_and_beats_json() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let bytes = mos.to_msgpack().unwrap();
+    assert_eq!(MOS::from_msgpack(&bytes).unwrap(), mos);
+    assert!(bytes.len() < serde_json::to_vec(&mos).unwrap().len());
+
+    let opts = SerializeOptions {
+        timestamps: TimestampFormat::Epoch,
+    };
+    let val: serde_json::Value =
+        rmp_serde::from_read_ref(&mos.to_msgpack_with(&opts).unwrap()).unwrap();
+    assert_eq!(val["meta"]["timestamp"], 1586692800);
+}
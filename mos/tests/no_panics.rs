@@ -0,0 +1,67 @@
+use proptest::prelude::*;
+use std::fs;
+
+use mos::{testing, ParseOptions, MOS};
+
+fn parse_both(text: &str) {
+    let _ = MOS::new(text);
+    let _ = MOS::new_with(
+        text,
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    );
+}
+
+/// Inputs that crashed the parser at some point
+#[test]
+fn crash_regressions() {
+    let dir = testing::fixtures_dir().join("crashes");
+    for entry in fs::read_dir(dir).unwrap() {
+        let text = fs::read_to_string(entry.unwrap().path()).unwrap();
+        parse_both(&text);
+    }
+}
+
+#[test]
+fn every_truncation() {
+    let bulletin = testing::load("kfit_mav.txt");
+    for (end, _) in bulletin.char_indices() {
+        parse_both(&bulletin[..end]);
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text(text in "\\PC*") {
+        parse_both(&text);
+    }
+
+    #[test]
+    fn arbitrary_lines_after_the_header(lines in prop::collection::vec("[ -~é°|/]{0,80}", 0..25)) {
+        let bulletin = testing::load("kfit_mav.txt");
+        let mut text: Vec<&str> = bulletin.lines().take(3).collect();
+        text.extend(lines.iter().map(|line| line.as_str()));
+        parse_both(&text.join("\n"));
+    }
+
+    #[test]
+    fn mutated_fixture(
+        name in prop::sample::select(vec!["kfit_mav.txt", "kfit_mex.txt", "kbed_mav_missing_rows.txt"]),
+        edits in prop::collection::vec((any::<prop::sample::Index>(), "[ -~é°\n]{0,4}", 0usize..4), 1..8),
+    ) {
+        let mut text = testing::load(name);
+        for (at, insert, remove) in edits {
+            let boundaries: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+            let start = boundaries[at.index(boundaries.len())];
+            let end = boundaries
+                .iter()
+                .cloned()
+                .find(|&i| i >= start + remove)
+                .unwrap_or_else(|| text.len());
+            text.replace_range(start..end, &insert);
+        }
+        parse_both(&text);
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{Duration, SecondsFormat, Utc};
+
+use mos::client::Response;
+use mos::pirep;
+use mos::testing::FnTransport;
+
+fn report(minutes_ago: i64, lat: f64, lon: f64, body: &str) -> String {
+    let time =
+        (Utc::now() - Duration::minutes(minutes_ago)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    format!(
+        "<AircraftReport><observation_time>{}</observation_time>\
+         <latitude>{}</latitude><longitude>{}</longitude>{}</AircraftReport>",
+        time, lat, lon, body
+    )
+}
+
+#[test]
+fn fetches_reports_near_a_station() {
+    let xml = format!(
+        "<response><data>{}{}{}</data></response>",
+        report(
+            20,
+            42.4,
+            -71.3,
+            "<raw_text>BOS UUA /OV BOS/TM 1410/FL060/TP C172/IC MOD RIME 050-070</raw_text>\
+             <aircraft_ref>C172</aircraft_ref><altitude_ft_msl>6000</altitude_ft_msl>\
+             <icing_condition icing_type=\"RIME\" icing_intensity=\"MOD\" \
+             icing_base_ft_msl=\"5000\" icing_top_ft_msl=\"7000\"/>"
+        ),
+        report(
+            200,
+            42.5,
+            -71.2,
+            "<raw_text>BED UA /OV BED/TP PA28/TB LGT</raw_text>"
+        ),
+        report(
+            10,
+            40.6,
+            -73.8,
+            "<raw_text>JFK UA /OV JFK/TB MOD</raw_text>"
+        ),
+    );
+    let transport = FnTransport(move |url: &str| {
+        assert!(url.contains("dataSource=aircraftreports"));
+        assert!(url.contains("radialDistance=58;-71.289,42.47"));
+        assert!(url.contains("hoursBeforeNow=2"));
+        Ok(Response {
+            status: 200,
+            body: xml.clone(),
+        })
+    });
+
+    let reports = pirep::fetch(&transport, "KBED", 50.0, Duration::hours(2)).unwrap();
+    assert_eq!(reports.len(), 1);
+
+    let report = &reports[0];
+    assert!(report.urgent);
+    assert_eq!(report.aircraft.as_deref(), Some("C172"));
+    assert_eq!(report.altitude, Some(6000));
+    let icing = report.icing.as_ref().unwrap();
+    assert_eq!(icing.intensity, "MOD");
+    assert_eq!(icing.kind.as_deref(), Some("RIME"));
+    assert_eq!((icing.base, icing.top), (Some(5000), Some(7000)));
+    assert_eq!(report.turbulence, None);
+}
+
+#[test]
+fn rejects_unknown_stations() {
+    let transport = FnTransport(|_: &str| -> Result<Response, mos::error::Error> {
+        panic!("no request for an unknown station")
+    });
+    let err = pirep::fetch(&transport, "kxxx", 50.0, Duration::hours(1)).unwrap_err();
+    assert_eq!(err.code(), "E_UNKNOWN_STATION");
+}
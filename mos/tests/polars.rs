@@ -0,0 +1,26 @@
+#![cfg(feature = "polars")]
+
+use mos::daily::DailySummary;
+use mos::testing;
+
+#[test]
+fn converts_to_dataframes() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+
+    let df = mos.to_dataframe().unwrap();
+    assert_eq!(df.shape(), (mos.entries.len(), 24));
+    let names = df.get_column_names();
+    assert_eq!(&names[..3], &["icao", "cycle", "timestamp"]);
+    assert_eq!(
+        df.column("tmp").unwrap().i32().unwrap().get(0),
+        mos.entries[0].tmp.map(|tmp| tmp as i32)
+    );
+
+    let days = mos.daily_summaries();
+    let df = DailySummary::to_dataframe(&days).unwrap();
+    assert_eq!(df.shape(), (days.len(), 8));
+    assert_eq!(
+        df.column("high").unwrap().i32().unwrap().get(1),
+        days[1].high.map(|high| high as i32)
+    );
+}
@@ -0,0 +1,21 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use mos::testing;
+
+#[test]
+fn combines_pops_over_a_window() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let at = |day, hour| Utc.ymd(2020, 4, day).and_hms(hour, 0, 0);
+
+    // Exactly one P06
+    assert_eq!(mos.pop_over(at(12, 18), at(13, 0)), Some(35));
+    // Two whole P12s: 1 - 0.6 * 0.75
+    assert_eq!(mos.pop_over(at(12, 12), at(13, 12)), Some(55));
+    // A P06 and half of the next: 1 - 0.65 * 0.3^0.5
+    assert_eq!(mos.pop_over(at(12, 18), at(13, 3)), Some(64));
+    // Never below the largest single PoP in the window
+    assert!(mos.pop_over(at(12, 18), at(13, 6)).unwrap() >= 70);
+
+    let after = mos.entries.last().unwrap().timestamp + Duration::hours(1);
+    assert_eq!(mos.pop_over(after, after + Duration::hours(6)), None);
+}
@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+use mos::client::Response;
+use mos::products;
+use mos::testing::{self, FnTransport};
+use mos::{Product, MOS};
+
+const API: &str = "https://api.weather.gov/products/types/MAV/locations";
+
+/// The products API with two MAV locations, only the second of which carries the
+/// collective fixture. The location codes are placeholders: which collective carries a
+/// station is recorded in data/mos_stations.txt by `cia-backed gen-stations`
+fn api(url: &str) -> Response {
+    let body = if url == API {
+        serde_json::json!({ "locations": { "LOC1": null, "LOC2": null } })
+    } else if url == format!("{}/LOC1/latest", API) {
+        serde_json::json!({ "productText": testing::load("kbed_mav_missing_rows.txt") })
+    } else if url == format!("{}/LOC2/latest", API) {
+        serde_json::json!({ "productText": testing::load("mav_collective.txt") })
+    } else {
+        return Response {
+            status: 404,
+            body: String::new(),
+        };
+    };
+    Response {
+        status: 200,
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn takes_the_bulletin_out_of_the_collective() {
+    let requested = RefCell::new(Vec::new());
+    let transport = FnTransport(|url: &str| {
+        requested.borrow_mut().push(url.to_string());
+        Ok(api(url))
+    });
+
+    let mos = products::fetch(&transport, "kfit", Product::Mav).unwrap();
+    let expected = testing::bulletins("mav_collective.txt")
+        .into_iter()
+        .find(|bulletin| bulletin.trim_start().starts_with("KFIT"))
+        .unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.entries, MOS::new(&expected).unwrap().entries);
+    assert_eq!(
+        *requested.borrow(),
+        vec![
+            API.to_string(),
+            format!("{}/LOC1/latest", API),
+            format!("{}/LOC2/latest", API),
+        ]
+    );
+
+    // The location carrying the station is remembered
+    requested.borrow_mut().clear();
+    products::fetch(&transport, "KFIT", Product::Mav).unwrap();
+    assert_eq!(*requested.borrow(), vec![format!("{}/LOC2/latest", API)]);
+
+    let first = products::fetch_location(&transport, "loc2", "KASE", Product::Mav).unwrap();
+    assert_eq!(first.meta.icao, "KASE");
+    assert_eq!(
+        products::locations(&transport, Product::Mav).unwrap(),
+        vec!["LOC1", "LOC2"]
+    );
+}
+
+#[test]
+fn stations_missing_from_the_collective() {
+    let transport = FnTransport(|url: &str| Ok(api(url)));
+    let err = products::fetch_location(&transport, "LOC2", "KBTV", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_STATION_NOT_FOUND");
+
+    let err = products::fetch(&transport, "KZZZ", Product::Mav).unwrap_err();
+    assert_eq!(err.code(), "E_STATION_NOT_FOUND");
+}
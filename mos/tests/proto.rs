@@ -0,0 +1,25 @@
+#![cfg(feature = "proto")]
+
+use prost::Message;
+
+use mos::proto::Forecast;
+use mos::{testing, MOS};
+
+#[test]
+fn roundtrips_through_protobuf() {
+    let mos = testing::parse("kfit_mav.txt").unwrap().to_metric();
+    let forecast = Forecast::from(&mos);
+    assert_eq!(
+        forecast.entries[0].tmp,
+        mos.entries[0].tmp.map(|t| t as i32)
+    );
+
+    let mut bytes = Vec::new();
+    forecast.encode(&mut bytes).unwrap();
+    let decoded = Forecast::decode(bytes.as_slice()).unwrap();
+    assert_eq!(MOS::from(decoded), mos);
+
+    let mex = testing::parse("kfit_mex.txt").unwrap();
+    let decoded = MOS::from(Forecast::from(&mex));
+    assert_eq!(decoded.extras, mex.extras);
+}
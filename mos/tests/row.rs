@@ -0,0 +1,48 @@
+use mos::{testing, ParseOptions, MOS};
+
+#[test]
+fn rows_match_the_typed_fields() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let tmp: Vec<Option<isize>> = mos.entries.iter().map(|entry| entry.tmp).collect();
+    assert_eq!(mos.row("TMP").unwrap(), tmp);
+    assert_eq!(mos.row("tmp").unwrap(), tmp);
+
+    let cld: Vec<Option<String>> = mos.entries.iter().map(|entry| entry.cld.clone()).collect();
+    assert_eq!(mos.row_text("CLD").unwrap(), cld);
+
+    assert!(mos.row("XYZ").is_none());
+}
+
+#[test]
+fn rows_without_a_field() {
+    let mex = testing::parse("kfit_mex.txt").unwrap();
+    let p24 = mex.row("P24").unwrap();
+    assert_eq!(p24.len(), mex.entries.len());
+    assert_eq!(&p24[..4], &[None, Some(65), None, Some(40)]);
+    assert_eq!(mex.row("WND").unwrap()[0], Some(14));
+}
+
+#[test]
+fn legacy_rows_answer_to_current_labels() {
+    let mos = testing::parse("kbos_ngm_legacy.txt").unwrap();
+    assert_eq!(mos.row("TMP"), mos.row("TEMP"));
+    assert_eq!(mos.row("TMP").unwrap()[0], Some(35));
+}
+
+#[test]
+fn unread_rows_are_kept_as_extras() {
+    let mav = testing::parse("kfit_mav.txt").unwrap();
+    assert!(mav.extras.is_empty());
+
+    let mex = testing::parse("kfit_mex.txt").unwrap();
+    let mut labels: Vec<&str> = mex.extras.keys().map(String::as_str).collect();
+    labels.sort();
+    assert_eq!(labels, ["P24", "PRS", "PSN", "PZP", "Q24", "T24", "WND"]);
+    assert_eq!(mex.extras["P24"].len(), mex.entries.len());
+    assert_eq!(&mex.extras["P24"][..2], &["", "65"]);
+
+    let opts = ParseOptions::default().with_row("P24", |cell| cell.parse::<isize>().ok());
+    let mex = MOS::new_with(&testing::load("kfit_mex.txt"), &opts).unwrap();
+    assert!(!mex.extras.contains_key("P24"));
+    assert!(mex.extras.contains_key("WND"));
+}
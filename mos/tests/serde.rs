@@ -0,0 +1,36 @@
+use chrono::{TimeZone, Utc};
+
+use mos::{testing, MOSEntry, MOS};
+
+fn roundtrip(mos: &MOS) -> MOS {
+    serde_json::from_str(&serde_json::to_string(mos).unwrap()).unwrap()
+}
+
+#[test]
+fn fixtures_roundtrip_through_json() {
+    for name in testing::snapshot_fixtures() {
+        let mos = match testing::parse(&name) {
+            Ok(mos) => mos,
+            Err(_) => continue,
+        };
+        assert_eq!(roundtrip(&mos), mos, "{}", name);
+
+        let metric = mos.to_metric();
+        assert_eq!(roundtrip(&metric), metric, "{} in metric", name);
+    }
+}
+
+#[test]
+fn keeps_blanks_tuples_and_subsecond_timestamps() {
+    let valid = Utc.ymd(2020, 4, 12).and_hms_milli(18, 0, 0, 250);
+    let entry = MOSEntry {
+        t06: Some((12, 3)),
+        heat_index: Some(101),
+        ..MOSEntry::at(valid)
+    };
+    let mos = MOS::from_entries("KFIT", valid, vec![entry.clone(), MOSEntry::at(valid)]);
+    assert_eq!(roundtrip(&mos), mos);
+
+    let json = serde_json::to_string(&entry).unwrap();
+    assert_eq!(serde_json::from_str::<MOSEntry>(&json).unwrap(), entry);
+}
@@ -0,0 +1,24 @@
+use std::fs;
+
+use mos::testing;
+
+/// Every single-bulletin fixture parses to its committed snapshot. After an intended change
+/// to the parser output, regenerate them with `cargo run -p cia-backed -- gen-snapshots`
+/// and review the diff
+#[test]
+fn fixtures_match_snapshots() {
+    let fixtures = testing::snapshot_fixtures();
+    assert!(!fixtures.is_empty());
+
+    for fixture in fixtures {
+        let path = testing::snapshots_dir().join(testing::snapshot_name(&fixture));
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("no snapshot for {}: {}", fixture, err));
+        assert!(
+            testing::snapshot(&fixture) == expected,
+            "{} no longer matches {}",
+            fixture,
+            path.display()
+        );
+    }
+}
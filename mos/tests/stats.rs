@@ -0,0 +1,32 @@
+use chrono::{TimeZone, Utc};
+
+use mos::{testing, MOS};
+
+#[test]
+fn sums_up_the_forecast_period() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let stats = mos.stats();
+
+    let temperature = stats.temperature.unwrap();
+    assert_eq!(temperature.min, 16);
+    assert_eq!(temperature.max, 35);
+    assert!((temperature.mean - 560.0 / 21.0).abs() < 1e-9);
+
+    let peak = stats.peak_wind.unwrap();
+    assert_eq!(peak.speed, 22);
+    assert_eq!(peak.direction, Some(30));
+    assert_eq!(peak.timestamp, Utc.ymd(2020, 4, 13).and_hms(0, 0, 0));
+
+    // Six P06 periods of 50% or more. The 95% P12 ending 00Z on the 15th doesn't count
+    // for the hours its 5% P06 covers
+    assert_eq!(stats.pop_50_hours, 36);
+}
+
+#[test]
+fn is_empty_without_entries() {
+    let cycle = Utc.ymd(2020, 4, 12).and_hms(12, 0, 0);
+    let stats = MOS::from_entries("KFIT", cycle, Vec::new()).stats();
+    assert_eq!(stats.temperature, None);
+    assert_eq!(stats.peak_wind, None);
+    assert_eq!(stats.pop_50_hours, 0);
+}
@@ -0,0 +1,40 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use mos::storage;
+use mos::testing;
+
+#[test]
+fn keys_sort_by_cycle_then_valid_time() {
+    let cycle = Utc.ymd(2020, 4, 12).and_hms(12, 0, 0);
+    let valid = Utc.ymd(2020, 4, 13).and_hms(0, 0, 0);
+    assert_eq!(
+        storage::sort_key(cycle, valid),
+        "2020-04-12T12:00:00Z#2020-04-13T00:00:00Z"
+    );
+    assert_eq!(
+        storage::valid_key("kfit", valid),
+        "KFIT#2020-04-13T00:00:00Z"
+    );
+    assert!(
+        storage::sort_key(cycle, valid)
+            < storage::sort_key(cycle + Duration::hours(6), cycle + Duration::hours(6))
+    );
+}
+
+#[test]
+fn round_trips_cycles() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let mut next = mos.clone();
+    next.meta.timestamp = mos.meta.timestamp + Duration::hours(6);
+
+    let mut stored = next.to_stored();
+    stored.extend(mos.to_stored());
+    assert_eq!(mos.to_stored()[0].lead_hours(), 6);
+
+    let cycles = storage::into_cycles(stored);
+    assert_eq!(cycles.len(), 2);
+    assert_eq!(cycles[0].meta.timestamp, mos.meta.timestamp);
+    assert_eq!(cycles[1].meta.timestamp, next.meta.timestamp);
+    assert_eq!(cycles[0].entries.len(), mos.entries.len());
+    assert_eq!(cycles[0].entries[3].tmp, mos.entries[3].tmp);
+}
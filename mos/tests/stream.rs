@@ -0,0 +1,50 @@
+use std::io::Cursor;
+
+use mos::testing;
+use mos::{parse_stream, MOS};
+
+#[test]
+fn yields_each_bulletin_of_a_collective() {
+    let collective = format!(
+        "000\nFOUS46 KWNO 121200\nMAVBOX\n{}",
+        testing::load("mav_collective.txt")
+    );
+    let expected: Vec<MOS> = testing::bulletins("mav_collective.txt")
+        .iter()
+        .map(|bulletin| MOS::new(bulletin).unwrap())
+        .collect();
+
+    let streamed: Vec<MOS> = parse_stream(Cursor::new(collective))
+        .map(|mos| mos.unwrap())
+        .collect();
+    assert_eq!(streamed.len(), expected.len());
+    for (streamed, expected) in streamed.iter().zip(&expected) {
+        assert_eq!(streamed.meta, expected.meta);
+        assert_eq!(streamed.entries, expected.entries);
+        assert_eq!(streamed.raw.trim_end(), expected.raw.trim_end());
+    }
+
+    // Line endings don't matter
+    let crlf = testing::load("mav_collective.txt").replace('\n', "\r\n");
+    let streamed: Vec<MOS> = parse_stream(Cursor::new(crlf))
+        .map(|mos| mos.unwrap())
+        .collect();
+    assert_eq!(streamed.len(), expected.len());
+    assert_eq!(streamed[0].entries, expected[0].entries);
+}
+
+#[test]
+fn goes_on_after_a_bulletin_that_does_not_parse() {
+    let text = format!(
+        "{}\n{}\n{}",
+        testing::load("kfit_mav.txt"),
+        testing::load("kfit_lamp.txt"),
+        testing::load("kbos_mav_00z.txt")
+    );
+
+    let results: Vec<_> = parse_stream(Cursor::new(text)).collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().meta.icao, "KFIT");
+    assert_eq!(results[1].as_ref().unwrap_err().code(), "E_PARSE_HOURLINE");
+    assert_eq!(results[2].as_ref().unwrap().meta.icao, "KBOS");
+}
@@ -0,0 +1,84 @@
+use chrono::{TimeZone, Utc};
+
+use mos::category::FlightCategory;
+use mos::taf::{ChangeKind, Taf};
+
+const KBOS: &str = "TAF KBOS 121130Z 1212/1318 28012G22KT P6SM BKN040 \
+    TEMPO 1214/1218 5SM -SHRA BKN025 \
+    FM122000 30008KT P6SM SCT050 \
+    BECMG 1302/1304 VRB03KT \
+    PROB30 TEMPO 1308/1312 2SM BR OVC008 \
+    FM131400 18010KT 3SM -RA OVC015";
+
+fn kbos() -> Taf {
+    Taf::parse_at(KBOS, Utc.ymd(2020, 4, 12).and_hms(11, 40, 0)).unwrap()
+}
+
+#[test]
+fn parses_change_groups() {
+    let taf = kbos();
+
+    assert_eq!(taf.icao, "KBOS");
+    assert_eq!(taf.issued, Utc.ymd(2020, 4, 12).and_hms(11, 30, 0));
+    assert_eq!(taf.valid_from, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(taf.valid_to, Utc.ymd(2020, 4, 13).and_hms(18, 0, 0));
+
+    let kinds: Vec<ChangeKind> = taf.groups.iter().map(|group| group.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ChangeKind::Base,
+            ChangeKind::Tempo,
+            ChangeKind::From,
+            ChangeKind::Becoming,
+            ChangeKind::Probability {
+                percent: 30,
+                tempo: true
+            },
+            ChangeKind::From,
+        ]
+    );
+
+    // Each FM group ends the prevailing group before it
+    assert_eq!(taf.groups[0].to, Utc.ymd(2020, 4, 12).and_hms(20, 0, 0));
+    assert_eq!(taf.groups[2].to, Utc.ymd(2020, 4, 13).and_hms(14, 0, 0));
+    assert_eq!(taf.groups[1].weather, vec!["-SHRA"]);
+    assert_eq!(taf.groups[4].ceiling(), Some(800));
+}
+
+#[test]
+fn prevailing_conditions() {
+    let taf = kbos();
+
+    let early = taf
+        .prevailing(Utc.ymd(2020, 4, 12).and_hms(15, 0, 0))
+        .unwrap();
+    assert_eq!(early.ceiling(), Some(4000));
+    assert_eq!(
+        taf.temporary(Utc.ymd(2020, 4, 12).and_hms(15, 0, 0)).len(),
+        1
+    );
+
+    let overnight = taf
+        .prevailing(Utc.ymd(2020, 4, 13).and_hms(6, 0, 0))
+        .unwrap();
+    assert_eq!(overnight.wind.unwrap().direction, None);
+    assert_eq!(overnight.ceiling(), None);
+
+    assert_eq!(
+        taf.flight_category(Utc.ymd(2020, 4, 13).and_hms(15, 0, 0)),
+        Some(FlightCategory::Mvfr)
+    );
+    assert_eq!(taf.prevailing(Utc.ymd(2020, 4, 13).and_hms(18, 0, 0)), None);
+}
+
+#[test]
+fn rolls_into_the_next_month() {
+    let taf = Taf::parse_at(
+        "TAF KFIT 302330Z 0100/0124 VRB03KT P6SM SKC",
+        Utc.ymd(2020, 4, 30).and_hms(23, 40, 0),
+    )
+    .unwrap();
+    assert_eq!(taf.valid_from, Utc.ymd(2020, 5, 1).and_hms(0, 0, 0));
+    assert_eq!(taf.valid_to, Utc.ymd(2020, 5, 2).and_hms(0, 0, 0));
+}
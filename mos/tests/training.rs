@@ -0,0 +1,44 @@
+use chrono::{TimeZone, Utc};
+
+use mos::metar::Metar;
+use mos::testing;
+use mos::training;
+
+#[test]
+fn joins_entries_with_observations() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let now = Utc.ymd(2020, 4, 13).and_hms(1, 0, 0);
+    let observations = vec![
+        Metar::parse_at("KFIT 121752Z 28004KT 10SM BKN008 00/M03 A3002", now).unwrap(),
+        Metar::parse_at("KFIT 122052Z 27012KT 10SM OVC030 03/M02 A2998", now).unwrap(),
+        Metar::parse_at("KFIT 130002Z 25020KT 3SM -RA OVC010 02/01 A2990", now).unwrap(),
+        Metar::parse_at("KBOS 121754Z 09010KT 10SM FEW250 10/M05 A3010", now).unwrap(),
+    ];
+
+    let rows = training::rows(&mos.to_stored(), &observations);
+    assert!(rows.iter().all(|row| row.station == "KFIT"));
+
+    let six = rows.iter().find(|row| row.lead_hours == 6).unwrap();
+    assert_eq!(six.cycle, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(six.valid, Utc.ymd(2020, 4, 12).and_hms(18, 0, 0));
+    assert_eq!(six.tmp, Some(35.0));
+    assert_eq!(six.observed_tmp, Some(32.0));
+    assert_eq!(six.observed_wdr, Some(280));
+    assert_eq!(six.observed_wsp, Some(4.0));
+    assert_eq!(six.observed_ceiling, Some(800));
+    assert_eq!(six.observed_visibility, Some(10.0));
+
+    let twelve = rows.iter().find(|row| row.lead_hours == 12).unwrap();
+    assert_eq!(twelve.observed_precip, Some(true));
+    assert_eq!(twelve.observed_ceiling, Some(1000));
+
+    // Nothing observed near the valid time or through a PoP period
+    assert!(rows.iter().all(|row| row.lead_hours != 15));
+
+    let csv = training::to_csv(&rows);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), rows.len() + 1);
+    assert!(lines[0].starts_with("station,cycle,valid,lead_hours,tmp,"));
+    assert!(lines[0].ends_with(",observed_precip"));
+    assert!(lines[1].starts_with("KFIT,2020-04-12T12:00:00+00:00,2020-04-12T18:00:00+00:00,6,35,"));
+}
@@ -0,0 +1,64 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use mos::testing;
+use mos::trend;
+
+#[test]
+fn follows_a_valid_time_across_cycles() {
+    let oldest = testing::parse("kfit_mav.txt").unwrap();
+    let valid = Utc.ymd(2020, 4, 13).and_hms(0, 0, 0);
+
+    let mut middle = oldest.clone();
+    middle.meta.timestamp = oldest.meta.timestamp + Duration::hours(6);
+    let mut newest = oldest.clone();
+    newest.meta.timestamp = oldest.meta.timestamp + Duration::hours(12);
+    for (mos, pop) in [(&mut middle, 40), (&mut newest, 70)].iter_mut() {
+        let entry = mos
+            .entries
+            .iter_mut()
+            .find(|e| e.timestamp == valid)
+            .unwrap();
+        entry.p06 = Some(*pop);
+    }
+    let mut early = oldest.clone();
+    early.meta.timestamp = oldest.meta.timestamp - Duration::hours(6);
+    early.entries.retain(|e| e.timestamp != valid);
+
+    let cycles = vec![newest, early, oldest, middle];
+    let pop = trend::trend(&cycles, valid, "p06").unwrap();
+    assert_eq!(pop.values.len(), 3);
+    assert_eq!(pop.change(), Some(35));
+    assert_eq!(pop.converging(), Some(false));
+    assert_eq!(
+        pop.to_string(),
+        "Mon 00Z P06: 35 → 40 → 70 over the last 3 runs"
+    );
+
+    let tmp = trend::trend(&cycles, valid, "TMP").unwrap();
+    assert_eq!(tmp.change(), Some(0));
+    assert!(trend::trends(&cycles, valid)
+        .iter()
+        .all(|t| t.element != "SNW"));
+}
+
+#[test]
+fn wind_direction_changes_the_short_way_around() {
+    let older = testing::parse("kfit_mav.txt").unwrap();
+    let valid = older.entries[0].timestamp;
+    let mut newer = older.clone();
+    newer.meta.timestamp = older.meta.timestamp + Duration::hours(6);
+    let mut older = older;
+    older.entries[0].wdr = Some(35);
+    newer.entries[0].wdr = Some(1);
+
+    let wdr = trend::trend(&[older.clone(), newer.clone()], valid, "WDR").unwrap();
+    assert_eq!(wdr.change(), Some(2));
+
+    let diff = older.diff(&newer);
+    let change = diff[0]
+        .changes
+        .iter()
+        .find(|change| change.element == "WDR")
+        .unwrap();
+    assert_eq!(change.delta, Some(2));
+}
@@ -0,0 +1,41 @@
+use chrono::{TimeZone, Utc};
+
+use mos::metar::Metar;
+use mos::testing;
+use mos::verify;
+
+#[test]
+fn scores_by_lead_time() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let now = Utc.ymd(2020, 4, 13).and_hms(1, 0, 0);
+    let observations = vec![
+        Metar::parse_at("KFIT 121752Z 28004KT 10SM BKN008 00/M03 A3002", now).unwrap(),
+        Metar::parse_at("KFIT 122052Z 27012KT 10SM OVC030 03/M02 A2998", now).unwrap(),
+        Metar::parse_at("KFIT 130002Z 25020KT 3SM -RA OVC010 02/01 A2990", now).unwrap(),
+        Metar::parse_at("KBOS 121754Z 09010KT 10SM FEW250 10/M05 A3010", now).unwrap(),
+    ];
+
+    let stats = verify::verify(&mos.to_stored(), &observations);
+    assert!(stats.iter().all(|s| s.station == "KFIT"));
+
+    let six = stats.iter().find(|s| s.lead_hours == 6).unwrap();
+    let temperature = six.temperature.unwrap();
+    assert_eq!(temperature.count, 1);
+    assert_eq!(temperature.mae, 3.0);
+    assert_eq!(temperature.bias, 3.0);
+    assert_eq!(six.wind_speed.unwrap().bias, -3.0);
+
+    let nine = stats.iter().find(|s| s.lead_hours == 9).unwrap();
+    assert_eq!(nine.wind_speed.unwrap().mae, 3.0);
+
+    let twelve = stats.iter().find(|s| s.lead_hours == 12).unwrap();
+    let pop = twelve.pop.unwrap();
+    assert_eq!(pop.count, 1);
+    assert!((pop.score - 0.4225).abs() < 1e-9);
+    assert_eq!(twelve.wind_speed.unwrap().bias, 2.0);
+
+    // Nothing observed near the later valid times
+    let later = stats.iter().find(|s| s.lead_hours == 15).unwrap();
+    assert_eq!(later.temperature, None);
+    assert_eq!(later.pop, None);
+}
@@ -0,0 +1,82 @@
+use chrono::{TimeZone, Utc};
+
+use mos::client::Response;
+use mos::testing::FnTransport;
+use mos::weather_gov;
+
+const POINT: &str = r#"{
+    "properties": {
+        "gridId": "BOX",
+        "gridX": 71,
+        "gridY": 90,
+        "forecastHourly": "https://api.weather.gov/gridpoints/BOX/71,90/forecast/hourly"
+    }
+}"#;
+
+const HOURLY: &str = r#"{
+    "properties": {
+        "updateTime": "2020-04-12T13:41:20+00:00",
+        "periods": [
+            {
+                "number": 1,
+                "startTime": "2020-04-12T10:00:00-04:00",
+                "temperature": 41,
+                "temperatureUnit": "F",
+                "dewpoint": {"unitCode": "wmoUnit:degC", "value": 2.2222},
+                "windSpeed": "5 to 10 mph",
+                "windDirection": "NW",
+                "shortForecast": "Cloudy"
+            },
+            {
+                "number": 2,
+                "startTime": "2020-04-12T11:00:00-04:00",
+                "temperature": 43,
+                "temperatureUnit": "F",
+                "windSpeed": "0 mph",
+                "windDirection": "",
+                "shortForecast": "Cloudy"
+            }
+        ]
+    }
+}"#;
+
+#[test]
+fn converts_the_hourly_forecast() {
+    let transport = FnTransport(|url: &str| {
+        let body = match url {
+            "https://api.weather.gov/points/42.5541,-71.7590" => POINT,
+            "https://api.weather.gov/gridpoints/BOX/71,90/forecast/hourly" => HOURLY,
+            _ => panic!("unexpected request {}", url),
+        };
+        Ok(Response {
+            status: 200,
+            body: body.to_string(),
+        })
+    });
+
+    let mos = weather_gov::fetch_hourly_for(&transport, "KFIT").unwrap();
+    assert_eq!(mos.meta.icao, "KFIT");
+    assert_eq!(mos.meta.timestamp, Utc.ymd(2020, 4, 12).and_hms(13, 41, 20));
+    assert_eq!(mos.entries.len(), 2);
+
+    let first = &mos.entries[0];
+    assert_eq!(first.timestamp, Utc.ymd(2020, 4, 12).and_hms(14, 0, 0));
+    assert_eq!(first.tmp, Some(41));
+    assert_eq!(first.dpt, Some(36));
+    assert_eq!((first.wdr, first.wsp), (Some(32), Some(9)));
+
+    let calm = &mos.entries[1];
+    assert_eq!((calm.wdr, calm.wsp), (Some(0), Some(0)));
+}
+
+#[test]
+fn reports_api_errors() {
+    let transport = FnTransport(|_: &str| {
+        Ok(Response {
+            status: 404,
+            body: String::new(),
+        })
+    });
+    let err = weather_gov::fetch_hourly(&transport, 0.0, 0.0).unwrap_err();
+    assert_eq!(err.code(), "E_UPSTREAM_STATUS");
+}
@@ -0,0 +1,49 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use mos::notify::webhook::{Dispatcher, Trigger};
+use mos::testing;
+
+const HOOKS: &str = r#"[
+    {"url": "https://example.com/cycles", "triggers": [{"type": "new_cycle"}]},
+    {"url": "https://example.com/wind", "triggers": [{"type": "threshold", "element": "wsp", "above": 20}]}
+]"#;
+
+#[test]
+fn fires_on_new_cycles_and_thresholds() {
+    let mut dispatcher = Dispatcher::from_json(HOOKS).unwrap();
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+
+    let due = dispatcher.evaluate(&mos);
+    assert_eq!(due.len(), 2);
+    assert_eq!(due[0].0, "https://example.com/cycles");
+    assert_eq!(due[0].1.trigger, Trigger::NewCycle);
+    assert_eq!(due[0].1.station, "KFIT");
+
+    let (url, wind) = &due[1];
+    assert_eq!(url, "https://example.com/wind");
+    assert_eq!(wind.valid, Some(Utc.ymd(2020, 4, 13).and_hms(0, 0, 0)));
+    assert_eq!(wind.value, Some(22));
+
+    // The same cycle again is a no-op
+    assert!(dispatcher.evaluate(&mos).is_empty());
+
+    // A newer cycle still crossing the threshold only announces the cycle
+    let mut next = mos.clone();
+    next.meta.timestamp = mos.meta.timestamp + Duration::hours(6);
+    let due = dispatcher.evaluate(&next);
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].1.trigger, Trigger::NewCycle);
+}
+
+#[test]
+fn serializes_the_payload() {
+    let mut dispatcher = Dispatcher::from_json(HOOKS).unwrap();
+    let due = dispatcher.evaluate(&testing::parse("kfit_mav.txt").unwrap());
+    let json = serde_json::to_value(&due[1].1).unwrap();
+
+    assert_eq!(json["station"], "KFIT");
+    assert_eq!(json["cycle"], "2020-04-12T12:00:00Z");
+    assert_eq!(json["trigger"]["type"], "threshold");
+    assert_eq!(json["trigger"]["element"], "wsp");
+    assert_eq!(json["value"], 22);
+}
@@ -0,0 +1,76 @@
+use chrono::{TimeZone, Utc};
+
+use mos::winds_aloft::{LevelWind, WindsAloft};
+
+const FB: &str = "\
+000
+FBUS31 KWNO 121359
+FD1US1
+DATA BASED ON 121200Z
+VALID 121800Z   FOR USE 1400-2100Z. TEMPS NEG ABV 24000
+
+FT  3000    6000    9000   12000   18000   24000  30000  34000  39000
+ALB 2714 2725-04 2735-09 2745-14 2858-26 2870-37 763748 772554 771563
+BOS 2916 3124-06 3136-10 3244-15 3263-27 3278-39 329953 820957 318062
+DEN              2310+05 2420-01 2535-15 2647-27 265943 266652 266760
+MIA 9900 0905+14 1110+09 1415+04 2020-07 2230-18 244033 245142 246453
+";
+
+fn fb() -> WindsAloft {
+    WindsAloft::parse_at(FB, Utc.ymd(2020, 4, 12).and_hms(14, 0, 0)).unwrap()
+}
+
+#[test]
+fn parses_the_header() {
+    let fb = fb();
+    assert_eq!(fb.based_on, Utc.ymd(2020, 4, 12).and_hms(12, 0, 0));
+    assert_eq!(fb.valid, Utc.ymd(2020, 4, 12).and_hms(18, 0, 0));
+    assert_eq!(fb.use_from, Utc.ymd(2020, 4, 12).and_hms(14, 0, 0));
+    assert_eq!(fb.use_to, Utc.ymd(2020, 4, 12).and_hms(21, 0, 0));
+    assert_eq!(
+        fb.levels,
+        vec![3000, 6000, 9000, 12000, 18000, 24000, 30000, 34000, 39000]
+    );
+    assert_eq!(fb.stations.len(), 4);
+}
+
+#[test]
+fn decodes_cells() {
+    let fb = fb();
+    let bos = fb.station("BOS").unwrap();
+    assert_eq!(
+        bos.at(3000),
+        Some(&LevelWind {
+            altitude: 3000,
+            direction: Some(290),
+            speed: 16,
+            temperature: None,
+        })
+    );
+    assert_eq!(bos.at(6000).unwrap().temperature, Some(-6));
+    // 82 encodes 320° with 100 kt added to the speed
+    let fl340 = bos.at(34000).unwrap();
+    assert_eq!((fl340.direction, fl340.speed), (Some(320), 109));
+    assert_eq!(fl340.temperature, Some(-57));
+
+    let mia = fb.station("MIA").unwrap();
+    assert_eq!(mia.at(3000).unwrap().direction, None);
+    assert_eq!(mia.at(6000).unwrap().temperature, Some(14));
+}
+
+#[test]
+fn skips_levels_below_the_station() {
+    let fb = fb();
+    let den = fb.station("DEN").unwrap();
+    assert_eq!(den.at(3000), None);
+    assert_eq!(den.at(6000), None);
+    assert_eq!(den.at(9000).unwrap().temperature, Some(5));
+}
+
+#[test]
+fn picks_the_nearest_fd_station() {
+    let fb = fb();
+    assert_eq!(fb.for_airport("KBED").unwrap().id, "BOS");
+    assert_eq!(fb.for_airport("KASE").unwrap().id, "DEN");
+    assert!(fb.for_airport("KXXX").is_none());
+}
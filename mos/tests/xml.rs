@@ -0,0 +1,17 @@
+#![cfg(feature = "quick-xml")]
+
+use mos::testing;
+
+#[test]
+fn writes_an_entry_per_valid_time() {
+    let mos = testing::parse("kfit_mav.txt").unwrap();
+    let xml = mos.to_xml().unwrap();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xml.contains("<mos icao=\"KFIT\" cycle=\"2020-04-12T12:00:00+00:00\">"));
+    assert_eq!(xml.matches("<entry valid=").count(), mos.entries.len());
+    assert!(xml.contains("<tmp>35</tmp>"));
+    assert!(xml.contains("<cld>FW</cld>"));
+    assert!(xml.contains("<t06 thunder=\"13\" severe=\"2\"/>"));
+    assert!(xml.trim_end().ends_with("</mos>"));
+}